@@ -15,12 +15,22 @@ pub mod entity;
 pub mod direction;
 pub mod generic_list;
 pub mod generic;
+pub mod generic_policy;
 pub mod port_list;
 pub mod port;
 pub mod entity_interface;
+pub mod interface_part;
+pub mod role_view;
+pub mod interface_package;
+pub mod record_type;
+pub mod interface_catalog;
+pub mod interface_registry;
+pub mod interface_inference;
 pub mod entity_interface_binding_list;
 pub mod entity_interface_binding;
 pub mod generic_binding;
+pub mod actual;
+pub mod association;
 pub mod port_binding;
 
 pub mod architecture;
@@ -35,6 +45,21 @@ pub mod process;
 pub mod sensitivity_list;
 pub mod process_declarative_item;
 pub mod variable_declaration;
+pub mod sequential_statement;
+pub mod if_statement;
 
 pub mod match_index;
+pub mod prefix_policy;
+pub mod port_mapping;
+pub mod matcher;
+pub mod connection_report;
+pub mod connection_plan;
+pub mod auto_connect;
+pub mod expression;
+pub mod data_type;
+pub mod netlist;
+pub mod interconnect;
+
+pub mod lint_finding;
+pub mod lint;
 
@@ -0,0 +1,120 @@
+use crate::element::Element;
+use crate::vhdl::expression::{ get_base_identifier, get_identifiers, get_parenthesized };
+
+/**
+ * The actual part of an association in a generic or port map.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Actual {
+    /// Signal, port, generic or constant name
+    Name( String ),
+    /// Slice or indexed element of a name, e.g. `data( 7 downto 0 )`
+    Indexed( String, String ),
+    /// Literal value like `'0'`, `32` or `(others => '0')`
+    Literal( String ),
+    /// Any other expression like a conversion function call
+    Expression( String ),
+    Open,
+}
+
+impl Actual {
+    pub fn name( name : & str ) -> Actual {
+        Actual::Name( name.to_string() )
+    }
+
+    pub fn indexed( name : & str, index : & str ) -> Actual {
+        Actual::Indexed( name.to_string(), index.to_string() )
+    }
+
+    pub fn literal( value : & str ) -> Actual {
+        Actual::Literal( value.to_string() )
+    }
+
+    pub fn expression( expression : & str ) -> Actual {
+        Actual::Expression( expression.to_string() )
+    }
+
+    pub fn function_call( function : & str, argument : & str ) -> Actual {
+        Actual::Expression( format!( "{}( {} )", function, argument ) )
+    }
+
+    /**
+     * Name of the signal or port the actual is attached to, if any.
+     */
+    pub fn get_net( & self ) -> Option< String > {
+        match self {
+            Actual::Name( name ) | Actual::Indexed( name, _ ) => get_base_identifier( name ),
+            _ => None,
+        }
+    }
+
+    /**
+     * Index or range of the net the actual is attached to, `None` for the whole net.
+     */
+    pub fn get_net_range( & self ) -> Option< String > {
+        match self {
+            Actual::Name( name ) => get_parenthesized( name ),
+            Actual::Indexed( _, index ) => Some( index.trim().to_string() ),
+            _ => None,
+        }
+    }
+
+    /**
+     * Identifiers read by the actual including those used in indices and expressions.
+     */
+    pub fn get_identifiers( & self ) -> Vec< String > {
+        match self {
+            Actual::Open => Vec::new(),
+            _ => get_identifiers( & self.to_source_code( 0 ) ),
+        }
+    }
+
+    pub fn is_open( & self ) -> bool {
+        matches!( self, Actual::Open )
+    }
+
+    /**
+     * Only names and slices of names can be associated with an output.
+     */
+    pub fn is_writable( & self ) -> bool {
+        matches!( self, Actual::Name( _ ) | Actual::Indexed( _, _ ) | Actual::Open )
+    }
+}
+
+impl Element for Actual {
+    fn to_source_code( & self, _indent : usize ) -> String {
+        match self {
+            Actual::Name( name ) => name.clone(),
+            Actual::Indexed( name, index ) => format!( "{}( {} )", name, index ),
+            Actual::Literal( value ) => value.clone(),
+            Actual::Expression( expression ) => expression.clone(),
+            Actual::Open => crate::vhdl::keywords::OPEN.to_string(),
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_source_code() {
+        assert_eq!( Actual::name( "a" ).to_source_code( 0 ), "a" );
+        assert_eq!( Actual::indexed( "data", "7 downto 0" ).to_source_code( 0 ), "data( 7 downto 0 )" );
+        assert_eq!( Actual::literal( "(others => '0')" ).to_source_code( 0 ), "(others => '0')" );
+        assert_eq!( Actual::function_call( "to_integer", "count" ).to_source_code( 0 ),
+                "to_integer( count )" );
+        assert_eq!( Actual::Open.to_source_code( 0 ), "open" );
+    }
+
+    #[test]
+    fn net() {
+        assert_eq!( Actual::indexed( "data", "7 downto 0" ).get_net(), Some( "data".to_string() ) );
+        assert_eq!( Actual::literal( "'0'" ).get_net(), None );
+        assert_eq!( Actual::indexed( "data", "7 downto 0" ).get_net_range(), Some( "7 downto 0".to_string() ) );
+        assert_eq!( Actual::name( "data" ).get_net_range(), None );
+        assert_eq!( Actual::function_call( "to_integer", "count" ).get_identifiers(),
+                vec![ "to_integer", "count" ] );
+    }
+}
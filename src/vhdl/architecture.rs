@@ -12,24 +12,61 @@ use crate::vhdl::concurrent_statement::ConcurrentStatement;
 use crate::vhdl::signal_assignment::SignalAssignment;
 use crate::vhdl::instance::Instance;
 use crate::vhdl::process::Process;
-use crate::vhdl::entity_interface_binding::EntityInterfaceBinding;
+use crate::vhdl::entity_interface::EntityInterface;
+use crate::vhdl::entity_interface_binding::{ EntityInterfaceBinding, get_entity_members };
 use crate::vhdl::generic::Generic;
 use crate::vhdl::generic_binding::GenericBinding;
 use crate::vhdl::match_index::MatchIndex;
+use crate::vhdl::matcher::{ Matcher, HeuristicMatcher };
+use crate::vhdl::connection_report::*;
+use crate::vhdl::connection_plan::{ ConnectionPlan, PlannedBinding };
+use crate::vhdl::auto_connect::{ LeftoverPolicy, get_max_weight_matching };
+use crate::vhdl::direction::Direction;
+use crate::vhdl::netlist::Netlist;
+use crate::vhdl::actual::Actual;
+use crate::vhdl::port::Port;
+use crate::vhdl::prefix_policy::PrefixPolicy;
+use crate::vhdl::port_mapping::PortMapping;
+use crate::vhdl::expression::{ get_identifiers, substitute_identifiers };
 
 pub struct Architecture {
     name : String,
     entity : Entity,
     declarations : BlockDeclarativeList,
     instances : LinkedHashMap< String, Instance >,
-    statements : Vec< Box< dyn ConcurrentStatement > >
+    statements : Vec< Box< dyn ConcurrentStatement > >,
+    matcher : Box< dyn Matcher >,
+    tie_break : TieBreak,
+    bundles : Vec< EntityInterface >,
+    shared_signals : Vec< String >,
 }
 
 impl Architecture {
     pub fn new( name : & str, entity : & Entity ) -> Architecture {
         Architecture { name : name.to_string(), entity : ( * entity ).clone(),
                 declarations : BlockDeclarativeList::new(), instances : LinkedHashMap::new(),
-                statements : Vec::new() }
+                statements : Vec::new(), matcher : Box::new( HeuristicMatcher ),
+                tie_break : TieBreak::Error, bundles : Vec::new(), shared_signals : Vec::new() }
+    }
+
+    /**
+     * Replaces the strategy used to pair interfaces when connecting automatically, the default
+     * is `HeuristicMatcher`.
+     */
+    pub fn set_matcher( & mut self, matcher : Box< dyn Matcher > ) {
+        self.matcher = matcher;
+    }
+
+    pub fn get_matcher( & self ) -> & dyn Matcher {
+        self.matcher.as_ref()
+    }
+
+    /**
+     * Sets how automatic connections resolve equally good candidates, the default is
+     * `TieBreak::Error`.
+     */
+    pub fn set_tie_break( & mut self, tie_break : TieBreak ) {
+        self.tie_break = tie_break;
     }
 
     pub fn add_constant_declaration( & mut self, constant : & ConstantDeclaration ) {
@@ -61,47 +98,92 @@ impl Architecture {
         self.statements.push( Box::< Process >::new( process ) );
     }
 
-    pub fn connect_instance_to_entity( & mut self, name : & str ) -> Result< (), VhdlError > {
-        println!( "Architecture::connect_instance_to_entity" );
-        self.requires_instance( name )?;
-        self.instances.get_mut( name ).unwrap().connect_to_entity( & self.entity );
-        Ok(())
+    pub fn get_name( & self ) -> & String {
+        & self.name
     }
 
-    pub fn connect_instance_to_instance( & mut self, inst_name_a : & str, inst_name_b: & str )
-            -> Result< (), VhdlError > {
-        println!( "Architecture::connect_instance_to_instance {} {}", inst_name_a, inst_name_b );
-        let matches;
-        {
-            self.requires_instance( inst_name_a )?;
-            self.requires_instance( inst_name_b )?;
-            let inst_a : & Instance = self.instances.get( inst_name_a ).unwrap();
-            let inst_b : & Instance = self.instances.get( inst_name_b ).unwrap();
-            // find a list of matching interfaces
-            matches = inst_a.get_instance_interface_matches( & inst_b );
-        }
-        let mut connection_signal_lists : Vec< Vec< SignalDeclaraion > > = Vec::new();
-        {
-            for ( a, _ ) in & matches {
-                let inst : & mut Instance = self.instances.get_mut( inst_name_a ).unwrap();
-                let interface_a : & EntityInterfaceBinding = & inst.get_interfaces()[ * a ];
-                let signal_list = interface_a.get_connection_signal_list( inst_name_a, inst_name_b );
-                inst.connect_interface_by_index_to_signal_list( * a, & signal_list );
-                connection_signal_lists.push( signal_list );
-            }
+    pub fn get_entity( & self ) -> & Entity {
+        & self.entity
+    }
+
+    pub fn get_declarations( & self ) -> & BlockDeclarativeList {
+        & self.declarations
+    }
+
+    pub fn get_instances( & self ) -> & LinkedHashMap< String, Instance > {
+        & self.instances
+    }
+
+    pub fn get_statements( & self ) -> & Vec< Box< dyn ConcurrentStatement > > {
+        & self.statements
+    }
+
+    pub fn get_netlist( & self ) -> Netlist {
+        Netlist::from_architecture( self )
+    }
+
+    pub fn connect_instance_to_entity( & mut self, name : & str )
+            -> Result< ConnectionReport, VhdlError > {
+        let plan = self.plan_instance_to_entity( name )?;
+        self.apply( & plan )?;
+        Ok( plan.get_report().clone() )
+    }
+
+    /**
+     * Plans binding the interfaces of an instance to the entity interfaces without changing the
+     * architecture, see `apply`.
+     */
+    pub fn plan_instance_to_entity( & self, name : & str ) -> Result< ConnectionPlan, VhdlError > {
+        let inst : & Instance = self.get_instance( name )?;
+        let report = inst.get_entity_connection_report( & self.entity, self.matcher.as_ref() );
+        report.requires_no_ties( self.tie_break )?;
+        let mut plan = ConnectionPlan::new();
+        for pair in report.get_pairs() {
+            plan.add_binding( PlannedBinding::EntityInterface { instance : name.to_string(),
+                    interface : pair.get_source_index(), name : pair.get_source().clone(),
+                    entity_interface : pair.get_target_index(), entity_name : pair.get_target().clone() } );
         }
-        {
-            for ( i, ( _, b ) ) in matches.iter().enumerate() {
-                let inst : & mut Instance = self.instances.get_mut( inst_name_b ).unwrap();
-                inst.connect_interface_by_index_to_signal_list( * b, & connection_signal_lists[ i ] );
+        plan.set_report( report );
+        Ok( plan )
+    }
+
+    pub fn connect_instance_to_instance( & mut self, inst_name_a : & str, inst_name_b: & str )
+            -> Result< ConnectionReport, VhdlError > {
+        let plan = self.plan_instance_to_instance( inst_name_a, inst_name_b )?;
+        self.apply( & plan )?;
+        Ok( plan.get_report().clone() )
+    }
+
+    /**
+     * Plans the signals and bindings connecting matching interfaces of two instances.
+     */
+    pub fn plan_instance_to_instance( & self, inst_name_a : & str, inst_name_b: & str )
+            -> Result< ConnectionPlan, VhdlError > {
+        let inst_a : & Instance = self.get_instance( inst_name_a )?;
+        let inst_b : & Instance = self.get_instance( inst_name_b )?;
+        // find a list of matching interfaces
+        let report = inst_a.get_instance_connection_report( inst_b, self.matcher.as_ref() );
+        report.requires_no_ties( self.tie_break )?;
+        let mut plan = ConnectionPlan::new();
+        for pair in report.get_pairs() {
+            let interface_a : & EntityInterfaceBinding = & inst_a.get_interfaces()[ pair.get_source_index() ];
+            let interface_b : & EntityInterfaceBinding = & inst_b.get_interfaces()[ pair.get_target_index() ];
+            let names = interface_a.get_connection_names( interface_b, inst_name_a, inst_name_b )?;
+            plan.add_binding( PlannedBinding::SignalList { instance : inst_name_a.to_string(),
+                    interface : pair.get_source_index(), name : pair.get_source().clone(),
+                    signals : names.names_a } );
+            plan.add_binding( PlannedBinding::SignalList { instance : inst_name_b.to_string(),
+                    interface : pair.get_target_index(), name : pair.get_target().clone(),
+                    signals : names.names_b } );
+            for signal in & names.signals {
+                plan.add_signal( signal );
             }
-        }
-        {
-            for signal_list in & connection_signal_lists {
-                self.add_signal_declaration_list( signal_list );
+            for signal in & names.shared {
+                plan.add_shared_signal( signal );
             }
         }
-        Ok(())
+        plan.set_report( report );
+        Ok( plan )
     }
 
     pub fn connect_instance_to_instance_by_interface( & mut self,
@@ -124,53 +206,660 @@ impl Architecture {
             }
             let interface_a = inst_a.get_interface_by_name( if_name_a ).unwrap();
             let interface_b = inst_b.get_interface_by_name( if_name_b ).unwrap();
-            match_index = interface_a.get_instance_matching( interface_b );
+            match_index = interface_a.get_instance_matching( interface_b, self.matcher.as_ref() );
             println!( "match_index : {}", match_index );
             if match_index == crate::vhdl::match_index::NONE {
                 return Err( VhdlError::new( & format!( "error: Interface classes of {:?} and {:?} do not match!",
                         if_name_a, if_name_b ) ) );
             }
+            if ! interface_a.check_complementary( interface_b )? {
+                return Err( VhdlError::new( & format!( "error: Interfaces {:?} and {:?} have no complementary directions!",
+                        if_name_a, if_name_b ) ) );
+            }
         }
-        let connection_signal_list : Vec< SignalDeclaraion >;
-        {
-            let inst : & mut Instance = self.instances.get_mut( inst_name_a ).unwrap();
-            let interface_a : & EntityInterfaceBinding = & inst.get_interface_by_name( if_name_a ).unwrap();
-            let signal_list = interface_a.get_connection_signal_list( inst_name_a, inst_name_b );
-            inst.connect_interface_by_name_to_signal_list( if_name_a, & signal_list );
-            connection_signal_list = signal_list;
+        let names = {
+            let inst_a : & Instance = self.instances.get( inst_name_a ).unwrap();
+            let inst_b : & Instance = self.instances.get( inst_name_b ).unwrap();
+            inst_a.get_interface_by_name( if_name_a ).unwrap().get_connection_names(
+                    inst_b.get_interface_by_name( if_name_b ).unwrap(), inst_name_a, inst_name_b )?
+        };
+        self.requires_free_shared_signals( & names.shared, & names.signals )?;
+        for ( inst_name, if_name, signals ) in [ ( inst_name_a, if_name_a, & names.names_a ),
+                ( inst_name_b, if_name_b, & names.names_b ) ] {
+            let inst : & mut Instance = self.instances.get_mut( inst_name ).unwrap();
+            let idx = inst.get_interfaces().iter().position( | i | i.get_name() == if_name ).unwrap();
+            inst.connect_interface_by_index_to_names( idx, signals )?;
         }
-        {
-            let inst : & mut Instance = self.instances.get_mut( inst_name_b ).unwrap();
-            inst.connect_interface_by_name_to_signal_list( if_name_b, & connection_signal_list );
+        self.add_signal_declaration_list( & names.signals );
+        self.add_shared_signal_declarations( & names.shared );
+        Ok(())
+    }
+
+    /**
+     * Connects an instance interface to an interface of the entity by member names.  Members
+     * with different names are given in `mapping`, any unmatched member is an error.
+     */
+    pub fn connect_instance_to_entity_by_mapping( & mut self, instance : & str, if_name : & str,
+            entity_if_name : & str, mapping : & PortMapping ) -> Result< (), VhdlError > {
+        let entity_interface = match self.entity.get_interfaces().iter()
+                .find( | i | i.get_name() == entity_if_name ) {
+            Some( interface ) => interface.clone(),
+            None => return Err( VhdlError::new( & format!(
+                    "error: Interface {:?} not found in entity {:?}!", entity_if_name,
+                    self.entity.get_name() ) ) ),
+        };
+        let inst : & Instance = self.get_instance( instance )?;
+        self.requires_interface_in_instance( inst, if_name )?;
+        let inst : & mut Instance = self.get_instance_mut( instance )?;
+        inst.get_interface_by_name_mut( if_name ).unwrap()
+                .connect_to_entity_interface_by_mapping( & entity_interface, mapping )
+    }
+
+    /**
+     * Connects two instance interfaces by member names with signals named like in
+     * `connect_instance_to_instance`.  Members with different names are given in `mapping` from
+     * `if_name_a` to `if_name_b`, any unmatched member or non-complementary direction is an error.
+     */
+    pub fn connect_instance_to_instance_by_mapping( & mut self,
+            inst_name_a : & str, inst_name_b: & str,
+            if_name_a : & str, if_name_b : & str, mapping : & PortMapping )
+            -> Result< (), VhdlError > {
+        let plan = self.plan_instance_to_instance_by_mapping( inst_name_a, inst_name_b, if_name_a, if_name_b,
+                mapping )?;
+        self.apply( & plan )
+    }
+
+    pub fn plan_instance_to_instance_by_mapping( & self,
+            inst_name_a : & str, inst_name_b: & str,
+            if_name_a : & str, if_name_b : & str, mapping : & PortMapping )
+            -> Result< ConnectionPlan, VhdlError > {
+        let inst_a : & Instance = self.get_instance( inst_name_a )?;
+        let inst_b : & Instance = self.get_instance( inst_name_b )?;
+        self.requires_interface_in_instance( inst_a, if_name_a )?;
+        self.requires_interface_in_instance( inst_b, if_name_b )?;
+        let idx_a = inst_a.get_interfaces().iter().position( | i | i.get_name() == if_name_a ).unwrap();
+        let idx_b = inst_b.get_interfaces().iter().position( | i | i.get_name() == if_name_b ).unwrap();
+        let interface_a = & inst_a.get_interfaces()[ idx_a ];
+        let interface_b = & inst_b.get_interfaces()[ idx_b ];
+        for ( inst_name, interface ) in [ ( inst_name_a, interface_a ), ( inst_name_b, interface_b ) ] {
+            if interface.is_bound() {
+                return Err( VhdlError::new( & format!( "error: Interface {:?} of instance {:?} is already bound!",
+                        interface.get_name(), inst_name ) ) );
+            }
         }
-        {
-            self.add_signal_declaration_list( & connection_signal_list );
+        let pairs = interface_a.get_instance_port_pairs( interface_b, mapping )?;
+        let signal_list = interface_a.get_connection_signal_list( inst_name_a, inst_name_b );
+        let mut names_a : Vec< String > = Vec::new();
+        let mut names_b = vec![ String::new(); interface_b.get_ports().len() ];
+        let mut names : Vec< String > = Vec::new();
+        for ( signal, idx ) in signal_list.iter().zip( & pairs ) {
+            self.requires_new_identifier( signal.get_name(), & mut names )?;
+            names_a.push( signal.get_name().clone() );
+            names_b[ * idx ] = signal.get_name().clone();
         }
-        Ok(())
+        let mut pair = ConnectionPair::new( inst_name_a, if_name_a, inst_name_b, if_name_b,
+                crate::vhdl::match_index::FULL );
+        pair.set_indices( idx_a, idx_b );
+        let mut report = ConnectionReport::new();
+        report.add_pair( pair );
+        let mut plan = ConnectionPlan::new();
+        plan.add_binding( PlannedBinding::SignalList { instance : inst_name_a.to_string(), interface : idx_a,
+                name : if_name_a.to_string(), signals : names_a } );
+        plan.add_binding( PlannedBinding::SignalList { instance : inst_name_b.to_string(), interface : idx_b,
+                name : if_name_b.to_string(), signals : names_b } );
+        for signal in & signal_list {
+            plan.add_signal( signal );
+        }
+        plan.set_report( report );
+        Ok( plan )
     }
 
+    /**
+     * Binds the unbound generics of an instance to the best matching entity generics.  Several
+     * equally good entity generics are an error unless the tie-break policy takes the first.
+     */
     pub fn connect_instance_unbound_by_name( & mut self, instance : & str )
+            -> Result< ConnectionReport, VhdlError > {
+        let plan = self.plan_instance_unbound_by_name( instance )?;
+        self.apply( & plan )?;
+        Ok( plan.get_report().clone() )
+    }
+
+    pub fn plan_instance_unbound_by_name( & self, instance : & str )
+            -> Result< ConnectionPlan, VhdlError > {
+        let inst : & Instance = self.get_instance( instance )?;
+        let mut report = ConnectionReport::new();
+        for generic in & inst.get_unbound_generics() {
+            let source = get_member_path( instance, generic.get_inner() );
+            let ( candidates, strength ) = self.get_instance_generic_match( generic );
+            if candidates.is_empty() {
+                report.add_unmatched( & source );
+                continue;
+            }
+            if candidates.len() > 1 {
+                report.add_tie( ConnectionTie::new( & source, candidates.clone(), strength ) );
+            }
+            report.add_pair( ConnectionPair::new( instance, generic.get_inner(), "",
+                    & candidates[ 0 ], strength ) );
+        }
+        report.requires_no_ties( self.tie_break )?;
+        let mut plan = ConnectionPlan::new();
+        for pair in report.get_pairs() {
+            plan.add_binding( PlannedBinding::Generic { instance : instance.to_string(),
+                    inner : pair.get_source().clone(), outer : pair.get_target().clone() } );
+        }
+        plan.set_report( report );
+        Ok( plan )
+    }
+
+    /**
+     * Connects an output interface of one instance to the matching input interfaces of several
+     * instances through one shared set of signals named `source_port`.  Sink ports are paired
+     * with the source ports by member name and every shared net may have only one driver,
+     * unless all of its drivers are inout ports.
+     */
+    pub fn broadcast_from_instance( & mut self, source : & str, if_name : & str, sinks : & [ & str ] )
+            -> Result< ConnectionReport, VhdlError > {
+        let plan = self.plan_broadcast_from_instance( source, if_name, sinks )?;
+        self.apply( & plan )?;
+        Ok( plan.get_report().clone() )
+    }
+
+    pub fn plan_broadcast_from_instance( & self, source : & str, if_name : & str, sinks : & [ & str ] )
+            -> Result< ConnectionPlan, VhdlError > {
+        let inst : & Instance = self.get_instance( source )?;
+        self.requires_interface_in_instance( inst, if_name )?;
+        let source_idx = inst.get_interfaces().iter().position( | i | i.get_name() == if_name ).unwrap();
+        let interface = & inst.get_interfaces()[ source_idx ];
+        if interface.is_bound() {
+            return Err( VhdlError::new( & format!( "error: Interface {:?} of instance {:?} is already bound!",
+                    if_name, source ) ) );
+        }
+        let signals : Vec< SignalDeclaraion > = interface.get_ports().iter()
+                .map( | p | SignalDeclaraion::new( & format!( "{}_{}", source, p.get_inner() ), p.get_data_type() ) )
+                .collect();
+        let names : Vec< String > = signals.iter().map( | s | s.get_name().clone() ).collect();
+        let mut report = ConnectionReport::new();
+        let mut plan = ConnectionPlan::new();
+        plan.add_binding( PlannedBinding::SignalList { instance : source.to_string(), interface : source_idx,
+                name : if_name.to_string(), signals : names.clone() } );
+        let mut drivers : Vec< Vec< ( String, Direction ) > > = interface.get_ports().iter()
+                .map( | p | if * p.get_direction() != Direction::IN {
+                    vec![ ( format!( "{}.{}", source, p.get_inner() ), * p.get_direction() ) ] } else { vec![] } )
+                .collect();
+        for ( sink_idx, sink ) in sinks.iter().enumerate() {
+            if * sink == source {
+                return Err( VhdlError::new( & format!( "error: Instance {:?} can not be its own sink!", sink ) ) );
+            }
+            self.requires_single_sink( sinks, sink_idx )?;
+            let ( idx, strength ) = self.get_sink_interface( sink, | i |
+                    if interface.is_complementary( i ) { interface.get_instance_matching( i, self.matcher.as_ref() ) }
+                    else { 0 } )?;
+            let sink_interface = & self.get_instance( sink )?.get_interfaces()[ idx ];
+            let pairs = sink_interface.get_binding_pairs( interface )?;
+            let mut sink_names : Vec< String > = Vec::new();
+            for ( port, pair ) in sink_interface.get_ports().iter().zip( pairs ) {
+                match pair {
+                    Some( source_port ) => {
+                        sink_names.push( names[ source_port ].clone() );
+                        if * port.get_direction() != Direction::IN {
+                            drivers[ source_port ].push( ( format!( "{}.{}", sink, port.get_inner() ), * port.get_direction() ) );
+                        }
+                    },
+                    None => sink_names.push( String::new() ),
+                }
+            }
+            let mut pair = ConnectionPair::new( source, if_name, sink, sink_interface.get_name(), strength );
+            pair.set_indices( source_idx, idx );
+            report.add_pair( pair );
+            plan.add_binding( PlannedBinding::SignalList { instance : sink.to_string(), interface : idx,
+                    name : sink_interface.get_name().clone(), signals : sink_names } );
+        }
+        self.requires_single_drivers( & names, & drivers )?;
+        for signal in & signals {
+            plan.add_signal( signal );
+        }
+        plan.set_report( report );
+        Ok( plan )
+    }
+
+    /**
+     * Connects an input interface of the entity to the matching interfaces of several instances,
+     * e.g. one `clk_rst` interface feeding all instances.
+     */
+    pub fn broadcast_from_entity( & mut self, entity_if_name : & str, sinks : & [ & str ] )
+            -> Result< ConnectionReport, VhdlError > {
+        let plan = self.plan_broadcast_from_entity( entity_if_name, sinks )?;
+        self.apply( & plan )?;
+        Ok( plan.get_report().clone() )
+    }
+
+    pub fn plan_broadcast_from_entity( & self, entity_if_name : & str, sinks : & [ & str ] )
+            -> Result< ConnectionPlan, VhdlError > {
+        let entity_idx = match self.entity.get_interfaces().iter().position( | i | i.get_name() == entity_if_name ) {
+            Some( idx ) => idx,
+            None => return Err( VhdlError::new( & format!( "error: Interface {:?} not found in entity {:?}!",
+                    entity_if_name, self.entity.get_name() ) ) ),
+        };
+        let outer = & self.entity.get_interfaces()[ entity_idx ];
+        let mut report = ConnectionReport::new();
+        let mut plan = ConnectionPlan::new();
+        let nets : Vec< String > = outer.get_ports().iter().map( | p | p.get_name().clone() ).collect();
+        let mut drivers : Vec< Vec< ( String, Direction ) > > = outer.get_ports().iter()
+                .map( | p | match p.get_direction() {
+                    Direction::IN | Direction::INOUT => vec![ ( p.get_name().clone(), p.get_direction() ) ],
+                    _ => vec![],
+                } )
+                .collect();
+        for ( sink_idx, sink ) in sinks.iter().enumerate() {
+            self.requires_single_sink( sinks, sink_idx )?;
+            let ( idx, strength ) = self.get_sink_interface( sink, | i |
+                    if i.has_same_directions( outer ) { i.get_entity_matching( outer, self.matcher.as_ref() ) }
+                    else { 0 } )?;
+            let sink_interface = & self.get_instance( sink )?.get_interfaces()[ idx ];
+            let pairs = sink_interface.get_member_pairs( & get_entity_members( outer ), outer.get_name() )?;
+            for ( port, pair ) in sink_interface.get_ports().iter().zip( pairs ) {
+                if let Some( entity_port ) = pair {
+                    if * port.get_direction() != Direction::IN {
+                        drivers[ entity_port ].push( ( format!( "{}.{}", sink, port.get_inner() ), * port.get_direction() ) );
+                    }
+                }
+            }
+            let mut pair = ConnectionPair::new( sink, sink_interface.get_name(), "", entity_if_name, strength );
+            pair.set_indices( idx, entity_idx );
+            report.add_pair( pair );
+            plan.add_binding( PlannedBinding::EntityInterface { instance : sink.to_string(), interface : idx,
+                    name : sink_interface.get_name().clone(), entity_interface : entity_idx,
+                    entity_name : entity_if_name.to_string() } );
+        }
+        self.requires_single_drivers( & nets, & drivers )?;
+        plan.set_report( report );
+        Ok( plan )
+    }
+
+    /**
+     * Returns the index and strength of the best matching unbound interface of a sink instance.
+     */
+    fn get_sink_interface< F >( & self, sink : & str, get_strength : F ) -> Result< ( usize, u32 ), VhdlError >
+            where F : Fn( & EntityInterfaceBinding ) -> u32 {
+        let inst : & Instance = self.get_instance( sink )?;
+        let mut match_index = MatchIndex::new();
+        for ( idx, interface ) in inst.get_interfaces().iter().enumerate() {
+            if interface.is_unbound() && ! interface.is_empty() {
+                match_index.update( idx, get_strength( interface ) );
+            }
+        }
+        if ! match_index.is_match() {
+            return Err( VhdlError::new( & format!( "error: Instance {:?} has no matching interface!", sink ) ) );
+        }
+        if match_index.is_tie() && self.tie_break == TieBreak::Error {
+            let candidates : Vec< & String > = match_index.candidates().iter()
+                    .map( | idx | inst.get_interfaces()[ * idx ].get_name() ).collect();
+            return Err( VhdlError::new( & format!( "error: Ambiguous connection of instance {:?}: {:?}!",
+                    sink, candidates ) ) );
+        }
+        Ok( ( match_index.position(), match_index.strength() ) )
+    }
+
+    fn requires_single_sink( & self, sinks : & [ & str ], idx : usize ) -> Result< (), VhdlError > {
+        if sinks[ .. idx ].contains( & sinks[ idx ] ) {
+            return Err( VhdlError::new( & format!( "error: Instance {:?} is given more than once as sink!",
+                    sinks[ idx ] ) ) );
+        }
+        Ok(())
+    }
+
+    /**
+     * Requires every net to have at most one driver, unless all of its drivers are inout ports.
+     */
+    fn requires_single_drivers( & self, nets : & [ String ], drivers : & [ Vec< ( String, Direction ) > ] )
             -> Result< (), VhdlError > {
-        println!( "connect_instance_unbound_by_name" );
-        self.requires_instance( instance )?;
-        let mut matches : Vec< ( String, String ) > = Vec::new();
-        {
-            let inst : Instance = ( * self.instances.get( instance ).unwrap() ).clone();
-            for generic in & inst.get_unbound_generics() {
-                let outer = self.get_instance_generic_match( generic );
-                matches.push( ( generic.get_inner().clone(), outer ) );
+        for ( net, net_drivers ) in nets.iter().zip( drivers ) {
+            if net_drivers.len() > 1 && net_drivers.iter().any( | ( _, d ) | * d != Direction::INOUT ) {
+                let names : Vec< & String > = net_drivers.iter().map( | ( n, _ ) | n ).collect();
+                return Err( VhdlError::new( & format!( "error: Net {:?} would be driven by {:?}!", net, names ) ) );
+            }
+        }
+        Ok(())
+    }
+
+    /**
+     * Connects the whole architecture in one call: instance interfaces are paired with entity
+     * interfaces and with one another by a matching maximizing the total score, generics are
+     * bound by name and everything left is handled according to `policy`.
+     */
+    pub fn auto_connect( & mut self, policy : & LeftoverPolicy ) -> Result< ConnectionReport, VhdlError > {
+        let plan = self.plan_auto_connect()?;
+        self.apply( & plan )?;
+        match policy {
+            LeftoverPolicy::Keep => {},
+            LeftoverPolicy::Promote( prefix ) => self.promote_unbound( prefix )?,
+            LeftoverPolicy::TieOff => self.finalize()?,
+        }
+        Ok( plan.get_report().clone() )
+    }
+
+    /**
+     * Plans the interface and generic connections of `auto_connect`.  Entity interfaces with
+     * inputs only may be bound by any number of instances, all other interfaces only once.
+     * Interfaces of class `__default__` are left to the leftover policy.
+     */
+    pub fn plan_auto_connect( & self ) -> Result< ConnectionPlan, VhdlError > {
+        // nodes are instance interfaces followed by entity interface slots
+        let mut interfaces : Vec< ( & String, usize, & EntityInterfaceBinding ) > = Vec::new();
+        for ( name, instance ) in & self.instances {
+            for ( idx, interface ) in instance.get_interfaces().iter().enumerate() {
+                if interface.is_unbound() && ! interface.is_empty() && interface.get_class() != "__default__" {
+                    interfaces.push( ( name, idx, interface ) );
+                }
+            }
+        }
+        let mut slots : Vec< ( usize, Option< & String > ) > = Vec::new();
+        for ( idx, interface ) in self.entity.get_interfaces().iter().enumerate() {
+            if interface.get_ports().is_empty() || interface.get_class() == "__default__" {
+                continue;
+            }
+            if interface.get_ports().iter().all( | p | p.get_direction() == Direction::IN ) {
+                for name in self.instances.keys() {
+                    slots.push( ( idx, Some( name ) ) );
+                }
+            }
+            else {
+                slots.push( ( idx, None ) );
+            }
+        }
+        let mut edges : Vec< ( usize, usize, u32 ) > = Vec::new();
+        for ( a, ( name_a, _, interface_a ) ) in interfaces.iter().enumerate() {
+            for ( b, ( name_b, _, interface_b ) ) in interfaces.iter().enumerate().skip( a + 1 ) {
+                let strength = interface_a.get_instance_matching( interface_b, self.matcher.as_ref() );
+                if name_a != name_b && strength > 0 && interface_a.is_complementary( interface_b ) {
+                    edges.push( ( a, b, strength ) );
+                }
+            }
+            for ( s, ( idx, instance ) ) in slots.iter().enumerate() {
+                let outer = & self.entity.get_interfaces()[ * idx ];
+                let strength = interface_a.get_entity_matching( outer, self.matcher.as_ref() );
+                if instance.is_none_or( | i | i == * name_a ) && strength > 0 &&
+                        interface_a.has_same_directions( outer ) {
+                    edges.push( ( a, interfaces.len() + s, strength ) );
+                }
+            }
+        }
+        let matching = get_max_weight_matching( interfaces.len() + slots.len(), & edges );
+        let get_path = | node : usize | -> String {
+            match interfaces.get( node ) {
+                Some( ( name, _, interface ) ) => get_member_path( name, interface.get_name() ),
+                None => get_member_path( "",
+                        self.entity.get_interfaces()[ slots[ node - interfaces.len() ].0 ].get_name() ),
+            }
+        };
+        let mut report = ConnectionReport::new();
+        let mut plan = ConnectionPlan::new();
+        for ( a, b ) in & matching.pairs {
+            let ( name_a, idx_a, interface_a ) = interfaces[ * a ];
+            let strength = edges.iter().find( | ( x, y, _ ) | x == a && y == b ).unwrap().2;
+            if let Some( ( name_b, idx_b, interface_b ) ) = interfaces.get( * b ) {
+                let mut pair = ConnectionPair::new( name_a, interface_a.get_name(), name_b,
+                        interface_b.get_name(), strength );
+                pair.set_indices( idx_a, * idx_b );
+                report.add_pair( pair );
+                let names = interface_a.get_connection_names( interface_b, name_a, name_b )?;
+                plan.add_binding( PlannedBinding::SignalList { instance : name_a.clone(),
+                        interface : idx_a, name : interface_a.get_name().clone(), signals : names.names_a } );
+                plan.add_binding( PlannedBinding::SignalList { instance : name_b.to_string(),
+                        interface : * idx_b, name : interface_b.get_name().clone(), signals : names.names_b } );
+                for signal in & names.signals {
+                    plan.add_signal( signal );
+                }
+                for signal in & names.shared {
+                    plan.add_shared_signal( signal );
+                }
+            }
+            else {
+                let entity_idx = slots[ * b - interfaces.len() ].0;
+                let entity_name = self.entity.get_interfaces()[ entity_idx ].get_name();
+                let mut pair = ConnectionPair::new( name_a, interface_a.get_name(), "", entity_name, strength );
+                pair.set_indices( idx_a, entity_idx );
+                report.add_pair( pair );
+                plan.add_binding( PlannedBinding::EntityInterface { instance : name_a.clone(),
+                        interface : idx_a, name : interface_a.get_name().clone(),
+                        entity_interface : entity_idx, entity_name : entity_name.clone() } );
+            }
+        }
+        for ( node, candidates ) in & matching.ties {
+            let strength = match candidates[ 0 ] {
+                Some( c ) => edges.iter().find( | ( x, y, _ ) | * x == ( * node ).min( c ) &&
+                        * y == ( * node ).max( c ) ).map_or( 0, | e | e.2 ),
+                None => 0,
+            };
+            let candidates = candidates.iter().map( | c | match c {
+                Some( c ) => get_path( * c ),
+                None => "<none>".to_string(),
+            } ).collect();
+            report.add_tie( ConnectionTie::new( & get_path( * node ), candidates, strength ) );
+        }
+        for ( node, _ ) in interfaces.iter().enumerate() {
+            if ! matching.pairs.iter().any( | ( a, b ) | * a == node || * b == node ) {
+                report.add_unmatched( & get_path( node ) );
             }
         }
-        let inst : & mut Instance = self.instances.get_mut( instance ).unwrap();
-        for ( inner, outer ) in matches {
-            if ! outer.is_empty() {
-                inst.connect_generic( & inner, & outer )?;
+        report.requires_no_ties( self.tie_break )?;
+        plan.set_report( report );
+        for name in self.instances.keys() {
+            plan.append( self.plan_instance_unbound_by_name( name )? );
+        }
+        Ok( plan )
+    }
+
+    /**
+     * Carries out a connection plan.  Nothing is changed if any binding of the plan fails.
+     */
+    pub fn apply( & mut self, plan : & ConnectionPlan ) -> Result< (), VhdlError > {
+        self.requires_free_shared_signals( plan.get_shared_signals(), plan.get_signals() )?;
+        let mut instances = self.instances.clone();
+        for binding in plan.get_bindings() {
+            let inst : & mut Instance = match instances.get_mut( binding.get_instance() ) {
+                Some( inst ) => inst,
+                None => return Err( VhdlError::new( & format!(
+                        "error: Architecture {:?} does not contain instance {:?}", self.name,
+                        binding.get_instance() ) ) ),
+            };
+            match binding {
+                PlannedBinding::EntityInterface { interface, entity_interface, entity_name, .. } => {
+                    let outer = match self.entity.get_interfaces().get( * entity_interface ) {
+                        Some( outer ) => outer,
+                        None => return Err( VhdlError::new( & format!(
+                                "error: Interface {:?} not found in entity {:?}!", entity_name,
+                                self.entity.get_name() ) ) ),
+                    };
+                    inst.connect_interface_by_index_to_entity_interface( * interface, outer )?;
+                },
+                PlannedBinding::SignalList { interface, signals, .. } =>
+                    inst.connect_interface_by_index_to_names( * interface, signals )?,
+                PlannedBinding::Generic { inner, outer, .. } => inst.connect_generic( inner, outer )?,
             }
         }
+        self.instances = instances;
+        self.add_signal_declaration_list( plan.get_signals() );
+        self.add_shared_signal_declarations( plan.get_shared_signals() );
+        Ok(())
+    }
+
+    /**
+     * Declares the shared nets of fixed ports that are neither entity ports nor declared yet.
+     */
+    fn add_shared_signal_declarations( & mut self, signals : & Vec< SignalDeclaraion > ) {
+        for signal in signals {
+            let lower = signal.get_name().to_lowercase();
+            if ! self.entity.get_ports().iter().any( | p | p.get_name().to_lowercase() == lower ) {
+                self.add_signal_declaration( signal );
+                if ! self.shared_signals.contains( & lower ) {
+                    self.shared_signals.push( lower );
+                }
+            }
+        }
+    }
+
+    /**
+     * Checks that the shared nets `shared` only meet entity ports and shared nets of the same data
+     * type, and no other signal, e.g. one of `signals` or the `clk` of another clock domain.
+     */
+    fn requires_free_shared_signals( & self, shared : & [ SignalDeclaraion ], signals : & [ SignalDeclaraion ] )
+            -> Result< (), VhdlError > {
+        for ( idx, signal ) in shared.iter().enumerate() {
+            let lower = signal.get_name().to_lowercase();
+            let data_type = match self.entity.get_ports().iter().find( | p | p.get_name().to_lowercase() == lower ) {
+                Some( port ) => Some( port.get_data_type().clone() ),
+                None => self.declarations.get_signals().iter().find( | s | s.get_name().to_lowercase() == lower )
+                    .map( | s | s.get_data_type().clone() ),
+            };
+            let other_signal = self.declarations.get_signals().iter().any( | s | s.get_name().to_lowercase() == lower ) &&
+                    ! self.shared_signals.contains( & lower );
+            let conflict = other_signal ||
+                    data_type.is_some_and( | t | & t != signal.get_data_type() ) ||
+                    signals.iter().any( | s | s.get_name().to_lowercase() == lower ) ||
+                    shared[ .. idx ].iter().any( | s | s.get_name().to_lowercase() == lower &&
+                        s.get_data_type() != signal.get_data_type() );
+            if conflict {
+                return Err( VhdlError::new( & format!(
+                        "error: Shared net {:?} collides with another signal of the same name in architecture {:?}!",
+                        signal.get_name(), self.name ) ) );
+            }
+        }
+        Ok(())
+    }
 
+    /**
+     * Declares a bundle of signals `<prefix>_<member>` for the ports of `interface`.  Instance
+     * and entity interfaces of the same class can be bound to the bundle by its name `prefix`,
+     * e.g. to tap the signals or to add logic between two instances.  Generics of `interface`
+     * have to be generics of the entity or have a default, which replaces them in the types.
+     */
+    pub fn declare_interface_signals( & mut self, interface : & EntityInterface, prefix : & str )
+            -> Result< (), VhdlError > {
+        if self.get_signal_bundle( prefix ).is_some() {
+            return Err( VhdlError::new( & format!( "error: Signal bundle {:?} already exists in architecture {:?}!",
+                    prefix, self.name ) ) );
+        }
+        let mut bundle = interface.clone();
+        let entity_generics = self.entity.get_generics();
+        for generic in interface.get_generics() {
+            if entity_generics.iter().any( | g | g.get_name() == * generic.get_base_name() ) {
+                continue;
+            }
+            if ! generic.has_default() {
+                return Err( VhdlError::new( & format!(
+                        "error: Signal bundle {:?} needs generic {:?} which entity {:?} does not declare!",
+                        prefix, generic.get_base_name(), self.entity.get_name() ) ) );
+            }
+            bundle.replace_in_port_types( generic.get_base_name(), generic.get_default() );
+        }
+        bundle.remove_generics();
+        bundle.rename( prefix );
+        for port in bundle.get_ports() {
+            self.requires_free_identifier( & port.get_name() )?;
+        }
+        for port in bundle.get_ports() {
+            self.add_signal_declaration( & SignalDeclaraion::new( & port.get_name(), port.get_data_type() ) );
+        }
+        self.bundles.push( bundle );
         Ok(())
     }
 
+    pub fn get_signal_bundles( & self ) -> & Vec< EntityInterface > {
+        & self.bundles
+    }
+
+    pub fn get_signal_bundle( & self, name : & str ) -> Option< & EntityInterface > {
+        self.bundles.iter().find( | b | b.get_name() == name )
+    }
+
+    /**
+     * Binds an instance interface to a signal bundle by member name, see
+     * `EntityInterfaceBinding::get_member_pairs`.  Ports missing in the bundle are tied off.
+     */
+    pub fn connect_instance_to_signal_bundle( & mut self, instance : & str, if_name : & str, bundle : & str )
+            -> Result< (), VhdlError > {
+        let inst = self.get_instance( instance )?;
+        self.requires_interface_in_instance( inst, if_name )?;
+        let binding = inst.get_interface_by_name( if_name ).unwrap();
+        let names = self.get_bundle_names( binding, bundle )?;
+        let driven : Vec< & String > = binding.get_ports().iter().zip( & names )
+                .filter( | ( p, n ) | ! n.is_empty() && * p.get_direction() != Direction::IN )
+                .map( | ( _, n ) | n ).collect();
+        self.requires_undriven( & driven )?;
+        self.get_instance_mut( instance )?.get_interface_by_name_mut( if_name ).unwrap().connect_to_names( & names )
+    }
+
+    /**
+     * Connects an entity interface to a signal bundle by member name with signal assignments,
+     * inputs drive the bundle and outputs are driven by it.
+     */
+    pub fn connect_entity_to_signal_bundle( & mut self, if_name : & str, bundle : & str )
+            -> Result< (), VhdlError > {
+        let interface = match self.entity.get_interfaces().iter().find( | i | i.get_name() == if_name ) {
+            Some( interface ) => interface,
+            None => return Err( VhdlError::new( & format!( "error: Interface {:?} not found in entity {:?}!",
+                    if_name, self.entity.get_name() ) ) ),
+        };
+        let names = self.get_bundle_names( & EntityInterfaceBinding::from_entity_interface( interface ), bundle )?;
+        let driven : Vec< & String > = interface.get_ports().iter().zip( & names )
+                .filter( | ( p, n ) | ! n.is_empty() && p.get_direction() == Direction::IN )
+                .map( | ( _, n ) | n ).collect();
+        self.requires_undriven( & driven )?;
+        let mut assignments = Vec::new();
+        for ( port, name ) in interface.get_ports().iter().zip( names ) {
+            match port.get_direction() {
+                _ if name.is_empty() => {},
+                Direction::IN => assignments.push( SignalAssignment::new( & name, & port.get_name() ) ),
+                Direction::OUT | Direction::BUFFER => assignments.push( SignalAssignment::new( & port.get_name(), & name ) ),
+                Direction::INOUT => return Err( VhdlError::new( & format!(
+                        "error: Inout port {:?} can not be connected to signal {:?}!", port.get_name(), name ) ) ),
+            }
+        }
+        for assignment in assignments {
+            self.add_signal_assignment( assignment );
+        }
+        Ok(())
+    }
+
+    /**
+     * Checks that none of `signals` is driven yet.
+     */
+    fn requires_undriven( & self, signals : & [ & String ] ) -> Result< (), VhdlError > {
+        let netlist = Netlist::from_architecture( self );
+        for signal in signals {
+            if netlist.get_net( signal ).is_some_and( | n | ! n.get_drivers().is_empty() ) {
+                return Err( VhdlError::new( & format!( "error: Signal {:?} is already driven in architecture {:?}!",
+                        signal, self.name ) ) );
+            }
+        }
+        Ok(())
+    }
+
+    /**
+     * Returns the bundle signal for every port of `interface`, empty for ports missing in the
+     * bundle.
+     */
+    fn get_bundle_names( & self, interface : & EntityInterfaceBinding, bundle : & str )
+            -> Result< Vec< String >, VhdlError > {
+        let bundle = match self.get_signal_bundle( bundle ) {
+            Some( bundle ) => bundle,
+            None => return Err( VhdlError::new( & format!( "error: Signal bundle {:?} not found in architecture {:?}!",
+                    bundle, self.name ) ) ),
+        };
+        if interface.get_class() != bundle.get_class() {
+            return Err( VhdlError::new( & format!(
+                    "error: Interface {:?} of class {:?} can not be connected to signal bundle {:?} of class {:?}!",
+                    interface.get_name(), interface.get_class(), bundle.get_name(), bundle.get_class() ) ) );
+        }
+        let pairs = interface.get_member_pairs( & get_entity_members( bundle ), bundle.get_name() )?;
+        Ok( pairs.iter().map( | pair | match pair {
+            Some( idx ) => bundle.get_ports()[ * idx ].get_name(),
+            None => String::new(),
+        } ).collect() )
+    }
+
     pub fn connect_instance_to_port_by_name( & mut self, instance : & str, inner : & str,
             outer : & str ) -> Result< (), Box< dyn Error > > {
         let instance : & mut Instance = self.get_instance_mut( instance )?;
@@ -179,6 +868,38 @@ impl Architecture {
         Ok(())
     }
 
+    /**
+     * Binds an instance port to a literal, slice, expression or `open`.
+     */
+    pub fn connect_instance_port_to_actual( & mut self, instance : & str, inner : & str,
+            actual : & Actual ) -> Result< (), Box< dyn Error > > {
+        let instance : & mut Instance = self.get_instance_mut( instance )?;
+        instance.connect_port_to_actual( inner, actual )?;
+        Ok(())
+    }
+
+    pub fn connect_instance_port_open( & mut self, instance : & str, inner : & str )
+            -> Result< (), Box< dyn Error > > {
+        self.connect_instance_port_to_actual( instance, inner, & Actual::Open )
+    }
+
+    /**
+     * Adds a formal partial association `inner( range ) => actual` to an instance port.
+     */
+    pub fn connect_instance_port_partial( & mut self, instance : & str, inner : & str,
+            range : & str, actual : & Actual ) -> Result< (), Box< dyn Error > > {
+        let instance : & mut Instance = self.get_instance_mut( instance )?;
+        instance.connect_port_partial( inner, range, actual )?;
+        Ok(())
+    }
+
+    pub fn set_instance_generic_value( & mut self, instance : & str, inner : & str, value : & str )
+            -> Result< (), Box< dyn Error > > {
+        let instance : & mut Instance = self.get_instance_mut( instance )?;
+        instance.set_generic_value( inner, value )?;
+        Ok(())
+    }
+
     pub fn connect_instance_to_signal_by_name( & mut self, instance : & str, inner : & str,
             outer : & str ) -> Result< (), Box< dyn Error > > {
         {
@@ -193,6 +914,103 @@ impl Architecture {
         Ok(())
     }
 
+    /**
+     * Adds every unbound interface or port of the instance to the entity of this architecture and
+     * binds it to the new entity member.  Interfaces without any bound port are promoted as a
+     * whole, remaining single ports are added to the default interface together with the unbound
+     * generics their data types refer to.  Nothing is changed on an error.
+     */
+    pub fn promote_instance_unbound( & mut self, instance : & str, prefix : & PrefixPolicy )
+            -> Result< (), VhdlError > {
+        let inst : Instance = self.get_instance( instance )?.clone();
+        let mut map = inst.get_generic_map();
+        let mut entity = self.entity.clone();
+        let mut names : Vec< String > = Vec::new();
+        let mut interfaces : Vec< ( usize, EntityInterface ) > = Vec::new();
+        let mut generics : Vec< ( String, String ) > = Vec::new();
+        let mut ports : Vec< ( String, String ) > = Vec::new();
+        for ( idx, interface ) in inst.get_interfaces().iter().enumerate() {
+            if interface.is_promotable() {
+                let promoted = interface.to_entity_interface(
+                        & prefix.apply( instance, interface.get_name() ), & map );
+                for port in promoted.get_ports() {
+                    self.requires_new_identifier( & port.get_name(), & mut names )?;
+                }
+                entity.add_interface( & promoted )?;
+                interfaces.push( ( idx, promoted ) );
+            }
+        }
+        let unbound_generics : Vec< GenericBinding > = inst.get_interfaces().iter()
+                .filter( | i | ! i.is_promotable() ).flat_map( | i | i.get_unbound_generics() ).collect();
+        for interface in inst.get_interfaces().iter().filter( | i | ! i.is_promotable() ) {
+            for port in interface.get_ports().iter().filter( | p | ! p.is_bound() ) {
+                for identifier in get_identifiers( port.get_data_type() ) {
+                    let generic = match unbound_generics.iter().find( | g | g.get_inner() == & identifier ||
+                            g.get_member() == & identifier ) {
+                        Some( generic ) => generic,
+                        None => continue,
+                    };
+                    if generics.iter().any( | ( inner, _ ) | inner == generic.get_inner() ) {
+                        continue;
+                    }
+                    let name = prefix.apply( instance, generic.get_inner() );
+                    if entity.get_generics().iter().any( | g | g.get_name().eq_ignore_ascii_case( & name ) ) {
+                        return Err( VhdlError::new( & format!( "error: Generic {:?} already exists in entity {:?}!",
+                                name, entity.get_name() ) ) );
+                    }
+                    entity.add_generic( Generic::new_with_default( & name, generic.get_data_type(),
+                            generic.get_default() ) );
+                    map.insert( generic.get_inner().clone(), name.clone() );
+                    map.insert( generic.get_member().clone(), name.clone() );
+                    generics.push( ( generic.get_inner().clone(), name ) );
+                }
+                let name = prefix.apply( instance, port.get_inner() );
+                self.requires_new_identifier( & name, & mut names )?;
+                let data_type = substitute_identifiers( port.get_data_type(), & map );
+                entity.add_port( Port::new_with_default( & name, * port.get_direction(),
+                        & data_type, port.get_default() ) );
+                ports.push( ( port.get_inner().clone(), name ) );
+            }
+        }
+        self.entity = entity;
+        let inst = self.instances.get_mut( instance ).unwrap();
+        for ( idx, promoted ) in interfaces {
+            inst.connect_interface_by_index_to_promoted_interface( idx, & promoted );
+        }
+        for ( inner, outer ) in generics {
+            inst.connect_generic( & inner, & outer )?;
+        }
+        for ( inner, outer ) in ports {
+            inst.connect_to_port( & inner, & outer )?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Promotes the unbound interfaces and ports of all instances.
+     */
+    pub fn promote_unbound( & mut self, prefix : & PrefixPolicy ) -> Result< (), VhdlError > {
+        let names : Vec< String > = self.instances.keys().cloned().collect();
+        for name in names {
+            self.promote_instance_unbound( & name, prefix )?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Final step before writing the architecture: ties every unbound instance input to its
+     * default or the zero value of its type and leaves unbound outputs open.
+     */
+    pub fn finalize( & mut self ) -> Result< (), VhdlError > {
+        for instance in self.instances.values() {
+            instance.requires_tie_offs()?;
+        }
+        for ( _, instance ) in self.instances.iter_mut() {
+            instance.tie_off_unbound_ports()?;
+        }
+        Ok(())
+    }
+
     fn get_instance( & self, instance : & str ) -> Result< & Instance, VhdlError > {
         match self.instances.get( instance ) {
             Some( instance ) => Ok( instance ),
@@ -219,6 +1037,32 @@ impl Architecture {
         Ok(())
     }
 
+    fn requires_free_identifier( & self, name : & str ) -> Result< (), VhdlError > {
+        let lower = name.to_lowercase();
+        let is_port = self.entity.get_ports().iter().any( | p | p.get_name().to_lowercase() == lower );
+        let is_signal = self.declarations.get_signals().iter()
+                .any( | s | s.get_name().to_lowercase() == lower );
+        if is_port || is_signal {
+            return Err( VhdlError::new( & format!( "error: Identifier {:?} already exists in architecture {:?}!",
+                    name, self.name ) ) );
+        }
+        Ok(())
+    }
+
+    /**
+     * Like `requires_free_identifier`, also checking the names in `names` that are about to be
+     * added.  Adds `name` to `names`.
+     */
+    fn requires_new_identifier( & self, name : & str, names : & mut Vec< String > ) -> Result< (), VhdlError > {
+        self.requires_free_identifier( name )?;
+        if names.iter().any( | n | n.eq_ignore_ascii_case( name ) ) {
+            return Err( VhdlError::new( & format!( "error: Identifier {:?} is added twice to architecture {:?}!",
+                    name, self.name ) ) );
+        }
+        names.push( name.to_string() );
+        Ok(())
+    }
+
     fn requires_interface_in_instance( & self, inst : & Instance, name : & str ) -> Result< (), VhdlError > {
         if ! inst.contains_interface( name ) {
             return Err( VhdlError::new( & format!( "error: Interface {:?} not found in instance {:?} of architecture {:?}!",
@@ -227,7 +1071,11 @@ impl Architecture {
         Ok(())
     }
 
-    fn get_instance_generic_match( & self, binding : & GenericBinding ) -> String {
+    /**
+     * Returns the names of the constants or generics matching best, more than one on a tie,
+     * and their strength.
+     */
+    fn get_instance_generic_match( & self, binding : & GenericBinding ) -> ( Vec< String >, u32 ) {
         let constants = self.declarations.get_constants();
         let mut constant_match = MatchIndex::new();
         for ( idx, constant ) in constants.iter().enumerate() {
@@ -242,12 +1090,16 @@ impl Architecture {
         }
         // in case both matches are zero constant is not greater than generic
         if constant_match.strength > generic_match.strength {
-            return constants[ constant_match.index ].get_name().clone();
+            ( constant_match.candidates().iter().map( | idx | constants[ * idx ].get_name().clone() )
+                    .collect(), constant_match.strength )
         }
         else if generic_match.strength > 0 {
-            return generics[ generic_match.index ].get_name().clone();
+            ( generic_match.candidates().iter().map( | idx | generics[ * idx ].get_name().clone() )
+                    .collect(), generic_match.strength )
+        }
+        else {
+            ( Vec::new(), 0 )
         }
-        return String::new();
     }
 
     fn get_generic_constant_match_strength( & self, _binding : & GenericBinding,
@@ -305,6 +1157,10 @@ impl DesignUnit for Architecture {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::vhdl::direction::Direction;
+    use crate::vhdl::matcher::{ ExactMatcher, ClassMatcher };
+    use crate::vhdl::auto_connect::LeftoverPolicy;
+    use crate::vhdl::lint::lint;
     const NAME : &'static str = "rtl";
     const ENTITY : &'static str = "test";
     const ENTITY_TEST : &'static str = "entity test is\nbegin\nend entity test;\n\n";
@@ -362,6 +1218,545 @@ mod tests {
             format!( "{}{}{}{}{}", ENTITY_TEST, HEADER, CONSTANT_DECLARATION, BEGIN, END ) );
     }
 
+    /**
+     * Promote an unbound interface as a whole and a single unbound port of a partially bound
+     * default interface.
+     */
+    #[test]
+    fn promote_instance_unbound() {
+        let mut interface = EntityInterface::new( "bus", "test" );
+        interface.add_generic( Generic::new( "WIDTH", "positive" ) );
+        interface.add_port( Port::new( "data", Direction::OUT, "std_logic_vector( WIDTH - 1 downto 0 )" ) );
+        interface.rename( "bus" );
+        let mut sub = Entity::with_interface( "sub", & interface );
+        sub.add_port( Port::new( "a", Direction::IN, "std_logic" ) );
+        sub.add_port( Port::new_with_default( "b", Direction::IN, "std_logic", "'0'" ) );
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        architecture.add_instance( Instance::from_entity( "u0", & sub ) );
+        architecture.connect_instance_to_port_by_name( "u0", "a", "a" ).unwrap();
+        architecture.promote_instance_unbound( "u0", & PrefixPolicy::InstanceName ).unwrap();
+
+        let ports : Vec< String > = architecture.get_entity().get_ports().iter()
+                .map( | p | p.to_source_code( 0 ) ).collect();
+        assert_eq!( ports, vec![ "u0_b : in std_logic := '0'",
+                "u0_bus_data : out std_logic_vector( u0_bus_WIDTH - 1 downto 0 )" ] );
+        let instance = architecture.get_instance( "u0" ).unwrap();
+        assert!( instance.get_interfaces()[ 1 ].is_bound() );
+        assert_eq!( instance.get_interfaces()[ 1 ].get_generics()[ 0 ].get_outer(), "u0_bus_WIDTH" );
+        assert_eq!( instance.get_interfaces()[ 1 ].get_ports()[ 0 ].get_outer(), "u0_bus_data" );
+        assert_eq!( instance.get_interfaces()[ 0 ].get_ports()[ 1 ].get_outer(), "u0_b" );
+    }
+
+    /**
+     * Promoting a single port also promotes the unbound generic its data type refers to.
+     */
+    #[test]
+    fn promote_port_with_generic() {
+        let mut sub = Entity::new( "sub" );
+        sub.add_generic( Generic::new( "WIDTH", "positive" ) );
+        sub.add_port( Port::new( "a", Direction::IN, "std_logic" ) );
+        sub.add_port( Port::new( "d", Direction::OUT, "std_logic_vector( WIDTH - 1 downto 0 )" ) );
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        architecture.add_instance( Instance::from_entity( "u0", & sub ) );
+        architecture.connect_instance_to_port_by_name( "u0", "a", "a" ).unwrap();
+        architecture.promote_instance_unbound( "u0", & PrefixPolicy::InstanceName ).unwrap();
+        let entity = architecture.get_entity();
+        assert_eq!( entity.get_generics()[ 0 ].to_source_code( 0 ), "u0_WIDTH : positive" );
+        assert_eq!( entity.get_ports()[ 0 ].to_source_code( 0 ),
+                "u0_d : out std_logic_vector( u0_WIDTH - 1 downto 0 )" );
+        let instance = architecture.get_instance( "u0" ).unwrap();
+        assert_eq!( instance.get_interfaces()[ 0 ].get_generics()[ 0 ].get_outer(), "u0_WIDTH" );
+    }
+
+    /**
+     * Promoting a port fails if the entity already contains a port of the same name.  Nothing is
+     * promoted then.
+     */
+    #[test]
+    fn promote_instance_unbound_collision() {
+        let mut sub = Entity::new( "sub" );
+        sub.add_port( Port::new( "a", Direction::IN, "std_logic" ) );
+        let mut interface = EntityInterface::new( "bus", "test" );
+        interface.add_port( Port::new( "data", Direction::OUT, "std_logic" ) );
+        interface.rename( "bus" );
+        sub.add_interface( & interface ).unwrap();
+        let mut top = Entity::new( ENTITY );
+        top.add_port( Port::new( "a", Direction::IN, "std_logic" ) );
+        let mut architecture = Architecture::new( NAME, & top );
+        architecture.add_instance( Instance::from_entity( "u0", & sub ) );
+        assert!( architecture.promote_instance_unbound( "u0", & PrefixPolicy::NoPrefix ).is_err() );
+        assert_eq!( architecture.get_entity().get_interfaces().len(), 1 );
+        assert!( ! architecture.get_instance( "u0" ).unwrap().get_interfaces()[ 1 ].is_bound() );
+    }
+
+    #[test]
+    fn finalize() {
+        let mut sub = Entity::new( "sub" );
+        sub.add_port( Port::new( "a", Direction::IN, "std_logic_vector( 3 downto 0 )" ) );
+        sub.add_port( Port::new_with_default( "b", Direction::IN, "std_logic", "'1'" ) );
+        sub.add_port( Port::new( "y", Direction::OUT, "std_logic" ) );
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        architecture.add_instance( Instance::from_entity( "u0", & sub ) );
+        architecture.finalize().unwrap();
+        let ports : Vec< String > = architecture.get_instances().get( "u0" ).unwrap().get_interfaces()
+                .iter().flat_map( | i | i.get_ports() ).map( | p | p.to_source_code( 0 ) ).collect();
+        assert_eq!( ports, vec![ "a => (others => '0')", "b => '1'", "y => open" ] );
+
+        let mut unresolvable = Entity::new( "unresolvable" );
+        unresolvable.add_port( Port::new( "p", Direction::IN, "positive" ) );
+        architecture.add_instance( Instance::from_entity( "u1", & unresolvable ) );
+        assert!( architecture.finalize().is_err() );
+    }
+
+    #[test]
+    fn connect_instance_to_instance_by_mapping() -> Result< (), VhdlError > {
+        let mut host = EntityInterface::new( "", "bus" );
+        host.add_port( Port::new( "addr", Direction::OUT, "std_logic" ) );
+        host.add_port( Port::new( "rd_data", Direction::IN, "std_logic" ) );
+        let mut agent = EntityInterface::new( "", "bus" );
+        agent.add_port( Port::new( "read_data", Direction::OUT, "std_logic" ) );
+        agent.add_port( Port::new( "addr", Direction::IN, "std_logic" ) );
+        let mut cpu = Entity::new( "cpu" );
+        host.rename( "bus" );
+        cpu.add_interface( & host )?;
+        let mut mem = Entity::new( "mem" );
+        agent.rename( "bus" );
+        mem.add_interface( & agent )?;
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        architecture.add_instance( Instance::from_entity( "u0", & cpu ) );
+        architecture.add_instance( Instance::from_entity( "u1", & mem ) );
+        assert!( architecture.connect_instance_to_instance_by_mapping( "u0", "u1", "bus", "bus",
+                & PortMapping::new() ).is_err() );
+        architecture.connect_instance_to_instance_by_mapping( "u0", "u1", "bus", "bus",
+                & PortMapping::from_pairs( & [ ( "rd_data", "read_data" ) ] ) )?;
+        let outers : Vec< String > = architecture.get_instances().get( "u1" ).unwrap()
+                .get_interface_by_name( "bus" ).unwrap().get_ports().iter()
+                .map( | p | p.get_outer() ).collect();
+        assert_eq!( outers, vec![ "u1_to_u0_bus_rd_data", "u0_to_u1_bus_addr" ] );
+        let outers : Vec< String > = architecture.get_instances().get( "u0" ).unwrap()
+                .get_interface_by_name( "bus" ).unwrap().get_ports().iter()
+                .map( | p | p.get_outer() ).collect();
+        assert_eq!( outers, vec![ "u0_to_u1_bus_addr", "u1_to_u0_bus_rd_data" ] );
+        assert!( architecture.connect_instance_to_instance_by_mapping( "u0", "u1", "bus", "bus",
+                & PortMapping::from_pairs( & [ ( "rd_data", "read_data" ) ] ) ).is_err() );
+
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        architecture.add_instance( Instance::from_entity( "u0", & cpu ) );
+        architecture.add_instance( Instance::from_entity( "u1", & mem ) );
+        architecture.add_signal_declaration( & SignalDeclaraion::new( "u0_to_u1_bus_addr", "std_logic" ) );
+        assert!( architecture.connect_instance_to_instance_by_mapping( "u0", "u1", "bus", "bus",
+                & PortMapping::from_pairs( & [ ( "rd_data", "read_data" ) ] ) ).is_err() );
+        assert!( architecture.get_instances().get( "u1" ).unwrap().get_interface_by_name( "bus" ).unwrap()
+                .is_unbound() );
+        Ok(())
+    }
+
+    #[test]
+    fn set_matcher() -> Result< (), VhdlError > {
+        let mut bus = EntityInterface::new( "", "bus" );
+        bus.add_port( Port::new( "data", Direction::OUT, "std_logic" ) );
+        let mut agent = bus.clone_inverted();
+        bus.rename( "host" );
+        agent.rename( "agent" );
+        let mut cpu = Entity::new( "cpu" );
+        cpu.add_interface( & bus )?;
+        let mut mem = Entity::new( "mem" );
+        mem.add_interface( & agent )?;
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        architecture.add_instance( Instance::from_entity( "u0", & cpu ) );
+        architecture.add_instance( Instance::from_entity( "u1", & mem ) );
+        architecture.set_matcher( Box::new( ExactMatcher ) );
+        assert!( architecture.connect_instance_to_instance_by_interface( "u0", "u1", "host", "agent" )
+                .is_err() );
+        architecture.set_matcher( Box::new( ClassMatcher ) );
+        architecture.connect_instance_to_instance_by_interface( "u0", "u1", "host", "agent" )?;
+        Ok(())
+    }
+
+    #[test]
+    fn same_roles_do_not_pair() -> Result< (), VhdlError > {
+        let mut host = crate::vhdl::interface_catalog::get_interface( "avalon_mmi" )?;
+        host.remove_generics();
+        host.rename( "bus" );
+        let mut cpu = Entity::new( "cpu" );
+        cpu.add_interface( & host )?;
+        let mut top = Entity::new( ENTITY );
+        top.add_interface( & host.clone_inverted() )?;
+        let mut architecture = Architecture::new( NAME, & top );
+        architecture.add_instance( Instance::from_entity( "u0", & cpu ) );
+        architecture.add_instance( Instance::from_entity( "u1", & cpu ) );
+        let plan = architecture.plan_instance_to_instance( "u0", "u1" )?;
+        assert!( plan.get_report().get_pairs().is_empty() );
+        assert!( plan.get_signals().is_empty() );
+        assert!( architecture.connect_instance_to_instance_by_interface( "u0", "u1", "bus", "bus" ).is_err() );
+        let plan = architecture.plan_instance_to_entity( "u0" )?;
+        assert!( plan.get_report().get_pairs().is_empty() );
+        Ok(())
+    }
+
+    #[test]
+    fn connect_reduced_interface() -> Result< (), VhdlError > {
+        let mut host = crate::vhdl::interface_catalog::get_interface( "avalon_mmi" )?;
+        let mut reduced = EntityInterface::new( "", "avalon_mmi" );
+        for port in host.clone_inverted().get_ports().iter().filter( | p | ! p.is_optional() ) {
+            reduced.add_port( port.clone() );
+        }
+        reduced.rename( "agent" );
+        host.rename( "host" );
+        let mut cpu = Entity::new( "cpu" );
+        cpu.add_interface( & host )?;
+        let mut mem = Entity::new( "mem" );
+        mem.add_interface( & reduced )?;
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        architecture.add_instance( Instance::from_entity( "u0", & cpu ) );
+        architecture.add_instance( Instance::from_entity( "u1", & mem ) );
+        let plan = architecture.plan_instance_to_instance( "u0", "u1" )?;
+        assert_eq!( plan.get_signals().len(), 5 );
+        assert!( plan.to_string().contains( "u0_to_u1_host_address, <tie-off>, u0_to_u1_host_read" ) );
+        architecture.apply( & plan )?;
+        let ports = architecture.get_instances()[ "u0" ].get_interfaces()[ 1 ].get_ports().clone();
+        assert_eq!( ports[ 1 ].get_outer(), "open" );
+        assert_eq!( ports[ 4 ].get_outer(), "(others => '0')" );
+        assert_eq!( ports[ 7 ].get_outer(), "'0'" );
+        assert_eq!( architecture.get_instances()[ "u1" ].get_interfaces()[ 1 ].get_ports()[ 3 ].get_outer(),
+                "u0_to_u1_host_write" );
+
+        // a missing mandatory port can not be tied off
+        let mut broken = EntityInterface::new( "agent", "avalon_mmi" );
+        broken.add_port( reduced.get_ports()[ 0 ].clone() );
+        let mut mem = Entity::new( "mem" );
+        mem.add_interface( & broken )?;
+        architecture.add_instance( Instance::from_entity( "u2", & mem ) );
+        architecture.add_instance( Instance::from_entity( "u3", & cpu ) );
+        assert!( architecture.connect_instance_to_instance( "u3", "u2" ).is_err() );
+        Ok(())
+    }
+
+    #[test]
+    fn connect_fixed_ports() -> Result< (), VhdlError > {
+        let clk_rst = crate::vhdl::interface_catalog::get_interface( "clk_rst" )?;
+        let mut entity = Entity::new( "producer" );
+        entity.add_interface( & clk_rst.clone_inverted_with_rename( "sys" ) )?;
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        for name in [ "u0", "u1", "u2" ] {
+            architecture.add_instance( Instance::from_entity( name, & entity ) );
+        }
+        let mut plan = architecture.plan_instance_to_instance( "u0", "u1" )?;
+        plan.append( architecture.plan_instance_to_instance( "u1", "u2" )? );
+        assert!( plan.get_signals().is_empty() );
+        assert_eq!( plan.get_shared_signals().len(), 2 );
+        architecture.apply( & plan )?;
+        assert_eq!( architecture.get_declarations().get_signals().len(), 2 );
+        assert_eq!( architecture.get_instances()[ "u0" ].get_interfaces()[ 1 ].get_ports()[ 0 ].get_outer(), "sys_clk" );
+        assert_eq!( architecture.get_instances()[ "u1" ].get_interfaces()[ 1 ].get_ports()[ 0 ].get_outer(), "sys_clk" );
+
+        // another clock domain gets nets of its own
+        let mut mem = Entity::new( "consumer" );
+        mem.add_interface( & clk_rst.clone_inverted_with_rename( "mem" ) )?;
+        architecture.add_instance( Instance::from_entity( "u3", & mem ) );
+        architecture.add_instance( Instance::from_entity( "u4", & mem ) );
+        architecture.connect_instance_to_instance_by_interface( "u3", "u4", "mem", "mem" )?;
+        assert_eq!( architecture.get_declarations().get_signals().len(), 4 );
+        assert_eq!( architecture.get_instances()[ "u3" ].get_interfaces()[ 1 ].get_ports()[ 0 ].get_outer(), "mem_clk" );
+
+        // a shared net must not take over a signal of another connection
+        architecture.add_signal_declaration( & SignalDeclaraion::new( "dbg_clk", "std_logic_vector( 1 downto 0 )" ) );
+        let mut dbg = Entity::new( "debugger" );
+        dbg.add_interface( & clk_rst.clone_inverted_with_rename( "dbg" ) )?;
+        architecture.add_instance( Instance::from_entity( "u5", & dbg ) );
+        architecture.add_instance( Instance::from_entity( "u6", & dbg ) );
+        assert!( architecture.connect_instance_to_instance_by_interface( "u5", "u6", "dbg", "dbg" ).is_err() );
+        let plan = architecture.plan_instance_to_instance( "u5", "u6" )?;
+        assert!( architecture.apply( & plan ).is_err() );
+        assert!( ! architecture.get_instances()[ "u5" ].get_interfaces()[ 1 ].is_bound() );
+
+        // the entity port is the shared net
+        let mut sys = clk_rst.clone();
+        sys.rename( "sys" );
+        let mut top = Entity::new( ENTITY );
+        top.add_interface( & sys )?;
+        let mut architecture = Architecture::new( NAME, & top );
+        architecture.add_instance( Instance::from_entity( "u0", & entity ) );
+        architecture.add_instance( Instance::from_entity( "u1", & entity ) );
+        architecture.connect_instance_to_instance_by_interface( "u0", "u1", "sys", "sys" )?;
+        assert!( architecture.get_declarations().get_signals().is_empty() );
+        Ok(())
+    }
+
+    #[test]
+    fn connect_merged_interface() -> Result< (), VhdlError > {
+        let mut bus = crate::vhdl::interface_catalog::get_interface( "avalon_mmi" )?;
+        bus.remove_generics();
+        let clk_rst = crate::vhdl::interface_catalog::get_interface( "clk_rst" )?;
+        let mut clocked = bus.clone_merged( & clk_rst, "avalon_mmi_clocked" )?;
+        clocked.rename( "host" );
+        let mut cpu = Entity::new( "cpu" );
+        cpu.add_interface( & clocked )?;
+        let mut mem = Entity::new( "mem" );
+        mem.add_interface( & bus.clone_inverted_with_rename( "agent" ) )?;
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        architecture.add_instance( Instance::from_entity( "u0", & cpu ) );
+        architecture.add_instance( Instance::from_entity( "u1", & mem ) );
+        architecture.connect_instance_to_instance( "u0", "u1" )?;
+        let host = & architecture.get_instances()[ "u0" ].get_interfaces()[ 1 ];
+        assert_eq!( host.get_ports()[ 0 ].get_outer(), "u0_to_u1_host_address" );
+        // clock and reset are left for another connection
+        assert!( ! host.get_ports()[ 8 ].is_bound() );
+        Ok(())
+    }
+
+    #[test]
+    fn signal_bundle() -> Result< (), VhdlError > {
+        // u0 drives the requests, the entity forwards them to an outside agent and the bundle
+        // taps the transfers in between
+        let host = crate::vhdl::interface_catalog::get_interface( "apb" )?;
+        let mut bus = host.clone();
+        bus.rename( "bus" );
+        let mut cpu = Entity::new( "cpu" );
+        cpu.add_interface( & bus )?;
+        let mut uart = Entity::new( "uart" );
+        uart.add_interface( & host.clone_inverted_with_rename( "regs" ) )?;
+        let mut top = Entity::new( ENTITY );
+        for generic in host.get_generics() {
+            top.add_generic( generic.clone() );
+        }
+        let mut ext = host.clone();
+        ext.remove_generics();
+        ext.rename( "ext" );
+        top.add_interface( & ext )?;
+        let mut architecture = Architecture::new( NAME, & top );
+        architecture.add_instance( Instance::from_entity( "u0", & cpu ) );
+        architecture.declare_interface_signals( & host, "apb" )?;
+        assert_eq!( architecture.get_declarations().get_signals().len(), host.get_ports().len() );
+        assert!( architecture.declare_interface_signals( & host, "apb" ).is_err() );
+        architecture.connect_instance_to_signal_bundle( "u0", "bus", "apb" )?;
+        architecture.connect_entity_to_signal_bundle( "ext", "apb" )?;
+        assert_eq!( architecture.get_instances()[ "u0" ].get_interfaces()[ 1 ].get_ports()[ 0 ].get_outer(), "apb_paddr" );
+        for generic in [ "ADDR_WIDTH", "DATA_WIDTH" ] {
+            architecture.get_instance_mut( "u0" )?.set_generic_value( & format!( "bus_{}", generic ), generic )?;
+        }
+        architecture.finalize()?;
+        assert!( lint( & architecture ).is_empty() );
+        let source = architecture.to_source_code( 0 );
+        assert!( source.contains( "    signal apb_paddr : std_logic_vector( ADDR_WIDTH - 1 downto 0 );\n" ) );
+        assert!( source.contains( "    ext_paddr <= apb_paddr;\n" ) );
+        assert!( source.contains( "    apb_prdata <= ext_prdata;\n" ) );
+
+        // the read data is driven by the entity input already
+        architecture.add_instance( Instance::from_entity( "u1", & uart ) );
+        assert!( architecture.connect_instance_to_signal_bundle( "u1", "regs", "apb" ).is_err() );
+        assert!( architecture.connect_entity_to_signal_bundle( "ext", "apb" ).is_err() );
+
+        let mut other = Entity::new( "other" );
+        other.add_interface( & EntityInterface::new( "bus", "wishbone" ) )?;
+        architecture.add_instance( Instance::from_entity( "u2", & other ) );
+        assert!( architecture.connect_instance_to_signal_bundle( "u2", "bus", "apb" ).is_err() );
+        assert!( architecture.connect_instance_to_signal_bundle( "u0", "bus", "axi" ).is_err() );
+        Ok(())
+    }
+
+    #[test]
+    fn signal_bundle_generics() -> Result< (), VhdlError > {
+        let mut host = crate::vhdl::interface_catalog::get_interface( "apb" )?;
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        assert!( architecture.declare_interface_signals( & host, "apb" ).is_err() );
+        host.get_generic_mut( "ADDR_WIDTH" ).unwrap().set_default( "12" );
+        host.get_generic_mut( "DATA_WIDTH" ).unwrap().set_default( "32" );
+        architecture.declare_interface_signals( & host, "apb" )?;
+        let source = architecture.to_source_code( 0 );
+        assert!( source.contains( "    signal apb_paddr : std_logic_vector( 12 - 1 downto 0 );\n" ) );
+        Ok(())
+    }
+
+    #[test]
+    fn connect_instance_to_instance_tie() -> Result< (), VhdlError > {
+        let mut bus = EntityInterface::new( "", "avalon_mm" );
+        bus.add_port( Port::new( "data", Direction::OUT, "std_logic" ) );
+        let mut cpu = Entity::new( "cpu" );
+        for name in [ "host_a", "host_b" ] {
+            let mut interface = bus.clone();
+            interface.rename( name );
+            cpu.add_interface( & interface )?;
+        }
+        let mut mem = Entity::new( "mem" );
+        for name in [ "port_0", "port_1" ] {
+            let mut interface = bus.clone_inverted();
+            interface.rename( name );
+            mem.add_interface( & interface )?;
+        }
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        architecture.add_instance( Instance::from_entity( "u0", & cpu ) );
+        architecture.add_instance( Instance::from_entity( "u1", & mem ) );
+        assert!( architecture.connect_instance_to_instance( "u0", "u1" ).is_err() );
+        assert!( architecture.get_declarations().get_signals().is_empty() );
+        architecture.set_tie_break( TieBreak::First );
+        let report = architecture.connect_instance_to_instance( "u0", "u1" )?;
+        assert_eq!( report.to_string(), concat!(
+                "pair: u0.host_a -> u1.port_0 (score 1, class)\n",
+                "pair: u0.host_b -> u1.port_1 (score 1, class)\n",
+                "tie: u0.host_a ties between u1.port_0, u1.port_1 (score 1)\n" ) );
+        Ok(())
+    }
+
+    #[test]
+    fn plan_and_apply() -> Result< (), VhdlError > {
+        let mut bus = EntityInterface::new( "", "bus" );
+        bus.add_port( Port::new( "data", Direction::OUT, "std_logic" ) );
+        let mut agent = bus.clone_inverted();
+        bus.rename( "host" );
+        agent.rename( "agent" );
+        let mut cpu = Entity::new( "cpu" );
+        cpu.add_interface( & bus )?;
+        let mut mem = Entity::new( "mem" );
+        mem.add_interface( & agent )?;
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        architecture.add_instance( Instance::from_entity( "u0", & cpu ) );
+        architecture.add_instance( Instance::from_entity( "u1", & mem ) );
+        let mut plan = architecture.plan_instance_to_instance( "u0", "u1" )?;
+        assert_eq!( plan.to_string(), concat!(
+                "bind u0.host to signals u0_to_u1_host_data\n",
+                "bind u1.agent to signals u0_to_u1_host_data\n",
+                "declare signal u0_to_u1_host_data : std_logic;\n" ) );
+        assert!( architecture.get_declarations().get_signals().is_empty() );
+        // rename the planned signal before applying the plan
+        plan.get_signals_mut()[ 0 ] = SignalDeclaraion::new( "data", "std_logic" );
+        for binding in plan.get_bindings_mut() {
+            if let PlannedBinding::SignalList { signals, .. } = binding {
+                signals[ 0 ] = "data".to_string();
+            }
+        }
+        architecture.apply( & plan )?;
+        let outer = architecture.get_instances().get( "u1" ).unwrap().get_interfaces()[ 1 ]
+                .get_ports()[ 0 ].get_outer();
+        assert_eq!( outer, "data" );
+        assert_eq!( architecture.get_declarations().get_signals()[ 0 ].get_name(), "data" );
+        Ok(())
+    }
+
+    #[test]
+    fn auto_connect() -> Result< (), VhdlError > {
+        let mut clk = EntityInterface::new( "", "clk_rst" );
+        clk.add_port( Port::new( "clk", Direction::IN, "std_logic" ) );
+        clk.rename( "sys" );
+        let mut bus = EntityInterface::new( "", "bus" );
+        bus.add_generic( Generic::new( "WIDTH", "positive" ) );
+        bus.add_port( Port::new( "data", Direction::OUT, "std_logic_vector( WIDTH - 1 downto 0 )" ) );
+        let mut agent = bus.clone_inverted();
+        bus.rename( "host" );
+        agent.rename( "agent" );
+        let mut cpu = Entity::new( "cpu" );
+        cpu.add_interface( & clk )?;
+        cpu.add_interface( & bus )?;
+        cpu.add_port( Port::new( "irq", Direction::IN, "std_logic" ) );
+        let mut mem = Entity::new( "mem" );
+        mem.add_interface( & clk )?;
+        mem.add_interface( & agent )?;
+        let mut top = Entity::new( ENTITY );
+        top.add_generic( Generic::new( "host_WIDTH", "positive" ) );
+        top.add_interface( & clk )?;
+        let mut architecture = Architecture::new( NAME, & top );
+        architecture.add_instance( Instance::from_entity( "u0", & cpu ) );
+        architecture.add_instance( Instance::from_entity( "u1", & mem ) );
+        let report = architecture.auto_connect( & LeftoverPolicy::Promote( PrefixPolicy::InstanceName ) )?;
+        assert_eq!( report.to_string(), concat!(
+                "pair: u0.sys -> sys (score 3, name)\n",
+                "pair: u0.host -> u1.agent (score 1, class)\n",
+                "pair: u1.sys -> sys (score 3, name)\n",
+                "pair: u0.host_WIDTH -> host_WIDTH (score 3, name)\n",
+                "pair: u1.agent_WIDTH -> host_WIDTH (score 1, class)\n" ) );
+        assert!( architecture.get_entity().get_ports().iter().any( | p | p.get_name() == "u0_irq" ) );
+        assert!( crate::vhdl::lint::lint( & architecture ).is_empty() );
+        Ok(())
+    }
+
+    fn new_broadcast_architecture( sink_direction : Direction ) -> Result< Architecture, VhdlError > {
+        let mut config = EntityInterface::new( "", "config" );
+        config.add_port( Port::new( "mode", Direction::OUT, "std_logic" ) );
+        config.add_port( Port::new( "ack", sink_direction.get_inverted(), "std_logic" ) );
+        let mut sink = config.clone_inverted();
+        config.rename( "cfg" );
+        sink.rename( "cfg" );
+        let mut source_entity = Entity::new( "source" );
+        source_entity.add_interface( & config )?;
+        let mut sink_entity = Entity::new( "sink" );
+        sink_entity.add_interface( & sink )?;
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        architecture.add_instance( Instance::from_entity( "src", & source_entity ) );
+        for name in [ "u0", "u1", "u2" ] {
+            architecture.add_instance( Instance::from_entity( name, & sink_entity ) );
+        }
+        Ok( architecture )
+    }
+
+    #[test]
+    fn broadcast_from_instance() -> Result< (), VhdlError > {
+        let mut architecture = new_broadcast_architecture( Direction::IN )?;
+        architecture.broadcast_from_instance( "src", "cfg", & [ "u0", "u1", "u2" ] )?;
+        let signals : Vec< & String > = architecture.get_declarations().get_signals().iter()
+                .map( | s | s.get_name() ).collect();
+        assert_eq!( signals, vec![ "src_cfg_mode", "src_cfg_ack" ] );
+        let netlist = architecture.get_netlist();
+        let net = netlist.get_net( "src_cfg_mode" ).unwrap();
+        assert_eq!( net.get_drivers().len(), 1 );
+        assert_eq!( net.get_loads().len(), 3 );
+        assert!( crate::vhdl::lint::lint( & architecture ).is_empty() );
+
+        let mut architecture = new_broadcast_architecture( Direction::OUT )?;
+        assert!( architecture.broadcast_from_instance( "src", "cfg", & [ "u0", "u1" ] ).is_err() );
+        architecture.broadcast_from_instance( "src", "cfg", & [ "u0" ] )?;
+        Ok(())
+    }
+
+    #[test]
+    fn broadcast_by_member_name() -> Result< (), VhdlError > {
+        let mut config = EntityInterface::new( "", "config" );
+        config.add_port( Port::new( "mode", Direction::OUT, "std_logic" ) );
+        let mut level = Port::new( "level", Direction::OUT, "std_logic" );
+        level.set_optional( true );
+        config.add_port( level );
+        config.add_port( Port::new( "ack", Direction::IN, "std_logic" ) );
+        config.rename( "cfg" );
+        let mut sink = EntityInterface::new( "", "config" );
+        sink.add_port( Port::new( "ack", Direction::OUT, "std_logic" ) );
+        sink.add_port( Port::new( "mode", Direction::IN, "std_logic" ) );
+        sink.rename( "cfg" );
+        let mut source_entity = Entity::new( "source" );
+        source_entity.add_interface( & config )?;
+        let mut sink_entity = Entity::new( "sink" );
+        sink_entity.add_interface( & sink )?;
+        let mut architecture = Architecture::new( NAME, & Entity::new( ENTITY ) );
+        architecture.add_instance( Instance::from_entity( "src", & source_entity ) );
+        architecture.add_instance( Instance::from_entity( "u0", & sink_entity ) );
+        architecture.add_instance( Instance::from_entity( "u1", & sink_entity ) );
+        assert!( architecture.broadcast_from_instance( "src", "cfg", & [ "u0", "u0" ] ).is_err() );
+        let error = architecture.broadcast_from_instance( "src", "cfg", & [ "u0", "u1" ] ).unwrap_err();
+        assert!( error.to_string().contains( "src_cfg_ack" ) );
+        architecture.broadcast_from_instance( "src", "cfg", & [ "u0" ] )?;
+        let actuals : Vec< Option< String > > = architecture.get_instance( "u0" )?.get_interfaces()[ 1 ].get_ports()
+                .iter().map( | p | p.get_actual().and_then( | a | a.get_net() ) ).collect();
+        assert_eq!( actuals, vec![ Some( "src_cfg_ack".to_string() ), Some( "src_cfg_mode".to_string() ) ] );
+        Ok(())
+    }
+
+    #[test]
+    fn broadcast_from_entity() -> Result< (), VhdlError > {
+        let mut clk = EntityInterface::new( "", "clk_rst" );
+        clk.add_port( Port::new( "clk", Direction::IN, "std_logic" ) );
+        clk.rename( "sys" );
+        let mut sub = Entity::new( "sub" );
+        sub.add_interface( & clk )?;
+        let mut top = Entity::new( ENTITY );
+        top.add_interface( & clk )?;
+        let mut architecture = Architecture::new( NAME, & top );
+        architecture.add_instance( Instance::from_entity( "u0", & sub ) );
+        architecture.add_instance( Instance::from_entity( "u1", & sub ) );
+        let report = architecture.broadcast_from_entity( "sys", & [ "u0", "u1" ] )?;
+        assert_eq!( report.get_pairs().len(), 2 );
+        let net = architecture.get_netlist().get_net( "sys_clk" ).unwrap().clone();
+        assert_eq!( net.get_loads().len(), 2 );
+        Ok(())
+    }
+
     /**
      * Create a architecture with a constant declaration.
      */
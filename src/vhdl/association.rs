@@ -0,0 +1,44 @@
+use crate::element::Element;
+use crate::vhdl::actual::Actual;
+
+/**
+ * A single `formal => actual` element of a generic or port map.
+ */
+#[derive(Debug, Clone)]
+pub struct Association {
+    formal : String,
+    actual : Actual,
+}
+
+impl Association {
+    pub fn new( formal : & str, actual : & Actual ) -> Association {
+        Association { formal : formal.to_string(), actual : actual.clone() }
+    }
+
+    pub fn get_formal( & self ) -> & String {
+        & self.formal
+    }
+
+    pub fn get_actual( & self ) -> & Actual {
+        & self.actual
+    }
+}
+
+impl Element for Association {
+    fn to_source_code( & self, indent : usize ) -> String {
+        let indent_str = crate::util::indent( indent );
+        format!( "{}{} => {}", indent_str, self.formal, self.actual.to_source_code( 0 ) )
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn association() {
+        let association = Association::new( "data( 3 downto 0 )", & Actual::name( "x" ) );
+        assert_eq!( association.to_source_code( 1 ), "    data( 3 downto 0 ) => x" );
+    }
+}
@@ -0,0 +1,710 @@
+use std::collections::HashMap;
+use crate::vhdl::prefix_policy::PrefixPolicy;
+
+/**
+ * What `Architecture::auto_connect` does with interfaces, ports and generics left unbound.
+ */
+#[derive(Debug, Clone)]
+pub enum LeftoverPolicy {
+    /// Leave them unbound
+    Keep,
+    /// Add them to the entity, see `Architecture::promote_unbound`
+    Promote( PrefixPolicy ),
+    /// Tie them off, see `Architecture::finalize`
+    TieOff,
+}
+
+/**
+ * Result of `get_max_weight_matching`: the matched node pairs and, for nodes whose partner is
+ * not unique among the optimal matchings, the alternative partners (`None` for unmatched).
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WeightedMatching {
+    pub pairs : Vec< ( usize, usize ) >,
+    pub ties : Vec< ( usize, Vec< Option< usize > > ) >,
+}
+
+/**
+ * Finds a matching of maximum total weight in an undirected graph with Edmonds' blossom
+ * algorithm.  The nodes of each connected component are decided in ascending order: a node is
+ * left unmatched or matched to the lowest partner that still allows an optimal matching of the
+ * remaining nodes, every other such choice is reported as a tie.
+ */
+pub fn get_max_weight_matching( node_count : usize, edges : & [ ( usize, usize, u32 ) ] )
+        -> WeightedMatching {
+    let mut matching = WeightedMatching::default();
+    for component in get_components( node_count, edges ) {
+        let local : Vec< ( usize, usize, u32 ) > = edges.iter()
+                .filter( | ( a, _, _ ) | component.contains( a ) )
+                .map( | ( a, b, w ) | ( component.iter().position( | n | n == a ).unwrap(),
+                        component.iter().position( | n | n == b ).unwrap(), * w ) )
+                .collect();
+        let result = get_component_matching( component.len(), & local );
+        for ( a, b ) in result.pairs {
+            matching.pairs.push( ( component[ a ], component[ b ] ) );
+        }
+        for ( node, candidates ) in result.ties {
+            matching.ties.push( ( component[ node ],
+                    candidates.iter().map( | c | c.map( | c | component[ c ] ) ).collect() ) );
+        }
+    }
+    matching.pairs.sort();
+    matching
+}
+
+fn get_components( node_count : usize, edges : & [ ( usize, usize, u32 ) ] ) -> Vec< Vec< usize > > {
+    let mut parent : Vec< usize > = ( 0..node_count ).collect();
+    fn find( parent : & mut [ usize ], node : usize ) -> usize {
+        let mut root = node;
+        while parent[ root ] != root {
+            root = parent[ root ];
+        }
+        parent[ node ] = root;
+        root
+    }
+    for ( a, b, _ ) in edges {
+        let root_a = find( & mut parent, * a );
+        let root_b = find( & mut parent, * b );
+        parent[ root_a ] = root_b;
+    }
+    let mut components : Vec< Vec< usize > > = Vec::new();
+    let mut roots : HashMap< usize, usize > = HashMap::new();
+    for node in 0..node_count {
+        if ! edges.iter().any( | ( a, b, _ ) | * a == node || * b == node ) {
+            continue;
+        }
+        let root = find( & mut parent, node );
+        let idx = * roots.entry( root ).or_insert_with( || {
+            components.push( Vec::new() );
+            components.len() - 1
+        } );
+        components[ idx ].push( node );
+    }
+    components
+}
+
+fn get_component_matching( node_count : usize, edges : & [ ( usize, usize, u32 ) ] ) -> WeightedMatching {
+    let mut weights = vec![ vec![ 0; node_count ]; node_count ];
+    for ( a, b, w ) in edges {
+        weights[ * a ][ * b ] = weights[ * a ][ * b ].max( * w );
+        weights[ * b ][ * a ] = weights[ * b ][ * a ].max( * w );
+    }
+    let mut matching = WeightedMatching::default();
+    let mut free = vec![ true; node_count ];
+    for node in 0..node_count {
+        if ! free[ node ] {
+            continue;
+        }
+        free[ node ] = false;
+        let mut options = vec![ ( None, get_best_weight( & weights, & free ) ) ];
+        for partner in 0..node_count {
+            let weight = weights[ node ][ partner ];
+            if weight > 0 && free[ partner ] {
+                free[ partner ] = false;
+                options.push( ( Some( partner ), weight as i64 + get_best_weight( & weights, & free ) ) );
+                free[ partner ] = true;
+            }
+        }
+        let best = options.iter().map( | ( _, total ) | * total ).max().unwrap();
+        let best_options : Vec< Option< usize > > = options.iter()
+                .filter( | ( _, total ) | * total == best ).map( | ( partner, _ ) | * partner ).collect();
+        if best_options.len() > 1 {
+            matching.ties.push( ( node, best_options.clone() ) );
+        }
+        if let Some( partner ) = best_options[ 0 ] {
+            free[ partner ] = false;
+            matching.pairs.push( ( node, partner ) );
+        }
+    }
+    matching
+}
+
+/**
+ * Total weight of a maximum weight matching of the `free` nodes.
+ */
+fn get_best_weight( weights : & [ Vec< u32 > ], free : & [ bool ] ) -> i64 {
+    let mut edges = Vec::new();
+    for a in 0..weights.len() {
+        for b in a + 1..weights.len() {
+            if free[ a ] && free[ b ] && weights[ a ][ b ] > 0 {
+                edges.push( ( a, b, weights[ a ][ b ] as i64 ) );
+            }
+        }
+    }
+    let mates = Blossom::new( weights.len(), edges ).solve();
+    let mut total = 0;
+    for ( a, mate ) in mates.iter().enumerate() {
+        if let Some( b ) = mate {
+            if a < * b {
+                total += weights[ a ][ * b ] as i64;
+            }
+        }
+    }
+    total
+}
+
+/**
+ * Edmonds' maximum weight matching in O(n³) following the primal-dual formulation of Galil,
+ * "Efficient algorithms for finding maximum matching in graphs".  Edge endpoints are numbered
+ * `2 * k` and `2 * k + 1` for edge `k`, vertices `0..n` and blossoms `n..2 * n`.  Labels are 1
+ * for S, 2 for T and 0 for free, dual variables of vertices are stored doubled so that integer
+ * weights keep all computations integer.
+ */
+struct Blossom {
+    n : usize,
+    edges : Vec< ( usize, usize, i64 ) >,
+    endpoint : Vec< usize >,
+    neighbend : Vec< Vec< usize > >,
+    mate : Vec< Option< usize > >,
+    label : Vec< u8 >,
+    labelend : Vec< Option< usize > >,
+    inblossom : Vec< usize >,
+    blossomparent : Vec< Option< usize > >,
+    blossomchilds : Vec< Vec< usize > >,
+    blossombase : Vec< Option< usize > >,
+    blossomendps : Vec< Vec< usize > >,
+    bestedge : Vec< Option< usize > >,
+    blossombestedges : Vec< Option< Vec< usize > > >,
+    unusedblossoms : Vec< usize >,
+    dualvar : Vec< i64 >,
+    allowedge : Vec< bool >,
+    queue : Vec< usize >,
+}
+
+/**
+ * Index into a cyclic list with a possibly negative position.
+ */
+fn cyclic( list : & [ usize ], idx : isize ) -> usize {
+    let len = list.len() as isize;
+    list[ ( ( idx % len + len ) % len ) as usize ]
+}
+
+impl Blossom {
+    fn new( n : usize, edges : Vec< ( usize, usize, i64 ) > ) -> Blossom {
+        let maxweight = edges.iter().map( | e | e.2 ).max().unwrap_or( 0 ).max( 0 );
+        let mut endpoint = Vec::new();
+        let mut neighbend = vec![ Vec::new(); n ];
+        for ( k, ( i, j, _ ) ) in edges.iter().enumerate() {
+            endpoint.push( * i );
+            endpoint.push( * j );
+            neighbend[ * i ].push( 2 * k + 1 );
+            neighbend[ * j ].push( 2 * k );
+        }
+        let mut dualvar = vec![ maxweight; n ];
+        dualvar.extend( vec![ 0; n ] );
+        let mut blossombase : Vec< Option< usize > > = ( 0..n ).map( Some ).collect();
+        blossombase.extend( vec![ None; n ] );
+        let edge_count = edges.len();
+        Blossom { n, edges, endpoint, neighbend, mate : vec![ None; n ], label : vec![ 0; 2 * n ],
+                labelend : vec![ None; 2 * n ], inblossom : ( 0..n ).collect(),
+                blossomparent : vec![ None; 2 * n ], blossomchilds : vec![ Vec::new(); 2 * n ],
+                blossombase, blossomendps : vec![ Vec::new(); 2 * n ], bestedge : vec![ None; 2 * n ],
+                blossombestedges : vec![ None; 2 * n ], unusedblossoms : ( n..2 * n ).collect(),
+                dualvar, allowedge : vec![ false; edge_count ], queue : Vec::new() }
+    }
+
+    fn slack( & self, k : usize ) -> i64 {
+        let ( i, j, wt ) = self.edges[ k ];
+        self.dualvar[ i ] + self.dualvar[ j ] - 2 * wt
+    }
+
+    fn leaves( & self, b : usize ) -> Vec< usize > {
+        if b < self.n {
+            return vec![ b ];
+        }
+        self.blossomchilds[ b ].iter().flat_map( | t | self.leaves( * t ) ).collect()
+    }
+
+    fn assign_label( & mut self, w : usize, t : u8, p : Option< usize > ) {
+        let b = self.inblossom[ w ];
+        self.label[ w ] = t;
+        self.label[ b ] = t;
+        self.labelend[ w ] = p;
+        self.labelend[ b ] = p;
+        self.bestedge[ w ] = None;
+        self.bestedge[ b ] = None;
+        if t == 1 {
+            let leaves = self.leaves( b );
+            self.queue.extend( leaves );
+        }
+        else if t == 2 {
+            let base = self.blossombase[ b ].unwrap();
+            let mate = self.mate[ base ].unwrap();
+            self.assign_label( self.endpoint[ mate ], 1, Some( mate ^ 1 ) );
+        }
+    }
+
+    /**
+     * Traces back from `v` and `w` to find a new blossom or an augmenting path, returns the base
+     * of the blossom.
+     */
+    fn scan_blossom( & mut self, v : usize, w : usize ) -> Option< usize > {
+        let mut path = Vec::new();
+        let mut base = None;
+        let ( mut v, mut w ) = ( Some( v ), Some( w ) );
+        while let Some( current ) = v {
+            let b = self.inblossom[ current ];
+            if self.label[ b ] & 4 != 0 {
+                base = self.blossombase[ b ];
+                break;
+            }
+            path.push( b );
+            self.label[ b ] = 5;
+            v = match self.labelend[ b ] {
+                None => None,
+                Some( p ) => {
+                    let t = self.inblossom[ self.endpoint[ p ] ];
+                    Some( self.endpoint[ self.labelend[ t ].unwrap() ] )
+                },
+            };
+            if w.is_some() {
+                std::mem::swap( & mut v, & mut w );
+            }
+        }
+        for b in path {
+            self.label[ b ] = 1;
+        }
+        base
+    }
+
+    fn add_blossom( & mut self, base : usize, k : usize ) {
+        let ( v, w, _ ) = self.edges[ k ];
+        let bb = self.inblossom[ base ];
+        let mut bv = self.inblossom[ v ];
+        let mut bw = self.inblossom[ w ];
+        let b = self.unusedblossoms.pop().unwrap();
+        self.blossombase[ b ] = Some( base );
+        self.blossomparent[ b ] = None;
+        self.blossomparent[ bb ] = Some( b );
+        let mut path = Vec::new();
+        let mut endps = Vec::new();
+        while bv != bb {
+            self.blossomparent[ bv ] = Some( b );
+            path.push( bv );
+            let p = self.labelend[ bv ].unwrap();
+            endps.push( p );
+            bv = self.inblossom[ self.endpoint[ p ] ];
+        }
+        path.push( bb );
+        path.reverse();
+        endps.reverse();
+        endps.push( 2 * k );
+        while bw != bb {
+            self.blossomparent[ bw ] = Some( b );
+            path.push( bw );
+            let p = self.labelend[ bw ].unwrap();
+            endps.push( p ^ 1 );
+            bw = self.inblossom[ self.endpoint[ p ] ];
+        }
+        self.blossomchilds[ b ] = path.clone();
+        self.blossomendps[ b ] = endps;
+        self.label[ b ] = 1;
+        self.labelend[ b ] = self.labelend[ bb ];
+        self.dualvar[ b ] = 0;
+        for leaf in self.leaves( b ) {
+            if self.label[ self.inblossom[ leaf ] ] == 2 {
+                self.queue.push( leaf );
+            }
+            self.inblossom[ leaf ] = b;
+        }
+        let mut bestedgeto : Vec< Option< usize > > = vec![ None; 2 * self.n ];
+        for bv in path {
+            let nblists : Vec< Vec< usize > > = match self.blossombestedges[ bv ].take() {
+                Some( list ) => vec![ list ],
+                None => self.leaves( bv ).iter()
+                        .map( | leaf | self.neighbend[ * leaf ].iter().map( | p | p / 2 ).collect() ).collect(),
+            };
+            for nblist in nblists {
+                for k in nblist {
+                    let ( i, mut j, _ ) = self.edges[ k ];
+                    if self.inblossom[ j ] == b {
+                        j = i;
+                    }
+                    let bj = self.inblossom[ j ];
+                    if bj != b && self.label[ bj ] == 1 &&
+                            bestedgeto[ bj ].is_none_or( | e | self.slack( k ) < self.slack( e ) ) {
+                        bestedgeto[ bj ] = Some( k );
+                    }
+                }
+            }
+            self.bestedge[ bv ] = None;
+        }
+        let list : Vec< usize > = bestedgeto.into_iter().flatten().collect();
+        let mut best = None;
+        for k in & list {
+            if best.is_none_or( | e | self.slack( * k ) < self.slack( e ) ) {
+                best = Some( * k );
+            }
+        }
+        self.blossombestedges[ b ] = Some( list );
+        self.bestedge[ b ] = best;
+    }
+
+    fn expand_blossom( & mut self, b : usize, endstage : bool ) {
+        for s in self.blossomchilds[ b ].clone() {
+            self.blossomparent[ s ] = None;
+            if s < self.n {
+                self.inblossom[ s ] = s;
+            }
+            else if endstage && self.dualvar[ s ] == 0 {
+                self.expand_blossom( s, endstage );
+            }
+            else {
+                for leaf in self.leaves( s ) {
+                    self.inblossom[ leaf ] = s;
+                }
+            }
+        }
+        if ! endstage && self.label[ b ] == 2 {
+            let childs = self.blossomchilds[ b ].clone();
+            let endps = self.blossomendps[ b ].clone();
+            let entrychild = self.inblossom[ self.endpoint[ self.labelend[ b ].unwrap() ^ 1 ] ];
+            let mut j = childs.iter().position( | c | * c == entrychild ).unwrap() as isize;
+            let ( jstep, endptrick ) : ( isize, usize ) = if j & 1 != 0 {
+                j -= childs.len() as isize;
+                ( 1, 0 )
+            }
+            else {
+                ( -1, 1 )
+            };
+            let mut p = self.labelend[ b ].unwrap();
+            while j != 0 {
+                self.label[ self.endpoint[ p ^ 1 ] ] = 0;
+                let q = cyclic( & endps, j - endptrick as isize );
+                self.label[ self.endpoint[ q ^ endptrick ^ 1 ] ] = 0;
+                self.assign_label( self.endpoint[ p ^ 1 ], 2, Some( p ) );
+                self.allowedge[ q / 2 ] = true;
+                j += jstep;
+                p = cyclic( & endps, j - endptrick as isize ) ^ endptrick;
+                self.allowedge[ p / 2 ] = true;
+                j += jstep;
+            }
+            let bv = cyclic( & childs, j );
+            self.label[ self.endpoint[ p ^ 1 ] ] = 2;
+            self.label[ bv ] = 2;
+            self.labelend[ self.endpoint[ p ^ 1 ] ] = Some( p );
+            self.labelend[ bv ] = Some( p );
+            self.bestedge[ bv ] = None;
+            j += jstep;
+            while cyclic( & childs, j ) != entrychild {
+                let bv = cyclic( & childs, j );
+                if self.label[ bv ] == 1 {
+                    j += jstep;
+                    continue;
+                }
+                if let Some( v ) = self.leaves( bv ).into_iter().find( | v | self.label[ * v ] != 0 ) {
+                    self.label[ v ] = 0;
+                    let mate = self.mate[ self.blossombase[ bv ].unwrap() ].unwrap();
+                    self.label[ self.endpoint[ mate ] ] = 0;
+                    self.assign_label( v, 2, self.labelend[ v ] );
+                }
+                j += jstep;
+            }
+        }
+        self.label[ b ] = 0;
+        self.labelend[ b ] = None;
+        self.blossomchilds[ b ].clear();
+        self.blossomendps[ b ].clear();
+        self.blossombase[ b ] = None;
+        self.blossombestedges[ b ] = None;
+        self.bestedge[ b ] = None;
+        self.unusedblossoms.push( b );
+    }
+
+    fn augment_blossom( & mut self, b : usize, v : usize ) {
+        let mut t = v;
+        while self.blossomparent[ t ] != Some( b ) {
+            t = self.blossomparent[ t ].unwrap();
+        }
+        if t >= self.n {
+            self.augment_blossom( t, v );
+        }
+        let childs = self.blossomchilds[ b ].clone();
+        let endps = self.blossomendps[ b ].clone();
+        let i = childs.iter().position( | c | * c == t ).unwrap();
+        let mut j = i as isize;
+        let ( jstep, endptrick ) : ( isize, usize ) = if i & 1 != 0 {
+            j -= childs.len() as isize;
+            ( 1, 0 )
+        }
+        else {
+            ( -1, 1 )
+        };
+        while j != 0 {
+            j += jstep;
+            let t = cyclic( & childs, j );
+            let p = cyclic( & endps, j - endptrick as isize ) ^ endptrick;
+            if t >= self.n {
+                self.augment_blossom( t, self.endpoint[ p ] );
+            }
+            j += jstep;
+            let t = cyclic( & childs, j );
+            if t >= self.n {
+                self.augment_blossom( t, self.endpoint[ p ^ 1 ] );
+            }
+            self.mate[ self.endpoint[ p ] ] = Some( p ^ 1 );
+            self.mate[ self.endpoint[ p ^ 1 ] ] = Some( p );
+        }
+        self.blossomchilds[ b ].rotate_left( i );
+        self.blossomendps[ b ].rotate_left( i );
+        self.blossombase[ b ] = self.blossombase[ self.blossomchilds[ b ][ 0 ] ];
+    }
+
+    fn augment_matching( & mut self, k : usize ) {
+        let ( v, w, _ ) = self.edges[ k ];
+        for ( mut s, mut p ) in [ ( v, 2 * k + 1 ), ( w, 2 * k ) ] {
+            loop {
+                let bs = self.inblossom[ s ];
+                if bs >= self.n {
+                    self.augment_blossom( bs, s );
+                }
+                self.mate[ s ] = Some( p );
+                let labelend = match self.labelend[ bs ] {
+                    Some( labelend ) => labelend,
+                    None => break,
+                };
+                let t = self.endpoint[ labelend ];
+                let bt = self.inblossom[ t ];
+                let bt_end = self.labelend[ bt ].unwrap();
+                s = self.endpoint[ bt_end ];
+                let j = self.endpoint[ bt_end ^ 1 ];
+                if bt >= self.n {
+                    self.augment_blossom( bt, j );
+                }
+                self.mate[ j ] = Some( bt_end );
+                p = bt_end ^ 1;
+            }
+        }
+    }
+
+    /**
+     * Returns the partner of every vertex.
+     */
+    fn solve( mut self ) -> Vec< Option< usize > > {
+        let n = self.n;
+        for _ in 0..n {
+            self.label = vec![ 0; 2 * n ];
+            self.bestedge = vec![ None; 2 * n ];
+            for b in n..2 * n {
+                self.blossombestedges[ b ] = None;
+            }
+            self.allowedge = vec![ false; self.edges.len() ];
+            self.queue.clear();
+            for v in 0..n {
+                if self.mate[ v ].is_none() && self.label[ self.inblossom[ v ] ] == 0 {
+                    self.assign_label( v, 1, None );
+                }
+            }
+            let mut augmented = false;
+            loop {
+                while ! augmented {
+                    let v = match self.queue.pop() {
+                        Some( v ) => v,
+                        None => break,
+                    };
+                    for p in self.neighbend[ v ].clone() {
+                        let k = p / 2;
+                        let w = self.endpoint[ p ];
+                        if self.inblossom[ v ] == self.inblossom[ w ] {
+                            continue;
+                        }
+                        let mut kslack = 0;
+                        if ! self.allowedge[ k ] {
+                            kslack = self.slack( k );
+                            if kslack <= 0 {
+                                self.allowedge[ k ] = true;
+                            }
+                        }
+                        if self.allowedge[ k ] {
+                            if self.label[ self.inblossom[ w ] ] == 0 {
+                                self.assign_label( w, 2, Some( p ^ 1 ) );
+                            }
+                            else if self.label[ self.inblossom[ w ] ] == 1 {
+                                match self.scan_blossom( v, w ) {
+                                    Some( base ) => self.add_blossom( base, k ),
+                                    None => {
+                                        self.augment_matching( k );
+                                        augmented = true;
+                                        break;
+                                    },
+                                }
+                            }
+                            else if self.label[ w ] == 0 {
+                                self.label[ w ] = 2;
+                                self.labelend[ w ] = Some( p ^ 1 );
+                            }
+                        }
+                        else if self.label[ self.inblossom[ w ] ] == 1 {
+                            let b = self.inblossom[ v ];
+                            if self.bestedge[ b ].is_none_or( | e | kslack < self.slack( e ) ) {
+                                self.bestedge[ b ] = Some( k );
+                            }
+                        }
+                        else if self.label[ w ] == 0 &&
+                                self.bestedge[ w ].is_none_or( | e | kslack < self.slack( e ) ) {
+                            self.bestedge[ w ] = Some( k );
+                        }
+                    }
+                }
+                if augmented {
+                    break;
+                }
+                // without maximum cardinality a vertex dual reaching zero ends the stage
+                let mut deltatype = 1;
+                let mut delta = * self.dualvar[ ..n ].iter().min().unwrap();
+                let mut deltaedge = 0;
+                let mut deltablossom = 0;
+                for v in 0..n {
+                    if self.label[ self.inblossom[ v ] ] == 0 {
+                        if let Some( e ) = self.bestedge[ v ] {
+                            let d = self.slack( e );
+                            if d < delta {
+                                delta = d;
+                                deltatype = 2;
+                                deltaedge = e;
+                            }
+                        }
+                    }
+                }
+                for b in 0..2 * n {
+                    if self.blossomparent[ b ].is_none() && self.label[ b ] == 1 {
+                        if let Some( e ) = self.bestedge[ b ] {
+                            let d = self.slack( e ) / 2;
+                            if d < delta {
+                                delta = d;
+                                deltatype = 3;
+                                deltaedge = e;
+                            }
+                        }
+                    }
+                }
+                for b in n..2 * n {
+                    if self.blossombase[ b ].is_some() && self.blossomparent[ b ].is_none() &&
+                            self.label[ b ] == 2 && self.dualvar[ b ] < delta {
+                        delta = self.dualvar[ b ];
+                        deltatype = 4;
+                        deltablossom = b;
+                    }
+                }
+                for v in 0..n {
+                    match self.label[ self.inblossom[ v ] ] {
+                        1 => self.dualvar[ v ] -= delta,
+                        2 => self.dualvar[ v ] += delta,
+                        _ => {},
+                    }
+                }
+                for b in n..2 * n {
+                    if self.blossombase[ b ].is_some() && self.blossomparent[ b ].is_none() {
+                        match self.label[ b ] {
+                            1 => self.dualvar[ b ] += delta,
+                            2 => self.dualvar[ b ] -= delta,
+                            _ => {},
+                        }
+                    }
+                }
+                match deltatype {
+                    1 => break,
+                    2 => {
+                        self.allowedge[ deltaedge ] = true;
+                        let ( mut i, j, _ ) = self.edges[ deltaedge ];
+                        if self.label[ self.inblossom[ i ] ] == 0 {
+                            i = j;
+                        }
+                        self.queue.push( i );
+                    },
+                    3 => {
+                        self.allowedge[ deltaedge ] = true;
+                        self.queue.push( self.edges[ deltaedge ].0 );
+                    },
+                    _ => self.expand_blossom( deltablossom, false ),
+                }
+            }
+            if ! augmented {
+                break;
+            }
+            for b in n..2 * n {
+                if self.blossomparent[ b ].is_none() && self.blossombase[ b ].is_some() &&
+                        self.label[ b ] == 1 && self.dualvar[ b ] == 0 {
+                    self.expand_blossom( b, true );
+                }
+            }
+        }
+        ( 0..n ).map( | v | self.mate[ v ].map( | p | self.endpoint[ p ] ) ).collect()
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_beats_greedy() {
+        // greedy takes 0-1 (3) and leaves 2 and 3 unmatched, the optimum is 0-2 and 1-3 (4)
+        let edges = vec![ ( 0, 1, 3 ), ( 0, 2, 2 ), ( 1, 3, 2 ) ];
+        let matching = get_max_weight_matching( 4, & edges );
+        assert_eq!( matching.pairs, vec![ ( 0, 2 ), ( 1, 3 ) ] );
+        assert!( matching.ties.is_empty() );
+    }
+
+    #[test]
+    fn ties() {
+        let edges = vec![ ( 0, 2, 1 ), ( 0, 3, 1 ), ( 1, 2, 1 ), ( 1, 3, 1 ) ];
+        let matching = get_max_weight_matching( 4, & edges );
+        assert_eq!( matching.pairs, vec![ ( 0, 2 ), ( 1, 3 ) ] );
+        assert_eq!( matching.ties, vec![ ( 0, vec![ Some( 2 ), Some( 3 ) ] ) ] );
+    }
+
+    /**
+     * Exhaustive search for the best total weight.
+     */
+    fn get_brute_force_weight( weights : & Vec< Vec< u32 > >, free : & mut Vec< bool > ) -> i64 {
+        let node = match free.iter().position( | f | * f ) {
+            Some( node ) => node,
+            None => return 0,
+        };
+        free[ node ] = false;
+        let mut best = get_brute_force_weight( weights, free );
+        for partner in 0..weights.len() {
+            if free[ partner ] && weights[ node ][ partner ] > 0 {
+                free[ partner ] = false;
+                best = best.max( weights[ node ][ partner ] as i64 + get_brute_force_weight( weights, free ) );
+                free[ partner ] = true;
+            }
+        }
+        free[ node ] = true;
+        best
+    }
+
+    #[test]
+    fn blossom_is_optimal() {
+        // odd cycles force blossoms, compare with an exhaustive search on pseudo-random graphs
+        let mut seed : u64 = 12345;
+        for _ in 0..200 {
+            let mut random = | m : u64 | {
+                seed = seed.wrapping_mul( 6364136223846793005 ).wrapping_add( 1442695040888963407 );
+                ( seed >> 33 ) % m
+            };
+            let n = 2 + random( 9 ) as usize;
+            let mut weights = vec![ vec![ 0; n ]; n ];
+            for ( a, b ) in ( 0..n ).flat_map( | a | ( a + 1..n ).map( move | b | ( a, b ) ) ) {
+                if random( 3 ) == 0 {
+                    let w = 1 + random( 8 ) as u32;
+                    weights[ a ][ b ] = w;
+                    weights[ b ][ a ] = w;
+                }
+            }
+            let free = vec![ true; n ];
+            assert_eq!( get_best_weight( & weights, & free ), get_brute_force_weight( & weights, & mut free.clone() ) );
+        }
+    }
+
+    #[test]
+    fn large_component() {
+        // a path of 40 nodes with heavier inner edges, beyond any bitmask search
+        let edges : Vec< ( usize, usize, u32 ) > = ( 0..39 ).map( | i | ( i, i + 1, 1 + ( i % 2 ) as u32 ) ).collect();
+        let matching = get_max_weight_matching( 40, & edges );
+        let expected : Vec< ( usize, usize ) > = ( 0..19 ).map( | i | ( 2 * i + 1, 2 * i + 2 ) ).collect();
+        assert_eq!( matching.pairs, expected );
+    }
+}
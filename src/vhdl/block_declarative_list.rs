@@ -44,6 +44,10 @@ impl BlockDeclarativeList {
     pub fn get_constants( & self ) -> & Vec< ConstantDeclaration > {
         & self.constants
     }
+
+    pub fn get_signals( & self ) -> & Vec< SignalDeclaraion > {
+        & self.signals
+    }
 }
 
 impl Element for BlockDeclarativeList {
@@ -1,5 +1,18 @@
+
 use crate::element::Element;
 
 pub trait ConcurrentStatement : Element {
-}
+    /**
+     * Names of the signals and ports assigned by the statement.
+     */
+    fn get_driven_signals( & self ) -> Vec< String > {
+        Vec::new()
+    }
 
+    /**
+     * Names of the signals and ports read by the statement.
+     */
+    fn get_read_signals( & self ) -> Vec< String > {
+        Vec::new()
+    }
+}
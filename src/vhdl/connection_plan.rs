@@ -0,0 +1,150 @@
+use crate::element::Element;
+use crate::vhdl::connection_report::{ ConnectionReport, get_member_path };
+use crate::vhdl::signal_declaration::SignalDeclaraion;
+
+/**
+ * A single binding proposed by a connection plan.  Interfaces are given by their index within
+ * the instance or entity, the name is kept for display.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlannedBinding {
+    /// Bind an instance interface to an interface of the entity
+    EntityInterface { instance : String, interface : usize, name : String,
+            entity_interface : usize, entity_name : String },
+    /// Bind the ports of an instance interface in order to signals, empty names are tied off
+    SignalList { instance : String, interface : usize, name : String, signals : Vec< String > },
+    /// Bind an instance generic to a generic or constant
+    Generic { instance : String, inner : String, outer : String },
+}
+
+impl PlannedBinding {
+    pub fn get_instance( & self ) -> & String {
+        match self {
+            PlannedBinding::EntityInterface { instance, .. } => instance,
+            PlannedBinding::SignalList { instance, .. } => instance,
+            PlannedBinding::Generic { instance, .. } => instance,
+        }
+    }
+}
+
+impl std::fmt::Display for PlannedBinding {
+    fn fmt( & self, f : & mut std::fmt::Formatter ) -> std::fmt::Result {
+        match self {
+            PlannedBinding::EntityInterface { instance, name, entity_name, .. } =>
+                write!( f, "bind {} to entity interface {}", get_member_path( instance, name ),
+                        get_member_path( "", entity_name ) ),
+            PlannedBinding::SignalList { instance, name, signals, .. } =>
+                write!( f, "bind {} to signals {}", get_member_path( instance, name ),
+                        signals.iter().map( | s | if s.is_empty() { "<tie-off>" } else { s } )
+                                .collect::< Vec< & str > >().join( ", " ) ),
+            PlannedBinding::Generic { instance, inner, outer } =>
+                write!( f, "bind {} to {}", get_member_path( instance, inner ), outer ),
+        }
+    }
+}
+
+/**
+ * Bindings and signal declarations a connection would make, created by the `plan_*` methods of
+ * `Architecture` and carried out by `Architecture::apply`.  The plan may be edited in between.
+ */
+#[derive(Clone, Default)]
+pub struct ConnectionPlan {
+    bindings : Vec< PlannedBinding >,
+    signals : Vec< SignalDeclaraion >,
+    shared_signals : Vec< SignalDeclaraion >,
+    report : ConnectionReport,
+}
+
+impl ConnectionPlan {
+    pub fn new() -> ConnectionPlan {
+        ConnectionPlan { bindings : Vec::new(), signals : Vec::new(), shared_signals : Vec::new(),
+                report : ConnectionReport::new() }
+    }
+
+    pub fn add_binding( & mut self, binding : PlannedBinding ) {
+        self.bindings.push( binding );
+    }
+
+    pub fn remove_binding( & mut self, index : usize ) -> PlannedBinding {
+        self.bindings.remove( index )
+    }
+
+    pub fn add_signal( & mut self, signal : & SignalDeclaraion ) {
+        self.signals.push( signal.clone() );
+    }
+
+    pub fn remove_signal( & mut self, index : usize ) -> SignalDeclaraion {
+        self.signals.remove( index )
+    }
+
+    /**
+     * Adds a net shared by fixed ports of several interfaces, e.g. `clk`.  It is declared once
+     * unless the entity has a port of the same name.  Nets of the same name and different data
+     * types are kept and refused by `Architecture::apply`.
+     */
+    pub fn add_shared_signal( & mut self, signal : & SignalDeclaraion ) {
+        if ! self.shared_signals.iter().any( | s | s.get_name() == signal.get_name() &&
+                s.get_data_type() == signal.get_data_type() ) {
+            self.shared_signals.push( signal.clone() );
+        }
+    }
+
+    pub fn get_shared_signals( & self ) -> & Vec< SignalDeclaraion > {
+        & self.shared_signals
+    }
+
+    pub fn set_report( & mut self, report : ConnectionReport ) {
+        self.report = report;
+    }
+
+    pub fn append( & mut self, other : ConnectionPlan ) {
+        self.bindings.extend( other.bindings );
+        self.signals.extend( other.signals );
+        for signal in & other.shared_signals {
+            self.add_shared_signal( signal );
+        }
+        self.report.append( other.report );
+    }
+
+    pub fn get_bindings( & self ) -> & Vec< PlannedBinding > {
+        & self.bindings
+    }
+
+    pub fn get_bindings_mut( & mut self ) -> & mut Vec< PlannedBinding > {
+        & mut self.bindings
+    }
+
+    pub fn get_signals( & self ) -> & Vec< SignalDeclaraion > {
+        & self.signals
+    }
+
+    pub fn get_signals_mut( & mut self ) -> & mut Vec< SignalDeclaraion > {
+        & mut self.signals
+    }
+
+    /**
+     * The report of the matching the plan was made from.
+     */
+    pub fn get_report( & self ) -> & ConnectionReport {
+        & self.report
+    }
+
+    pub fn is_empty( & self ) -> bool {
+        self.bindings.is_empty() && self.signals.is_empty() && self.shared_signals.is_empty()
+    }
+}
+
+impl std::fmt::Display for ConnectionPlan {
+    fn fmt( & self, f : & mut std::fmt::Formatter ) -> std::fmt::Result {
+        for binding in & self.bindings {
+            writeln!( f, "{}", binding )?;
+        }
+        for signal in & self.signals {
+            write!( f, "declare {}", signal.to_source_code( 0 ) )?;
+        }
+        for signal in & self.shared_signals {
+            write!( f, "share {}", signal.to_source_code( 0 ) )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,281 @@
+use crate::vhdl::match_index::*;
+use crate::vhdl::vhdl_error::VhdlError;
+
+/**
+ * Why two interfaces or generics were paired, derived from the match strength.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchReason {
+    /// Only the class, or for generics the data type, matches
+    Class,
+    /// One name contains the other
+    Substring,
+    /// The names are equal
+    Name,
+}
+
+impl MatchReason {
+    pub fn from_strength( strength : u32 ) -> Option< MatchReason > {
+        if strength >= FULL {
+            Some( MatchReason::Name )
+        }
+        else if strength == PARTIAL {
+            Some( MatchReason::Substring )
+        }
+        else if strength == CLASS {
+            Some( MatchReason::Class )
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for MatchReason {
+    fn fmt( & self, f : & mut std::fmt::Formatter ) -> std::fmt::Result {
+        match self {
+            MatchReason::Class => write!( f, "class" ),
+            MatchReason::Substring => write!( f, "substring" ),
+            MatchReason::Name => write!( f, "name" ),
+        }
+    }
+}
+
+/**
+ * How to resolve several candidates of the same best score.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// A tie is an error and nothing is connected
+    Error,
+    /// The first candidate in declaration order wins
+    First,
+}
+
+/**
+ * Returns the path of an interface or generic, `instance.member` or `member` for the entity.
+ */
+pub fn get_member_path( instance : & str, member : & str ) -> String {
+    let member = if member.is_empty() { "<default>" } else { member };
+    if instance.is_empty() {
+        member.to_string()
+    }
+    else {
+        format!( "{}.{}", instance, member )
+    }
+}
+
+/**
+ * A pair made by an automatic connection.  An empty instance name stands for the entity of the
+ * architecture.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionPair {
+    source_instance : String,
+    source : String,
+    target_instance : String,
+    target : String,
+    strength : u32,
+    indices : ( usize, usize ),
+}
+
+impl ConnectionPair {
+    pub fn new( source_instance : & str, source : & str, target_instance : & str, target : & str,
+            strength : u32 ) -> ConnectionPair {
+        ConnectionPair { source_instance : source_instance.to_string(), source : source.to_string(),
+                target_instance : target_instance.to_string(), target : target.to_string(), strength,
+                indices : ( 0, 0 ) }
+    }
+
+    /**
+     * Sets the interface indices of source and target, interface names need not be unique.
+     */
+    pub fn set_indices( & mut self, source : usize, target : usize ) {
+        self.indices = ( source, target );
+    }
+
+    pub fn get_source_index( & self ) -> usize {
+        self.indices.0
+    }
+
+    pub fn get_target_index( & self ) -> usize {
+        self.indices.1
+    }
+
+    pub fn get_source_instance( & self ) -> & String {
+        & self.source_instance
+    }
+
+    pub fn get_source( & self ) -> & String {
+        & self.source
+    }
+
+    pub fn get_target_instance( & self ) -> & String {
+        & self.target_instance
+    }
+
+    pub fn get_target( & self ) -> & String {
+        & self.target
+    }
+
+    pub fn get_strength( & self ) -> u32 {
+        self.strength
+    }
+
+    pub fn get_reason( & self ) -> Option< MatchReason > {
+        MatchReason::from_strength( self.strength )
+    }
+
+    pub fn get_source_path( & self ) -> String {
+        get_member_path( & self.source_instance, & self.source )
+    }
+
+    pub fn get_target_path( & self ) -> String {
+        get_member_path( & self.target_instance, & self.target )
+    }
+}
+
+impl std::fmt::Display for ConnectionPair {
+    fn fmt( & self, f : & mut std::fmt::Formatter ) -> std::fmt::Result {
+        let reason = match self.get_reason() {
+            Some( reason ) => reason.to_string(),
+            None => "none".to_string(),
+        };
+        write!( f, "{} -> {} (score {}, {})", self.get_source_path(), self.get_target_path(),
+                self.strength, reason )
+    }
+}
+
+/**
+ * Several candidates with the same best score for one interface or generic.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionTie {
+    source : String,
+    candidates : Vec< String >,
+    strength : u32,
+}
+
+impl ConnectionTie {
+    pub fn new( source : & str, candidates : Vec< String >, strength : u32 ) -> ConnectionTie {
+        ConnectionTie { source : source.to_string(), candidates, strength }
+    }
+
+    pub fn get_source( & self ) -> & String {
+        & self.source
+    }
+
+    pub fn get_candidates( & self ) -> & Vec< String > {
+        & self.candidates
+    }
+
+    pub fn get_strength( & self ) -> u32 {
+        self.strength
+    }
+}
+
+impl std::fmt::Display for ConnectionTie {
+    fn fmt( & self, f : & mut std::fmt::Formatter ) -> std::fmt::Result {
+        write!( f, "{} ties between {} (score {})", self.source, self.candidates.join( ", " ),
+                self.strength )
+    }
+}
+
+/**
+ * Result of an automatic connection: the pairs made with score and reason, interfaces or
+ * generics left without partner and ties between equally good candidates.
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConnectionReport {
+    pairs : Vec< ConnectionPair >,
+    unmatched : Vec< String >,
+    ties : Vec< ConnectionTie >,
+}
+
+impl ConnectionReport {
+    pub fn new() -> ConnectionReport {
+        ConnectionReport { pairs : Vec::new(), unmatched : Vec::new(), ties : Vec::new() }
+    }
+
+    pub fn add_pair( & mut self, pair : ConnectionPair ) {
+        self.pairs.push( pair );
+    }
+
+    pub fn add_unmatched( & mut self, path : & str ) {
+        self.unmatched.push( path.to_string() );
+    }
+
+    pub fn add_tie( & mut self, tie : ConnectionTie ) {
+        self.ties.push( tie );
+    }
+
+    pub fn append( & mut self, other : ConnectionReport ) {
+        self.pairs.extend( other.pairs );
+        self.unmatched.extend( other.unmatched );
+        self.ties.extend( other.ties );
+    }
+
+    pub fn get_pairs( & self ) -> & Vec< ConnectionPair > {
+        & self.pairs
+    }
+
+    pub fn get_unmatched( & self ) -> & Vec< String > {
+        & self.unmatched
+    }
+
+    pub fn get_ties( & self ) -> & Vec< ConnectionTie > {
+        & self.ties
+    }
+
+    pub fn has_ties( & self ) -> bool {
+        ! self.ties.is_empty()
+    }
+
+    /**
+     * Fails if the report contains ties and the policy does not allow to break them.
+     */
+    pub fn requires_no_ties( & self, tie_break : TieBreak ) -> Result< (), VhdlError > {
+        if tie_break == TieBreak::Error && self.has_ties() {
+            let ties : Vec< String > = self.ties.iter().map( | t | t.to_string() ).collect();
+            return Err( VhdlError::new( & format!( "error: Ambiguous connection: {}!",
+                    ties.join( "; " ) ) ) );
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for ConnectionReport {
+    fn fmt( & self, f : & mut std::fmt::Formatter ) -> std::fmt::Result {
+        for pair in & self.pairs {
+            writeln!( f, "pair: {}", pair )?;
+        }
+        for unmatched in & self.unmatched {
+            writeln!( f, "unmatched: {}", unmatched )?;
+        }
+        for tie in & self.ties {
+            writeln!( f, "tie: {}", tie )?;
+        }
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_to_string() {
+        let mut report = ConnectionReport::new();
+        report.add_pair( ConnectionPair::new( "u0", "host", "u1", "agent", CLASS ) );
+        report.add_unmatched( "u0.irq" );
+        report.add_tie( ConnectionTie::new( "u2.host",
+                vec![ "u3.agent_a".to_string(), "u3.agent_b".to_string() ], CLASS ) );
+        assert_eq!( report.to_string(), concat!(
+                "pair: u0.host -> u1.agent (score 1, class)\n",
+                "unmatched: u0.irq\n",
+                "tie: u2.host ties between u3.agent_a, u3.agent_b (score 1)\n" ) );
+        assert!( report.requires_no_ties( TieBreak::Error ).is_err() );
+        assert!( report.requires_no_ties( TieBreak::First ).is_ok() );
+    }
+}
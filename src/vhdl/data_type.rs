@@ -0,0 +1,51 @@
+use crate::vhdl::expression::normalize_data_type;
+
+const ZERO_VALUES : [ ( &str, &str ); 12 ] = [
+    ( "std_logic_vector", "(others => '0')" ),
+    ( "std_ulogic_vector", "(others => '0')" ),
+    ( "bit_vector", "(others => '0')" ),
+    ( "unsigned", "(others => '0')" ),
+    ( "signed", "(others => '0')" ),
+    ( "std_logic", "'0'" ),
+    ( "std_ulogic", "'0'" ),
+    ( "bit", "'0'" ),
+    ( "boolean", "false" ),
+    ( "integer", "0" ),
+    ( "natural", "0" ),
+    ( "real", "0.0" ),
+];
+
+/**
+ * Returns the zero value of a known data type, e.g. `'0'` for `std_logic` or
+ * `(others => '0')` for vectors.  Types without a zero value like `positive` give `None`.
+ */
+pub fn get_zero_value( data_type : & str ) -> Option< String > {
+    let data_type = normalize_data_type( data_type );
+    let base = match data_type.find( '(' ) {
+        Some( idx ) => & data_type[ ..idx ],
+        None => & data_type,
+    };
+    ZERO_VALUES.iter().find( | ( name, _ ) | * name == base ).map( | ( _, value ) | value.to_string() )
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_values() {
+        assert_eq!( get_zero_value( "std_logic" ), Some( "'0'".to_string() ) );
+        assert_eq!( get_zero_value( "std_logic_vector( WIDTH - 1 downto 0 )" ),
+                Some( "(others => '0')".to_string() ) );
+        assert_eq!( get_zero_value( "unsigned(7 downto 0)" ), Some( "(others => '0')".to_string() ) );
+        assert_eq!( get_zero_value( "Boolean" ), Some( "false".to_string() ) );
+        assert_eq!( get_zero_value( "natural" ), Some( "0".to_string() ) );
+    }
+
+    #[test]
+    fn unknown_zero_values() {
+        assert_eq!( get_zero_value( "positive" ), None );
+        assert_eq!( get_zero_value( "my_record_t" ), None );
+    }
+}
@@ -8,8 +8,12 @@ use crate::vhdl::library::Library;
 use crate::vhdl::library_use::LibraryUse;
 use crate::vhdl::known_libraries::get_known_library_use;
 use crate::vhdl::generic::Generic;
+use crate::vhdl::generic_policy::GenericPolicy;
 use crate::vhdl::port::Port;
 use crate::vhdl::entity_interface::EntityInterface;
+use crate::vhdl::interface_package::get_package_name;
+use crate::vhdl::interface_inference::{ InferredInterface, infer_interfaces };
+use crate::vhdl::interface_registry::InterfaceRegistry;
 use crate::vhdl::vhdl_error::VhdlError;
 
 #[derive(Clone)]
@@ -19,13 +23,16 @@ pub struct Entity {
     description : SingleLineComment,
     libraries : LibraryList,
     interfaces : Vec< EntityInterface >,
+    generic_policy : GenericPolicy,
+    port_order : Vec< String >,
 }
 
 impl Entity {
     pub fn new( name : & str ) -> Entity {
         let mut entity = Entity { name : String::from( name ), library : "work".to_string(),
                 description : SingleLineComment::new(), libraries : LibraryList::new(),
-                interfaces : Vec::new() };
+                interfaces : Vec::new(), generic_policy : GenericPolicy::default(),
+                port_order : Vec::new() };
         entity.add_interface( & EntityInterface::new( "", "__default__" ) ).unwrap();
         return entity;
     }
@@ -69,6 +76,22 @@ impl Entity {
         self.interfaces[ 0 ].add_port( port );
     }
 
+    pub fn get_generic_policy( & self ) -> GenericPolicy {
+        self.generic_policy
+    }
+
+    /**
+     * Policy for generics of added interfaces already declared by another interface.
+     */
+    pub fn set_generic_policy( & mut self, policy : GenericPolicy ) {
+        self.generic_policy = policy;
+    }
+
+    /**
+     * Adds `interface`.  Generics of the same name as a generic of another interface are shared,
+     * prefixed or refused according to the generic policy, and the port data types of both
+     * interfaces are rewritten to the resulting names.
+     */
     pub fn add_interface( & mut self, interface : & EntityInterface )
             -> Result< (), VhdlError > {
         if self.contains_interface( interface ) {
@@ -78,13 +101,115 @@ impl Entity {
                 interface.get_class(),
                 interface.get_name() ) ) );
         }
+        let mut interface = interface.clone();
+        self.resolve_generics( & mut interface )?;
         for data_type in interface.get_data_types() {
             self.add_missing_library_use( & data_type );
         }
-        self.interfaces.push( interface.clone() );
+        if interface.get_record_standard().is_some() {
+            self.add_library_use( & LibraryUse::new( WORK, & get_package_name( interface.get_class() ) ) );
+        }
+        self.interfaces.push( interface );
         Ok(())
     }
 
+    /**
+     * Applies the generic policy to the generics of `interface` declared by other interfaces.
+     */
+    fn resolve_generics( & mut self, interface : & mut EntityInterface ) -> Result< (), VhdlError > {
+        // all generics are checked on copies before anything is changed
+        let mut interfaces = self.interfaces.clone();
+        let mut candidate = interface.clone();
+        for generic in interface.get_generics() {
+            let base = generic.get_base_name().clone();
+            let owners : Vec< usize > = ( 0 .. interfaces.len() )
+                .filter( | i | interfaces[ * i ].get_generics().iter().any( | g | g.get_base_name() == & base ) )
+                .collect();
+            for i in owners {
+                let owner = & mut interfaces[ i ];
+                let other = owner.get_generics().iter().find( | g | g.get_base_name() == & base ).unwrap().clone();
+                match self.generic_policy {
+                    GenericPolicy::Error => return Err( VhdlError::new( & format!(
+                        "error: Generic {:?} of interface {:?} is already declared by interface {:?} of entity {:?}!",
+                        base, interface.get_name(), owner.get_name(), self.name ) ) ),
+                    GenericPolicy::Share => {
+                        if other.get_data_type() != generic.get_data_type() || other.get_default() != generic.get_default() {
+                            return Err( VhdlError::new( & format!(
+                                "error: Generic {:?} of interface {:?} differs from the one of interface {:?} and can not be shared!",
+                                base, interface.get_name(), owner.get_name() ) ) );
+                        }
+                        owner.get_generic_mut( & base ).unwrap().remove_interface();
+                        owner.replace_in_port_types( & other.get_name(), & base );
+                        candidate.replace_in_port_types( & generic.get_name(), & base );
+                        candidate.remove_generic( & base );
+                    },
+                    GenericPolicy::Prefix => {
+                        // a generic of an unnamed interface, e.g. of the entity itself, keeps its name
+                        if interface.get_name() == owner.get_name() {
+                            return Err( VhdlError::new( & format!(
+                                "error: Generic {:?} of interfaces {:?} and {:?} can not be told apart by prefix!",
+                                base, owner.get_name(), interface.get_name() ) ) );
+                        }
+                        if ! owner.get_name().is_empty() {
+                            let prefix = owner.get_name().clone();
+                            owner.get_generic_mut( & base ).unwrap().set_interface( & prefix );
+                            owner.replace_in_port_types( & other.get_name(), & format!( "{}_{}", prefix, base ) );
+                            owner.replace_in_port_types( & base, & format!( "{}_{}", prefix, base ) );
+                        }
+                        if ! interface.get_name().is_empty() {
+                            let prefix = interface.get_name().clone();
+                            candidate.get_generic_mut( & base ).unwrap().set_interface( & prefix );
+                            candidate.replace_in_port_types( & generic.get_name(), & format!( "{}_{}", prefix, base ) );
+                            candidate.replace_in_port_types( & base, & format!( "{}_{}", prefix, base ) );
+                        }
+                    },
+                }
+            }
+        }
+        self.interfaces = interfaces;
+        * interface = candidate;
+        Ok(())
+    }
+
+    /**
+     * Groups the ports of the default interface into interfaces of the classes of `registry`, see
+     * `infer_interfaces`.  Groupings with at least `min_confidence` are moved into their own
+     * interface, the ports keep their order.  Returns every grouping found.  On error the entity is
+     * left unchanged.
+     */
+    pub fn infer_interfaces( & mut self, registry : & InterfaceRegistry, min_confidence : f64 )
+            -> Result< Vec< InferredInterface >, VhdlError > {
+        let ports = self.interfaces[ 0 ].get_ports().clone();
+        let inferred = infer_interfaces( & ports, registry );
+        let mut entity = self.clone();
+        if entity.port_order.is_empty() {
+            entity.port_order = self.get_ports().iter().map( | p | p.get_name() ).collect();
+        }
+        let mut moved : Vec< String > = Vec::new();
+        for grouping in inferred.iter().filter( | i | i.get_confidence() >= min_confidence ) {
+            let interface = grouping.to_entity_interface( & ports, registry )?;
+            entity.add_interface( & interface )?;
+            moved.extend( grouping.get_ports().iter().map( | ( n, _ ) | n.clone() ) );
+        }
+        entity.interfaces[ 0 ] = entity.interfaces[ 0 ].clone_subset( | p | ! moved.contains( & p.get_name() ) );
+        * self = entity;
+        Ok( inferred )
+    }
+
+    /**
+     * Sorts `ports` by the port order recorded when interfaces were inferred, ports not recorded
+     * follow in interface order.
+     */
+    fn sort_ports( & self, ports : & mut [ Port ] ) {
+        if self.port_order.is_empty() {
+            return;
+        }
+        ports.sort_by_key( | p | {
+            let name = p.get_name();
+            self.port_order.iter().position( | n | n == & name ).unwrap_or( self.port_order.len() )
+        } );
+    }
+
     pub fn get_name( & self ) -> & String {
         & self.name
     }
@@ -117,6 +242,17 @@ impl Entity {
         return vec;
     }
 
+    pub fn get_ports( & self ) -> Vec< Port > {
+        let mut vec = Vec::new();
+        for interface in & self.interfaces {
+            for port in interface.get_ports() {
+                vec.push( port.clone() );
+            }
+        }
+        self.sort_ports( & mut vec );
+        return vec;
+    }
+
     fn generics_to_source_code( & self, indent : usize ) -> String {
         let mut source = String::new();
         let indent_str = crate::util::indent( indent );
@@ -145,7 +281,7 @@ impl Entity {
         let mut source = String::new();
         let indent_str = crate::util::indent( indent );
         let list_indent_str = crate::util::indent( indent + 1 );
-        let port_list = self.get_ports();
+        let port_list = self.get_ports_boxed();
         if ! port_list.is_empty() {
             let list = to_source_code_list( & port_list,
                     & format!( ";\n{}", list_indent_str ) );
@@ -155,12 +291,15 @@ impl Entity {
         return source;
     }
 
-    fn get_ports( & self ) -> Vec< Box< dyn Element > > {
-        let mut port_list : Vec< Box< dyn Element > > = Vec::new();
+    fn get_ports_boxed( & self ) -> Vec< Box< dyn Element > > {
+        let mut ports : Vec< Port > = Vec::new();
         for interface in & self.interfaces {
-            for port in interface.get_ports() {
-                port_list.push( Box::new( port.clone() ) );
-            }
+            ports.extend( interface.get_declared_ports() );
+        }
+        self.sort_ports( & mut ports );
+        let mut port_list : Vec< Box< dyn Element > > = Vec::new();
+        for port in ports {
+            port_list.push( Box::new( port ) );
         }
         return port_list;
     }
@@ -195,6 +334,7 @@ mod tests {
     use std::error::Error;
     use std::path::Path;
     use crate::vhdl::direction::Direction;
+    use crate::vhdl::interface_package::VhdlStandard;
 
     const NAME : &'static str = "test";
     const DESCRIPTION : &'static str = "-- A cool entity description\n";
@@ -297,6 +437,159 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn entity_with_record_interface() -> Result< (), Box< dyn Error > > {
+        let mut interface = EntityInterface::new( "s", "stream" );
+        interface.add_port( Port::new( "data", Direction::OUT, "std_logic_vector( 7 downto 0 )" ) );
+        interface.add_port( Port::new( "ready", Direction::IN, "std_logic" ) );
+        interface.set_views( "source", "sink" );
+        interface.rename( "s" );
+        interface.set_record_standard( Some( VhdlStandard::VHDL2019 ) )?;
+        let mut entity = Entity::new( NAME );
+        entity.add_port( Port::new( "clk", Direction::IN, "std_logic" ) );
+        entity.add_interface( & interface.clone_inverted() )?;
+        assert_eq!( entity.to_source_code( 0 ), format!( "{}{}{}{}{}{}", USE_STD_LOGIC_1164,
+                concat!( "library work;\n", "    use work.stream_pkg.all;\n\n" ), HEADER,
+                concat!( "    port (\n", "        clk : in std_logic;\n",
+                        "        s : view stream_sink_view of stream_t\n", "    );\n" ), BEGIN, END ) );
+        Ok(())
+    }
+
+    #[test]
+    fn infer_interfaces() -> Result< (), VhdlError > {
+        let mut entity = Entity::new( NAME );
+        entity.add_port( Port::new( "clk", Direction::IN, "std_logic" ) );
+        entity.add_port( Port::new( "m0_address", Direction::OUT, "std_logic_vector( 15 downto 0 )" ) );
+        entity.add_port( Port::new( "m0_read", Direction::OUT, "std_logic" ) );
+        entity.add_port( Port::new( "m0_read_data", Direction::IN, "std_logic_vector( 31 downto 0 )" ) );
+        entity.add_port( Port::new( "m0_write", Direction::OUT, "std_logic" ) );
+        entity.add_port( Port::new( "m0_write_data", Direction::OUT, "std_logic_vector( 31 downto 0 )" ) );
+        entity.add_port( Port::new( "s_axis_tdata", Direction::IN, "std_logic_vector( 7 downto 0 )" ) );
+        let inferred = entity.infer_interfaces( & InterfaceRegistry::new(), 0.9 )?;
+        let summary : Vec< String > = inferred.iter().map( | i | i.to_string() ).collect();
+        assert_eq!( summary, vec![ "m0 : avalon_mmi@host (1.00)", "s_axis : axi4_stream@sink (0.25, missing tvalid)",
+            "<unnamed> : clk_rst@sink (0.25, missing rst)" ] );
+        let classes : Vec< & String > = entity.get_interfaces().iter().map( | i | i.get_class() ).collect();
+        assert_eq!( classes, vec![ "__default__", "avalon_mmi" ] );
+        assert_eq!( entity.get_interfaces()[ 0 ].get_ports().len(), 2 );
+        let names : Vec< String > = entity.get_ports().iter().map( | p | p.get_name() ).collect();
+        assert_eq!( names, vec![ "clk", "m0_address", "m0_read", "m0_read_data", "m0_write", "m0_write_data",
+            "s_axis_tdata" ] );
+        Ok(())
+    }
+
+    #[test]
+    fn infer_interfaces_is_atomic() {
+        let mut entity = Entity::new( NAME );
+        entity.add_port( Port::new( "m0_address", Direction::OUT, "std_logic_vector( 15 downto 0 )" ) );
+        entity.add_port( Port::new( "m0_read", Direction::OUT, "std_logic" ) );
+        entity.add_port( Port::new( "m0_read_data", Direction::IN, "std_logic_vector( 31 downto 0 )" ) );
+        entity.add_port( Port::new( "m0_write", Direction::OUT, "std_logic" ) );
+        entity.add_port( Port::new( "m0_write_data", Direction::OUT, "std_logic_vector( 31 downto 0 )" ) );
+        entity.add_port( Port::new( "s0_address", Direction::IN, "std_logic_vector( 15 downto 0 )" ) );
+        entity.add_port( Port::new( "s0_read", Direction::IN, "std_logic" ) );
+        entity.add_port( Port::new( "s0_read_data", Direction::OUT, "std_logic_vector( 31 downto 0 )" ) );
+        entity.add_port( Port::new( "s0_write", Direction::IN, "std_logic" ) );
+        entity.add_port( Port::new( "s0_write_data", Direction::IN, "std_logic_vector( 31 downto 0 )" ) );
+        entity.add_interface( & EntityInterface::new( "s0", "avalon_mmi" ) ).unwrap();
+        let before = entity.to_source_code( 0 );
+        assert!( entity.infer_interfaces( & InterfaceRegistry::new(), 0.9 ).is_err() );
+        assert_eq!( entity.to_source_code( 0 ), before );
+        assert_eq!( entity.get_interfaces().len(), 2 );
+    }
+
+    fn get_avalon_interfaces( first : & str, second : & str ) -> ( EntityInterface, EntityInterface ) {
+        let avalon = crate::vhdl::interface_catalog::get_interface( "avalon_mmi" ).unwrap();
+        let mut host = avalon.clone_subset( | p | p.get_base_name() == "address" || p.get_base_name() == "read_data" );
+        let mut agent = host.clone_inverted();
+        host.rename( first );
+        agent.rename( second );
+        ( host, agent )
+    }
+
+    #[test]
+    fn share_generics() -> Result< (), VhdlError > {
+        let ( host, agent ) = get_avalon_interfaces( "m0", "s0" );
+        let mut entity = Entity::new( NAME );
+        entity.set_generic_policy( GenericPolicy::Share );
+        entity.add_interface( & host )?;
+        entity.add_interface( & agent )?;
+        let names : Vec< String > = entity.get_generics().iter().map( | g | g.get_name() ).collect();
+        assert_eq!( names, vec![ "ADDR_WIDTH", "DATA_WIDTH", "BYTE_COUNT" ] );
+        assert_eq!( entity.get_ports()[ 3 ].to_source_code( 0 ),
+            "s0_read_data : out std_logic_vector( DATA_WIDTH - 1 downto 0 )" );
+        Ok(())
+    }
+
+    #[test]
+    fn prefix_generics() -> Result< (), VhdlError > {
+        let ( mut host, agent ) = get_avalon_interfaces( "m0", "s0" );
+        host.remove_interface_from_generics();
+        let mut entity = Entity::new( NAME );
+        entity.add_interface( & host )?;
+        entity.add_interface( & agent )?;
+        let names : Vec< String > = entity.get_generics().iter().map( | g | g.get_name() ).collect();
+        assert_eq!( names, vec![ "m0_ADDR_WIDTH", "m0_DATA_WIDTH", "m0_BYTE_COUNT",
+            "s0_ADDR_WIDTH", "s0_DATA_WIDTH", "s0_BYTE_COUNT" ] );
+        assert_eq!( entity.get_ports()[ 0 ].to_source_code( 0 ),
+            "m0_address : out std_logic_vector( m0_ADDR_WIDTH - 1 downto 0 )" );
+        assert_eq!( entity.get_ports()[ 3 ].to_source_code( 0 ),
+            "s0_read_data : out std_logic_vector( s0_DATA_WIDTH - 1 downto 0 )" );
+
+        // the generics of an unnamed interface keep their names
+        let ( host, agent ) = get_avalon_interfaces( "", "s0" );
+        let mut entity = Entity::new( NAME );
+        entity.add_interface( & host )?;
+        entity.add_interface( & agent )?;
+        let names : Vec< String > = entity.get_generics().iter().map( | g | g.get_name() ).collect();
+        assert_eq!( names, vec![ "ADDR_WIDTH", "DATA_WIDTH", "BYTE_COUNT",
+            "s0_ADDR_WIDTH", "s0_DATA_WIDTH", "s0_BYTE_COUNT" ] );
+
+        let ( host, agent ) = get_avalon_interfaces( "", "" );
+        let mut entity = Entity::new( NAME );
+        entity.add_interface( & host )?;
+        assert!( entity.add_interface( & agent ).is_err() );
+        Ok(())
+    }
+
+    #[test]
+    fn prefix_entity_generics() -> Result< (), VhdlError > {
+        let ( host, _ ) = get_avalon_interfaces( "m0", "s0" );
+        let mut entity = Entity::new( NAME );
+        entity.add_generic( Generic::new( "DATA_WIDTH", "positive" ) );
+        entity.add_interface( & host )?;
+        let names : Vec< String > = entity.get_generics().iter().map( | g | g.get_name() ).collect();
+        assert_eq!( names, vec![ "DATA_WIDTH", "m0_ADDR_WIDTH", "m0_DATA_WIDTH", "m0_BYTE_COUNT" ] );
+        assert_eq!( entity.get_ports()[ 1 ].to_source_code( 0 ),
+            "m0_read_data : in std_logic_vector( m0_DATA_WIDTH - 1 downto 0 )" );
+        Ok(())
+    }
+
+    #[test]
+    fn share_generics_checks_all_first() -> Result< (), VhdlError > {
+        let ( host, mut agent ) = get_avalon_interfaces( "m0", "s0" );
+        agent.get_generic_mut( "BYTE_COUNT" ).unwrap().set_default( "4" );
+        let mut entity = Entity::new( NAME );
+        entity.set_generic_policy( GenericPolicy::Share );
+        entity.add_interface( & host )?;
+        assert!( entity.add_interface( & agent ).is_err() );
+        // ADDR_WIDTH and DATA_WIDTH of m0 keep their prefix
+        let names : Vec< String > = entity.get_generics().iter().map( | g | g.get_name() ).collect();
+        assert_eq!( names, vec![ "m0_ADDR_WIDTH", "m0_DATA_WIDTH", "m0_BYTE_COUNT" ] );
+        Ok(())
+    }
+
+    #[test]
+    fn refuse_generics() -> Result< (), VhdlError > {
+        let ( host, agent ) = get_avalon_interfaces( "m0", "s0" );
+        let mut entity = Entity::new( NAME );
+        entity.set_generic_policy( GenericPolicy::Error );
+        entity.add_interface( & host )?;
+        assert!( entity.add_interface( & agent ).is_err() );
+        assert_eq!( entity.get_interfaces().len(), 2 );
+        Ok(())
+    }
+
     /**
      * Create a entity with libraries
      */
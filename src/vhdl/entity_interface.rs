@@ -1,11 +1,18 @@
 use std::error::Error;
 use std::fs;
-use std::path::Path;
-use std::collections::HashSet;
+use std::path::{ Path, PathBuf };
+use std::collections::{ HashMap, HashSet };
+use regex::{ NoExpand, Regex };
 use serde_derive::Deserialize;
 use serde_json_schema::Schema;
 use crate::vhdl::generic::Generic;
+use crate::vhdl::interface_catalog;
+use crate::vhdl::interface_package::{ InterfacePackage, VhdlStandard };
+use crate::vhdl::interface_part::InterfacePart;
+use crate::vhdl::interface_registry::InterfaceRegistry;
 use crate::vhdl::port::Port;
+use crate::vhdl::role_view::{ RoleView, split_role_reference };
+use crate::vhdl::vhdl_error::VhdlError;
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct EntityInterface {
@@ -13,17 +20,80 @@ pub struct EntityInterface {
     name : String,
     class : String,
     #[serde(default)]
-    inverted : bool,
+    version : String,
+    #[serde(default)]
+    views : Vec< RoleView >,
+    #[serde(default)]
+    role : String,
     #[serde(default)]
     generics : Vec< Generic >,
     #[serde(default)]
     ports : Vec< Port >,
+    #[serde(skip)]
+    base : String,
+    #[serde(skip)]
+    parts : Vec< InterfacePart >,
+    #[serde(skip)]
+    record_standard : Option< VhdlStandard >,
+    #[serde(skip)]
+    lineage : Vec< String >,
+}
+
+/**
+ * Class name or `$ref` file of an interface description referenced by `extends` or `include`.
+ */
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum Reference {
+    Class( String ),
+    File {
+        #[serde(rename = "$ref")]
+        file : String,
+    },
+}
+
+/**
+ * A sub-interface given by `class` or `$ref` whose ports are added with `prefix`.
+ */
+#[derive(Deserialize, Debug, Clone)]
+struct Include {
+    #[serde(default)]
+    prefix : String,
+    #[serde(default)]
+    class : String,
+    #[serde(default, rename = "$ref")]
+    file : String,
+    #[serde(default)]
+    inverted : bool,
+    #[serde(default)]
+    role : String,
+}
+
+impl Include {
+    fn get_reference( & self ) -> Reference {
+        if self.file.is_empty() {
+            Reference::Class( self.class.clone() )
+        }
+        else {
+            Reference::File { file : self.file.clone() }
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct Composition {
+    #[serde(default)]
+    extends : Option< Reference >,
+    #[serde(default)]
+    include : Vec< Include >,
 }
 
 impl EntityInterface {
     pub fn new( name : & str, class : & str ) -> EntityInterface {
         EntityInterface { name : name.to_string(), class : class.to_string(),
-            inverted : false, generics : Vec::new(), ports : Vec::new() }
+            version : String::new(), views : Vec::new(), role : String::new(), generics : Vec::new(),
+            ports : Vec::new(), base : String::new(), parts : Vec::new(), record_standard : None,
+            lineage : Vec::new() }
     }
 
     pub fn new_unnamed( class : & str ) -> EntityInterface {
@@ -54,6 +124,13 @@ impl EntityInterface {
         Ok( interface )
     }
 
+    /**
+     * Reads an interface description from a JSON string, e.g. one embedded in the binary.
+     */
+    pub fn from_json_str( description : & str ) -> Result< EntityInterface, Box< dyn Error > > {
+        Resolver::new( None ).resolve( description, None )
+    }
+
     pub fn clone_inverted( & self ) -> EntityInterface {
         let mut inverted = self.clone();
         inverted.invert();
@@ -75,6 +152,330 @@ impl EntityInterface {
         & self.class
     }
 
+    pub fn get_version( & self ) -> & String {
+        & self.version
+    }
+
+    /**
+     * Role views of the interface class with the mode of every port.
+     */
+    pub fn get_views( & self ) -> & Vec< RoleView > {
+        & self.views
+    }
+
+    pub fn get_view_names( & self ) -> Vec< String > {
+        self.views.iter().map( | v | v.get_name().clone() ).collect()
+    }
+
+    /**
+     * The role view the ports are in, empty for an interface without views.
+     */
+    pub fn get_role( & self ) -> & String {
+        & self.role
+    }
+
+    /**
+     * Declares the roles `role`, given by the current port directions, and `inverted_role`.
+     */
+    pub fn set_views( & mut self, role : & str, inverted_role : & str ) {
+        self.views.clear();
+        self.role = role.to_string();
+        self.add_view( RoleView::new( role ) );
+        self.add_view( RoleView::new( inverted_role ) );
+    }
+
+    /**
+     * Adds a role view.  Ports without a mode in the view get their direction in the current
+     * role, or their inverted direction if the view is another role.  The first view added
+     * becomes the current role and sets the port directions.
+     */
+    pub fn add_view( & mut self, view : RoleView ) {
+        self.views.push( view );
+        self.complete_views();
+    }
+
+    /**
+     * Switches the ports to the modes of the role view `role`.
+     */
+    pub fn set_role( & mut self, role : & str ) -> Result< (), VhdlError > {
+        let view = match self.views.iter().find( | v | v.get_name() == role ) {
+            Some( view ) => view,
+            None => return Err( VhdlError::new( & format!( "error: Interface class {:?} has no view {:?}!",
+                self.class, role ) ) ),
+        };
+        for port in & mut self.ports {
+            if let Some( mode ) = view.get_mode( port.get_base_name() ) {
+                port.set_direction( mode );
+            }
+        }
+        self.role = role.to_string();
+        Ok(())
+    }
+
+    /**
+     * Returns a copy of the interface in the role view `role`.
+     */
+    pub fn clone_view( & self, role : & str ) -> Result< EntityInterface, VhdlError > {
+        let mut interface = self.clone();
+        interface.set_role( role )?;
+        Ok( interface )
+    }
+
+    /**
+     * Fills in the modes missing in the views and applies the modes of the current role, which
+     * defaults to the first view.
+     */
+    fn complete_views( & mut self ) {
+        if self.views.is_empty() {
+            return;
+        }
+        if ! self.views.iter().any( | v | v.get_name() == & self.role ) {
+            self.role = self.views[ 0 ].get_name().clone();
+        }
+        let role = self.views.iter().position( | v | v.get_name() == & self.role ).unwrap();
+        for port in & mut self.ports {
+            match self.views[ role ].get_mode( port.get_base_name() ) {
+                Some( mode ) => port.set_direction( mode ),
+                None => self.views[ role ].set_mode( port.get_base_name(), port.get_direction() ),
+            }
+        }
+        for port in & self.ports {
+            for view in & mut self.views {
+                if view.get_mode( port.get_base_name() ).is_none() {
+                    let mut inverted = port.clone();
+                    inverted.invert();
+                    view.set_mode( port.get_base_name(), inverted.get_direction() );
+                }
+            }
+        }
+    }
+
+    /**
+     * Returns a copy with the ports selected by `predicate`, e.g. the read ports of a bus.
+     * Class and views are kept, so the subset still matches interfaces of its class.
+     */
+    pub fn clone_subset< P : Fn( & Port ) -> bool >( & self, predicate : P ) -> EntityInterface {
+        let mut subset = self.clone();
+        let names : Vec< String > = self.ports.iter().filter( | p | predicate( p ) )
+                .map( | p | p.get_base_name().clone() ).collect();
+        subset.retain_ports( & names );
+        subset
+    }
+
+    /**
+     * Splits the interface into one interface per `( name, pattern )`, each with the ports whose
+     * name matches the regular expression `pattern` and no earlier one.  Every port has to match
+     * a pattern.
+     */
+    pub fn clone_split( & self, parts : & [ ( & str, & str ) ] ) -> Result< Vec< EntityInterface >, VhdlError > {
+        let mut patterns = Vec::new();
+        for ( name, pattern ) in parts {
+            if parts.iter().filter( | ( n, _ ) | n == name ).count() > 1 {
+                return Err( VhdlError::new( & format!( "error: Interface {:?} is split into several parts named {:?}!",
+                    self.name, name ) ) );
+            }
+            match Regex::new( pattern ) {
+                Ok( regex ) => patterns.push( regex ),
+                Err( err ) => return Err( VhdlError::new( & format!( "error: Invalid port name pattern {:?}: {}!",
+                    pattern, err ) ) ),
+            }
+        }
+        let mut names : Vec< Vec< String > > = vec![ Vec::new(); parts.len() ];
+        for port in & self.ports {
+            match patterns.iter().position( | p | p.is_match( port.get_base_name() ) ) {
+                Some( idx ) => names[ idx ].push( port.get_base_name().clone() ),
+                None => return Err( VhdlError::new( & format!( "error: Port {:?} of interface {:?} matches no pattern!",
+                    port.get_base_name(), self.name ) ) ),
+            }
+        }
+        let mut split = Vec::new();
+        for ( ( name, _ ), names ) in parts.iter().zip( names ) {
+            let mut part = self.clone();
+            part.retain_ports( & names );
+            part.rename( name );
+            split.push( part );
+        }
+        Ok( split )
+    }
+
+    /**
+     * Returns the ports of this and `other` as one interface of class `class`, e.g. a port group
+     * of two interfaces always used together.  Views of the same name are merged.  Both classes
+     * are kept as lineage, ports and generics must not collide.
+     */
+    pub fn clone_merged( & self, other : & EntityInterface, class : & str ) -> Result< EntityInterface, VhdlError > {
+        let mut merged = self.clone();
+        merged.class = class.to_string();
+        for port in & other.ports {
+            if self.ports.iter().any( | p | p.get_base_name().to_lowercase() == port.get_base_name().to_lowercase() ) {
+                return Err( VhdlError::new( & format!( "error: Port {:?} is part of interface classes {:?} and {:?}!",
+                    port.get_base_name(), self.class, other.class ) ) );
+            }
+            let mut port = port.clone();
+            port.set_interface( & self.name );
+            merged.ports.push( port );
+        }
+        for generic in & other.generics {
+            match self.generics.iter().find( | g | g.get_base_name() == generic.get_base_name() ) {
+                Some( g ) if g.get_data_type() != generic.get_data_type() => return Err( VhdlError::new( & format!(
+                    "error: Generic {:?} of interface classes {:?} and {:?} has different types!",
+                    generic.get_base_name(), self.class, other.class ) ) ),
+                Some( _ ) => {},
+                None => {
+                    let mut generic = generic.clone();
+                    generic.set_interface( & self.name );
+                    merged.generics.push( generic );
+                },
+            }
+        }
+        for view in & mut merged.views {
+            if let Some( other_view ) = other.views.iter().find( | v | v.get_name() == view.get_name() ) {
+                for ( port, mode ) in other_view.get_modes() {
+                    view.set_mode( port, * mode );
+                }
+            }
+        }
+        merged.parts.extend( other.parts.iter().cloned() );
+        merged.lineage = Vec::new();
+        for class in self.get_ancestry().into_iter().chain( other.get_ancestry() ) {
+            if ! merged.lineage.contains( & class ) {
+                merged.lineage.push( class );
+            }
+        }
+        merged.complete_views();
+        Ok( merged )
+    }
+
+    /**
+     * Returns a copy with the ports renamed by `rename`, e.g. to add a prefix.  Two ports must
+     * not get the same name.
+     */
+    pub fn clone_with_renamed_ports< F : Fn( & str ) -> String >( & self, rename : F )
+            -> Result< EntityInterface, VhdlError > {
+        let names : HashMap< String, String > = self.ports.iter()
+                .map( | p | ( p.get_base_name().clone(), rename( p.get_base_name() ) ) ).collect();
+        let mut lower : HashSet< String > = HashSet::new();
+        for port in & self.ports {
+            let name = & names[ port.get_base_name() ];
+            if ! lower.insert( name.to_lowercase() ) {
+                return Err( VhdlError::new( & format!( "error: Renaming the ports of interface {:?} gives port {:?} twice!",
+                    self.name, name ) ) );
+            }
+        }
+        let mut renamed = self.clone();
+        for port in & mut renamed.ports {
+            port.set_name( & names[ port.get_base_name() ] );
+        }
+        for view in & mut renamed.views {
+            view.rename_modes( & names );
+        }
+        for part in & mut renamed.parts {
+            part.rename_ports( & names );
+        }
+        Ok( renamed )
+    }
+
+    /**
+     * Classes the interface was merged from, see `clone_merged`.
+     */
+    pub fn get_lineage( & self ) -> & Vec< String > {
+        & self.lineage
+    }
+
+    /**
+     * The interface is of class `class`, extends it or was merged from it.
+     */
+    pub fn is_derived_from( & self, class : & str ) -> bool {
+        self.get_ancestry().iter().any( | c | c == class )
+    }
+
+    /**
+     * Class, base class and lineage of the interface.
+     */
+    pub fn get_ancestry( & self ) -> Vec< String > {
+        let mut ancestry = vec![ self.class.clone() ];
+        if ! self.base.is_empty() {
+            ancestry.push( self.base.clone() );
+        }
+        ancestry.extend( self.lineage.iter().cloned() );
+        ancestry
+    }
+
+    /**
+     * Keeps the ports with the given base names in views and parts too.
+     */
+    fn retain_ports( & mut self, names : & [ String ] ) {
+        self.ports.retain( | p | names.contains( p.get_base_name() ) );
+        for view in & mut self.views {
+            view.retain_modes( | p | names.iter().any( | n | n == p ) );
+        }
+        for part in & mut self.parts {
+            part.retain_ports( | p | names.iter().any( | n | n == p ) );
+        }
+        self.parts.retain( | p | ! p.get_ports().is_empty() );
+    }
+
+    /**
+     * Class of the interface this one `extends`, empty if none.
+     */
+    pub fn get_base_class( & self ) -> & String {
+        & self.base
+    }
+
+    /**
+     * Sub-interfaces included into this one, their ports are part of `get_ports`.
+     */
+    pub fn get_parts( & self ) -> & Vec< InterfacePart > {
+        & self.parts
+    }
+
+    /**
+     * Lets entities declare the interface as record ports of `standard` instead of single
+     * ports, see `InterfacePackage`.  `None` switches back to single ports.
+     */
+    pub fn set_record_standard( & mut self, standard : Option< VhdlStandard > ) -> Result< (), VhdlError > {
+        if let Some( standard ) = standard {
+            InterfacePackage::from_entity_interface( self, standard )?;
+        }
+        self.record_standard = standard;
+        Ok(())
+    }
+
+    pub fn get_record_standard( & self ) -> Option< VhdlStandard > {
+        self.record_standard
+    }
+
+    /**
+     * Returns the package with the record types and mode views of the interface class.
+     */
+    pub fn get_package( & self, standard : VhdlStandard ) -> Result< InterfacePackage, VhdlError > {
+        InterfacePackage::from_entity_interface( self, standard )
+    }
+
+    /**
+     * Record ports replacing the non-fixed ports, empty unless a record standard is set.
+     */
+    pub fn get_record_ports( & self ) -> Vec< Port > {
+        match self.record_standard.map( | s | self.get_package( s ) ) {
+            Some( Ok( package ) ) => package.get_record_ports( self ),
+            _ => Vec::new(),
+        }
+    }
+
+    /**
+     * Ports as declared by an entity, i.e. the fixed ports and the record ports if a record
+     * standard is set.
+     */
+    pub fn get_declared_ports( & self ) -> Vec< Port > {
+        let records = self.get_record_ports();
+        if records.is_empty() {
+            return self.ports.clone();
+        }
+        let mut ports : Vec< Port > = self.ports.iter().filter( | p | p.is_fixed() ).cloned().collect();
+        ports.extend( records );
+        ports
+    }
+
     pub fn get_generics( & self ) -> & Vec< Generic > {
         & self.generics
     }
@@ -104,11 +505,21 @@ impl EntityInterface {
         }
     }
 
+    /**
+     * Switches an interface with two role views to the other role.  Without exactly two views
+     * every port but the fixed ones is inverted and the role is cleared, as no view is known to
+     * be the inverse one.
+     */
     pub fn invert( & mut self ) {
+        if self.views.len() == 2 {
+            let other = self.views.iter().find( | v | v.get_name() != & self.role ).unwrap().get_name().clone();
+            self.set_role( & other ).unwrap();
+            return;
+        }
         for port in & mut self.ports {
             port.invert();
         }
-        self.inverted = ! self.inverted;
+        self.role.clear();
     }
 
     pub fn add_generic( & mut self, generic : Generic ) {
@@ -119,6 +530,28 @@ impl EntityInterface {
         self.generics.clear();
     }
 
+    pub fn get_generic_mut( & mut self, name : & str ) -> Option< & mut Generic > {
+        self.generics.iter_mut().find( | g | g.get_base_name() == name )
+    }
+
+    pub fn remove_generic( & mut self, name : & str ) {
+        self.generics.retain( | g | g.get_base_name() != name );
+    }
+
+    /**
+     * Replaces the identifier `name` in the data types and defaults of the ports, e.g. a generic
+     * in `std_logic_vector( DATA_WIDTH - 1 downto 0 )`.  Identifiers compare case-insensitively.
+     */
+    pub fn replace_in_port_types( & mut self, name : & str, replacement : & str ) {
+        let regex = Regex::new( & format!( r"(?i)\b{}\b", regex::escape( name ) ) ).unwrap();
+        for port in & mut self.ports {
+            let data_type = regex.replace_all( port.get_data_type(), NoExpand( replacement ) ).to_string();
+            let default = regex.replace_all( port.get_default(), NoExpand( replacement ) ).to_string();
+            port.set_data_type( & data_type );
+            port.set_default( & default );
+        }
+    }
+
     pub fn remove_interface_from_generics( & mut self ) {
         for generic in & mut self.generics {
             generic.remove_interface();
@@ -127,6 +560,7 @@ impl EntityInterface {
 
     pub fn add_port( & mut self, port : Port ) {
         self.ports.push( port );
+        self.complete_views();
     }
 
     fn read_schema() -> Result< Schema, Box< dyn Error > > {
@@ -136,16 +570,137 @@ impl EntityInterface {
         Ok( schema )
     }
 
+    /**
+     * Reads an interface description and resolves its `extends`, `include` and `$ref`s into a
+     * flat interface.  Class names are looked up next to `file` and in the built-in catalog.
+     */
     fn read_and_validate_description( file : & Path, schema : & Schema )
             -> Result< EntityInterface, Box< dyn Error > > {
+        Resolver::new( Some( schema ) ).read( file )
+    }
+
+    /**
+     * Adds `port` or replaces the port with the same name.
+     */
+    fn add_or_replace_port( & mut self, port : Port ) {
+        match self.ports.iter().position( | p | p.get_base_name() == port.get_base_name() ) {
+            Some( idx ) => self.ports[ idx ] = port,
+            None => self.ports.push( port ),
+        }
+    }
+
+    fn add_or_replace_generic( & mut self, generic : Generic ) {
+        match self.generics.iter().position( | g | g.get_base_name() == generic.get_base_name() ) {
+            Some( idx ) => self.generics[ idx ] = generic,
+            None => self.generics.push( generic ),
+        }
+    }
+}
+
+/**
+ * Resolves composed interface descriptions, keeping the files being read to detect cycles.
+ */
+struct Resolver< 'a > {
+    schema : Option< &'a Schema >,
+    files : Vec< PathBuf >,
+}
+
+impl< 'a > Resolver< 'a > {
+    fn new( schema : Option< &'a Schema > ) -> Resolver< 'a > {
+        Resolver { schema, files : Vec::new() }
+    }
+
+    fn read( & mut self, file : & Path ) -> Result< EntityInterface, Box< dyn Error > > {
+        let canonical = fs::canonicalize( file )?;
+        if self.files.contains( & canonical ) {
+            return Err( Box::new( VhdlError::new( & format!( "error: Cyclic interface reference to {:?}!",
+                file.to_string_lossy() ) ) ) );
+        }
         let module_str = fs::read_to_string( file )?;
-        let module_json : serde_json::Value = serde_json::from_str( & module_str )?;
-        match schema.validate( & module_json ) {
-            Ok(_)   => {},
-            Err( err ) => { eprintln!( "Failed to validate the {:?}\n    with error {:?}",
-                file.to_string_lossy(), err ) }, };
-        let description : EntityInterface = serde_json::from_str( & module_str )?;
-        Ok( description )
+        if let Some( schema ) = self.schema {
+            let module_json : serde_json::Value = serde_json::from_str( & module_str )?;
+            match schema.validate( & module_json ) {
+                Ok(_)   => {},
+                Err( err ) => { eprintln!( "Failed to validate the {:?}\n    with error {:?}",
+                    file.to_string_lossy(), err ) }, };
+        }
+        self.files.push( canonical );
+        let interface = self.resolve( & module_str, file.parent() );
+        self.files.pop();
+        interface
+    }
+
+    fn resolve( & mut self, description : & str, dir : Option< & Path > )
+            -> Result< EntityInterface, Box< dyn Error > > {
+        let own : EntityInterface = serde_json::from_str( description )?;
+        let composition : Composition = serde_json::from_str( description )?;
+        let mut interface = own.clone();
+        interface.generics.clear();
+        interface.ports.clear();
+        if let Some( reference ) = & composition.extends {
+            let base = self.get( reference, dir )?;
+            interface.base = base.class.clone();
+            interface.generics = base.generics;
+            interface.ports = base.ports;
+            interface.parts = base.parts;
+            if interface.views.is_empty() {
+                interface.views = base.views;
+            }
+        }
+        for include in & composition.include {
+            let mut sub = self.get( & include.get_reference(), dir )?;
+            if ! include.role.is_empty() {
+                sub.set_role( & include.role )?;
+            }
+            else if include.inverted {
+                sub.invert();
+            }
+            let mut part = InterfacePart::new( & include.prefix, & sub.class, include.inverted, & sub.role );
+            for generic in sub.generics {
+                if ! interface.generics.iter().any( | g | g.get_base_name() == generic.get_base_name() ) {
+                    interface.generics.push( generic );
+                }
+            }
+            for mut port in sub.ports {
+                port.set_name( & format!( "{}{}", include.prefix, port.get_base_name() ) );
+                part.add_port( port.get_base_name() );
+                interface.add_or_replace_port( port );
+            }
+            interface.parts.push( part );
+        }
+        for generic in own.generics {
+            interface.add_or_replace_generic( generic );
+        }
+        for port in own.ports {
+            interface.add_or_replace_port( port );
+        }
+        interface.complete_views();
+        Ok( interface )
+    }
+
+    fn get( & mut self, reference : & Reference, dir : Option< & Path > )
+            -> Result< EntityInterface, Box< dyn Error > > {
+        match reference {
+            Reference::File { file } => match dir {
+                Some( dir ) => self.read( & dir.join( file ) ),
+                None => self.read( Path::new( file ) ),
+            },
+            Reference::Class( class ) => {
+                if let Some( dir ) = dir {
+                    let ( class_name, role ) = split_role_reference( class );
+                    let mut registry = InterfaceRegistry::new();
+                    registry.add_search_path( dir )?;
+                    if let Some( path ) = registry.get_path( class_name ) {
+                        let mut interface = self.read( path )?;
+                        if let Some( role ) = role {
+                            interface.set_role( role )?;
+                        }
+                        return Ok( interface );
+                    }
+                }
+                Ok( interface_catalog::get_interface( class )? )
+            },
+        }
     }
 }
 
@@ -219,6 +774,135 @@ mod tests {
         assert_eq!( INVERTED, source );
     }
 
+    #[test]
+    fn include() -> Result< (), Box< dyn Error > > {
+        let interface = EntityInterface::from_file( "axi", Path::new( "tests/vhdl/composition/axi_write.json" ) )?;
+        let names : Vec< String > = interface.get_ports().iter().map( | p | p.get_name() ).collect();
+        assert_eq!( names, vec![ "axi_awvalid", "axi_awready", "axi_awaddr", "axi_wvalid", "axi_wready",
+            "axi_bvalid", "axi_bready", "axi_bresp", "axi_wdata" ] );
+        assert_eq!( interface.get_ports()[ 5 ].get_direction(), Direction::IN );
+        assert_eq!( interface.get_generics().len(), 2 );
+        let parts = interface.get_parts();
+        assert_eq!( parts.len(), 3 );
+        assert_eq!( parts[ 0 ].get_class(), "axi_aw" );
+        assert_eq!( parts[ 0 ].get_ports(), & vec![ "awvalid", "awready", "awaddr" ] );
+        assert!( parts[ 2 ].is_inverted() );
+        assert_eq!( interface.clone_inverted().get_ports()[ 5 ].get_direction(), Direction::OUT );
+        Ok(())
+    }
+
+    #[test]
+    fn extends() -> Result< (), Box< dyn Error > > {
+        let interface = EntityInterface::from_file_unnamed( Path::new( "tests/vhdl/composition/axi_write_user.json" ) )?;
+        assert_eq!( interface.get_class(), "axi_write_user" );
+        assert_eq!( interface.get_base_class(), "axi_write" );
+        assert_eq!( interface.get_role(), "host" );
+        assert_eq!( interface.get_ports().len(), 10 );
+        assert!( interface.get_ports()[ 7 ].is_optional() );
+        assert_eq!( interface.get_ports()[ 9 ].get_name(), "awuser" );
+        assert_eq!( interface.get_parts().len(), 3 );
+        Ok(())
+    }
+
+    #[test]
+    fn role_views() -> Result< (), Box< dyn Error > > {
+        let mut interface = EntityInterface::from_json_str( concat!( "{ \"class\" : \"test\", ",
+                "\"views\" : [ { \"name\" : \"host\", \"modes\" : { \"q\" : \"buffer\" } }, \"agent\" ], ",
+                "\"ports\" : [ { \"name\" : \"q\", \"direction\" : \"out\", \"data_type\" : \"bit\" }, ",
+                "{ \"name\" : \"d\", \"direction\" : \"out\", \"data_type\" : \"bit\" } ] }" ) )?;
+        assert_eq!( interface.get_role(), "host" );
+        assert_eq!( interface.get_ports()[ 0 ].get_direction(), Direction::BUFFER );
+        interface.invert();
+        assert_eq!( interface.get_role(), "agent" );
+        assert_eq!( interface.get_ports()[ 0 ].get_direction(), Direction::IN );
+        assert_eq!( interface.get_ports()[ 1 ].get_direction(), Direction::IN );
+        interface.invert();
+        assert_eq!( interface.get_ports()[ 0 ].get_direction(), Direction::BUFFER );
+        interface.add_port( Port::new( "e", Direction::IN, "bit" ) );
+        assert_eq!( interface.clone_view( "agent" )?.get_ports()[ 2 ].get_direction(), Direction::OUT );
+        assert!( interface.set_role( "monitor" ).is_err() );
+
+        // with a third view the inverse role is ambiguous
+        let mut monitor = RoleView::new( "monitor" );
+        monitor.set_mode( "q", Direction::IN );
+        interface.add_view( monitor );
+        interface.invert();
+        assert_eq!( interface.get_role(), "" );
+        assert_eq!( interface.get_ports()[ 0 ].get_direction(), Direction::IN );
+        Ok(())
+    }
+
+    #[test]
+    fn subset() -> Result< (), Box< dyn Error > > {
+        let interface = interface_catalog::get_interface( "avalon_mmi" )?;
+        let mut read_only = interface.clone_subset( | p | ! p.get_base_name().starts_with( "write" ) );
+        assert_eq!( read_only.get_ports().len(), 6 );
+        assert_eq!( read_only.get_class(), "avalon_mmi" );
+        assert!( read_only.get_views()[ 1 ].get_mode( "write_data" ).is_none() );
+        read_only.invert();
+        assert_eq!( read_only.get_role(), "agent" );
+        assert_eq!( read_only.get_ports()[ 0 ].get_direction(), Direction::IN );
+        Ok(())
+    }
+
+    #[test]
+    fn split() -> Result< (), Box< dyn Error > > {
+        let interface = interface_catalog::get_interface( "axi4_stream" )?;
+        let parts = interface.clone_split( & [ ( "payload", "^t(data|strb|keep|last)$" ),
+                ( "handshake", "^t(valid|ready)$" ), ( "side", ".*" ) ] )?;
+        let sizes : Vec< usize > = parts.iter().map( | p | p.get_ports().len() ).collect();
+        assert_eq!( sizes, vec![ 4, 2, 3 ] );
+        assert_eq!( parts[ 1 ].get_ports()[ 1 ].get_name(), "handshake_tready" );
+        assert!( interface.clone_split( & [ ( "payload", "^tdata$" ), ( "handshake", "^t(valid|ready)$" ) ] ).is_err() );
+        assert!( interface.clone_split( & [ ( "a", "^tdata$" ), ( "a", ".*" ) ] ).is_err() );
+        assert!( interface.clone_split( & [ ( "a", "(" ) ] ).is_err() );
+        Ok(())
+    }
+
+    #[test]
+    fn merge() -> Result< (), Box< dyn Error > > {
+        let mut bus = interface_catalog::get_interface( "avalon_mmi" )?;
+        bus.rename( "bus" );
+        let clk_rst = interface_catalog::get_interface( "clk_rst" )?;
+        let merged = bus.clone_merged( & clk_rst, "avalon_mmi_clocked" )?;
+        assert_eq!( merged.get_ports().len(), 10 );
+        assert_eq!( merged.get_ports()[ 8 ].get_name(), "bus_clk" );
+        assert_eq!( merged.get_lineage(), & vec![ "avalon_mmi".to_string(), "clk_rst".to_string() ] );
+        assert!( merged.is_derived_from( "clk_rst" ) && ! merged.is_derived_from( "apb" ) );
+        // merging again keeps the classes merged before
+        let mut irq = EntityInterface::new( "", "irq" );
+        irq.add_port( Port::new( "irq", Direction::IN, "std_logic" ) );
+        let twice = merged.clone_merged( & irq, "avalon_mmi_irq" )?;
+        assert_eq!( twice.get_lineage(), & vec![ "avalon_mmi_clocked".to_string(), "avalon_mmi".to_string(),
+            "clk_rst".to_string(), "irq".to_string() ] );
+        assert_eq!( merged.clone_inverted().get_ports()[ 8 ].get_direction(), Direction::IN );
+        assert!( bus.clone_merged( & bus, "twice" ).is_err() );
+        Ok(())
+    }
+
+    #[test]
+    fn rename_ports() -> Result< (), Box< dyn Error > > {
+        let interface = interface_catalog::get_interface( "avalon_mmi" )?;
+        let renamed = interface.clone_with_renamed_ports( | n | format!( "m_{}", n ) )?;
+        assert_eq!( renamed.get_ports()[ 0 ].get_base_name(), "m_address" );
+        assert_eq!( renamed.get_views()[ 1 ].get_mode( "m_address" ), Some( Direction::IN ) );
+        assert!( interface.clone_with_renamed_ports( | n | n.replace( "read_", "write_" ) ).is_err() );
+        // swapping two names keeps the modes with the ports
+        let swapped = interface.clone_with_renamed_ports( | n | match n {
+            "read_data" => "write_data".to_string(),
+            "write_data" => "read_data".to_string(),
+            _ => n.to_string(),
+        } )?;
+        assert_eq!( swapped.get_views()[ 0 ].get_mode( "read_data" ), Some( Direction::OUT ) );
+        assert_eq!( swapped.get_views()[ 0 ].get_mode( "write_data" ), Some( Direction::IN ) );
+        Ok(())
+    }
+
+    #[test]
+    fn cyclic_reference() {
+        assert!( EntityInterface::from_file_unnamed( Path::new( "tests/vhdl/composition/cycle_a.json" ) ).is_err() );
+    }
+
     fn new_test_interface() -> EntityInterface {
         let mut interface = EntityInterface::new_unnamed( "test" );
         add_generics( & mut interface );
@@ -1,16 +1,40 @@
+use std::collections::HashMap;
+use crate::vhdl::generic::Generic;
 use crate::vhdl::generic_binding::GenericBinding;
 use crate::vhdl::port_binding::PortBinding;
 use crate::vhdl::entity_interface::EntityInterface;
 use crate::vhdl::direction::Direction;
+use crate::vhdl::expression::substitute_identifiers;
+use crate::vhdl::port::Port;
+use crate::vhdl::port_mapping::PortMapping;
 use crate::vhdl::signal_declaration::SignalDeclaraion;
+use crate::vhdl::vhdl_error::VhdlError;
 use crate::vhdl::match_index::*;
+use crate::vhdl::matcher::Matcher;
+
+/**
+ * Signal names for the ports of two connected interfaces and the signals to declare, see
+ * `EntityInterfaceBinding::get_connection_names`.
+ */
+#[derive(Clone, Default)]
+pub struct ConnectionNames {
+    pub names_a : Vec< String >,
+    pub names_b : Vec< String >,
+    /// Signals between the two interfaces
+    pub signals : Vec< SignalDeclaraion >,
+    /// Nets of fixed ports shared with all other interfaces, named after the interface and member
+    pub shared : Vec< SignalDeclaraion >,
+}
 
 #[derive(Clone)]
 pub struct EntityInterfaceBinding {
     name : String,
     class : String,
+    lineage : Vec< String >,
+    role : String,
     generics : Vec< GenericBinding >,
     ports : Vec< PortBinding >,
+    records : Vec< PortBinding >,
     bound : bool,
 }
 
@@ -19,8 +43,11 @@ impl EntityInterfaceBinding {
         EntityInterfaceBinding {
                 name : interface.get_name().to_string(),
                 class : interface.get_class().to_string(),
+                lineage : interface.get_ancestry().into_iter().skip( 1 ).collect(),
+                role : interface.get_role().to_string(),
                 generics : EntityInterfaceBinding::generic_bindings_from_interface( interface ),
                 ports : EntityInterfaceBinding::port_bindings_from_interface( interface ),
+                records : interface.get_record_ports().iter().map( PortBinding::from_port ).collect(),
                 bound : false,
         }
     }
@@ -33,6 +60,27 @@ impl EntityInterfaceBinding {
         & self.class
     }
 
+    /**
+     * Base class and classes the interface was merged from, see `EntityInterface::get_ancestry`.
+     */
+    pub fn get_lineage( & self ) -> & Vec< String > {
+        & self.lineage
+    }
+
+    /**
+     * The interface is of class `class`, extends it or was merged from it.
+     */
+    pub fn is_derived_from( & self, class : & str ) -> bool {
+        self.class == class || self.lineage.iter().any( | c | c == class )
+    }
+
+    /**
+     * Role view of the interface, see `EntityInterface::get_role`.
+     */
+    pub fn get_role( & self ) -> & String {
+        & self.role
+    }
+
     pub fn get_generics( & self ) -> & Vec< GenericBinding > {
         & self.generics
     }
@@ -41,6 +89,14 @@ impl EntityInterfaceBinding {
         & self.ports
     }
 
+    /**
+     * Record ports of an interface declared with records, see
+     * `EntityInterface::set_record_standard`.  The other ports are bound as their elements.
+     */
+    pub fn get_records( & self ) -> & Vec< PortBinding > {
+        & self.records
+    }
+
     pub fn get_unbound_generics( & self ) -> Vec< GenericBinding > {
         let mut vec = Vec::new();
         for generic in & self.generics {
@@ -63,18 +119,163 @@ impl EntityInterfaceBinding {
         ! self.is_bound()
     }
 
-    pub fn connect_to_entity_interface( & mut self, entity : & EntityInterface ) {
-        let mut idx = 0;
+    /**
+     * An interface without generics and ports, e.g. an unused default interface.
+     */
+    pub fn is_empty( & self ) -> bool {
+        self.generics.is_empty() && self.ports.is_empty()
+    }
+
+    /**
+     * Binds the interface to an entity interface of the same class, see `get_member_pairs`.
+     * Ports missing on the entity interface are tied off.
+     */
+    pub fn connect_to_entity_interface( & mut self, entity : & EntityInterface ) -> Result< (), VhdlError > {
+        let pairs = self.get_member_pairs( & get_entity_members( entity ), entity.get_name() )?;
+        let same_generics = self.generics.len() == entity.get_generics().len();
+        for ( idx, generic ) in self.generics.iter_mut().enumerate() {
+            if same_generics {
+                generic.connect( & entity.get_generics()[ idx ] );
+            }
+            else if let Some( outer ) = entity.get_generics().iter()
+                    .find( | g | g.get_base_name() == generic.get_member() ) {
+                generic.connect( outer );
+            }
+        }
+        for ( port, pair ) in self.ports.iter_mut().zip( pairs ) {
+            match pair {
+                Some( idx ) => port.connect( & entity.get_ports()[ idx ] ),
+                None => port.tie_off()?,
+            }
+        }
+        let entity_records = entity.get_record_ports();
+        for record in & mut self.records {
+            if let Some( outer ) = entity_records.iter().find( | r | r.get_data_type() == record.get_data_type() ) {
+                record.connect( outer );
+            }
+        }
+        self.bound = true;
+        Ok(())
+    }
+
+    /**
+     * Pairs the ports with the ports of another interface of the same class, given as member name
+     * and optional flag, and returns the index of the other port for each port.  Ports are
+     * paired by member name only.  Ports missing on the other side have to be optional and are
+     * not paired.
+     */
+    pub fn get_member_pairs( & self, other : & [ ( String, bool ) ], other_name : & str )
+            -> Result< Vec< Option< usize > >, VhdlError > {
+        let pairs : Vec< Option< usize > > = self.ports.iter()
+                .map( | p | other.iter().position( | ( m, _ ) | m.to_lowercase() == p.get_member().to_lowercase() ) )
+                .collect();
+        if let Some( ( port, _ ) ) = self.ports.iter().zip( & pairs )
+                .find( | ( p, pair ) | pair.is_none() && ! p.is_optional() ) {
+            return Err( VhdlError::new( & format!( "error: Port {:?} is missing in interface {:?} and not optional!",
+                    port.get_inner(), other_name ) ) );
+        }
+        for ( idx, ( member, optional ) ) in other.iter().enumerate() {
+            if ! optional && ! pairs.contains( & Some( idx ) ) {
+                return Err( VhdlError::new( & format!( "error: Port {:?} of interface {:?} is missing in interface {:?} and not optional!",
+                        member, other_name, self.name ) ) );
+            }
+        }
+        Ok( pairs )
+    }
+
+    /**
+     * Pairs the ports with the ports of another instance interface, see `get_member_pairs`.  If
+     * one interface is derived from the class of the other, e.g. merged with clock and reset,
+     * its ports missing on the other side are not paired whether optional or not.
+     */
+    pub fn get_binding_pairs( & self, other : & EntityInterfaceBinding )
+            -> Result< Vec< Option< usize > >, VhdlError > {
+        if self.class == other.class {
+            return self.get_member_pairs( & other.get_members(), other.get_name() );
+        }
+        let mut members = other.get_members();
+        if other.is_derived_from( & self.class ) {
+            for member in & mut members {
+                member.1 = true;
+            }
+        }
+        let pairs : Vec< Option< usize > > = self.ports.iter()
+                .map( | p | members.iter().position( | ( m, _ ) | m.to_lowercase() == p.get_member().to_lowercase() ) )
+                .collect();
+        let own_extras = self.is_derived_from( & other.class );
+        if let Some( ( port, _ ) ) = self.ports.iter().zip( & pairs )
+                .find( | ( p, pair ) | pair.is_none() && ! p.is_optional() && ! own_extras ) {
+            return Err( VhdlError::new( & format!( "error: Port {:?} is missing in interface {:?} and not optional!",
+                    port.get_inner(), other.name ) ) );
+        }
+        for ( idx, ( member, optional ) ) in members.iter().enumerate() {
+            if ! optional && ! pairs.contains( & Some( idx ) ) {
+                return Err( VhdlError::new( & format!( "error: Port {:?} of interface {:?} is missing in interface {:?} and not optional!",
+                        member, other.name, self.name ) ) );
+            }
+        }
+        Ok( pairs )
+    }
+
+    /**
+     * Member names and optional flags of the ports, see `get_member_pairs`.
+     */
+    pub fn get_members( & self ) -> Vec< ( String, bool ) > {
+        self.ports.iter().map( | p | ( p.get_member().clone(), p.is_optional() ) ).collect()
+    }
+
+    /**
+     * Binds the interface to an entity interface by member names, see `PortMapping::pair`.
+     * Generics are bound where the entity interface has a generic of the mapped name.
+     */
+    pub fn connect_to_entity_interface_by_mapping( & mut self, entity : & EntityInterface,
+            mapping : & PortMapping ) -> Result< (), VhdlError > {
+        let outer : Vec< ( String, Direction ) > = entity.get_ports().iter()
+                .map( | p | ( p.get_base_name().clone(), p.get_direction() ) ).collect();
+        let pairs = self.pair_ports( & outer, mapping, false )?;
         for generic in & mut self.generics {
-            generic.connect( & entity.get_generics()[ idx ] );
-            idx += 1;
+            let target = mapping.get( generic.get_member() ).to_lowercase();
+            if let Some( outer ) = entity.get_generics().iter()
+                    .find( | g | g.get_base_name().to_lowercase() == target ) {
+                generic.connect( outer );
+            }
         }
-        idx = 0;
-        for port in & mut self.ports {
-            port.connect( & entity.get_ports()[ idx ] );
-            idx += 1;
+        for ( port, idx ) in self.ports.iter_mut().zip( pairs ) {
+            port.connect_by_name( & entity.get_ports()[ idx ].get_name() );
         }
         self.bound = true;
+        Ok(())
+    }
+
+    /**
+     * Pairs the ports of this interface with the complementary ports of `other` by member names
+     * and returns the index of the port in `other` for each port.
+     */
+    pub fn get_instance_port_pairs( & self, other : & EntityInterfaceBinding, mapping : & PortMapping )
+            -> Result< Vec< usize >, VhdlError > {
+        let outer : Vec< ( String, Direction ) > = other.get_ports().iter()
+                .map( | p | ( p.get_member().clone(), * p.get_direction() ) ).collect();
+        self.pair_ports( & outer, mapping, true )
+    }
+
+    /**
+     * Binds the ports at the given indices to the signals of the list, e.g. from the pairs of
+     * `get_instance_port_pairs`.
+     */
+    pub fn connect_to_signal_list_by_index( & mut self, signal_list : & [ SignalDeclaraion ],
+            indices : & Vec< usize > ) {
+        for ( signal, idx ) in signal_list.iter().zip( indices ) {
+            self.ports[ * idx ].connect_by_name( signal.get_name() );
+        }
+        self.bound = true;
+    }
+
+    fn pair_ports( & self, outer : & [ ( String, Direction ) ], mapping : & PortMapping,
+            complementary : bool ) -> Result< Vec< usize >, VhdlError > {
+        let inner : Vec< ( String, Direction ) > = self.ports.iter()
+                .map( | p | ( p.get_member().clone(), * p.get_direction() ) ).collect();
+        mapping.pair( & inner, outer, complementary ).map_err( | e | VhdlError::new( & format!(
+                "{} (interface {:?})", e, self.name ) ) )
     }
 
     pub fn contains_generic( & self, name : & str ) -> bool {
@@ -82,7 +283,7 @@ impl EntityInterfaceBinding {
     }
 
     pub fn contains_port( & self, name : & str ) -> bool {
-        self.ports.iter().any( |p| p.get_inner() == name )
+        self.ports.iter().chain( self.records.iter() ).any( |p| p.get_inner() == name )
     }
 
     pub fn get_generic_mut( & mut self, name : & str ) -> Option< & mut GenericBinding > {
@@ -95,60 +296,174 @@ impl EntityInterfaceBinding {
     }
 
     pub fn get_port_mut( & mut self, name : & str ) -> Option< & mut PortBinding > {
-        for port in & mut self.ports {
-            if port.get_inner() == name {
-                return Some( port );
-            }
-        }
-        None
+        self.ports.iter_mut().chain( self.records.iter_mut() ).find( | p | p.get_inner() == name )
     }
 
-    pub fn get_entity_matching( & self, entity : & EntityInterface ) -> u32 {
+    /**
+     * Strength of `matcher`, at least `CLASS` if one interface is derived from the class of the
+     * other, e.g. a merged `avalon_mmi_clocked` and an `avalon_mmi`.
+     */
+    pub fn get_entity_matching( & self, entity : & EntityInterface, matcher : & dyn Matcher ) -> u32 {
         if self.bound {
             return NONE;
         }
-        let instance_name = self.get_name().to_string().to_lowercase();
-        let entity_name = entity.get_name().to_string().to_lowercase();
-        let class_match : bool = self.get_class() == entity.get_class();
-        let name_match : bool = instance_name == entity_name;
-        let instance_in_entity = entity_name.contains( & instance_name );
-        let entity_in_instance = instance_name.contains( & entity_name );
-        if class_match && name_match {
-            return FULL;
+        let strength = matcher.get_match_strength( & self.name, & self.class, entity.get_name(), entity.get_class() );
+        let derived = self.is_derived_from( entity.get_class() ) || entity.is_derived_from( & self.class );
+        if strength == NONE && & self.class != entity.get_class() && derived {
+            return CLASS;
         }
-        else if instance_in_entity || entity_in_instance {
-            return PARTIAL;
+        strength
+    }
+
+    pub fn get_instance_matching( & self, inst_b : & EntityInterfaceBinding, matcher : & dyn Matcher )
+            -> u32 {
+        if self.bound {
+            return NONE;
         }
-        else if class_match {
+        let strength = matcher.get_match_strength( & self.name, & self.class, inst_b.get_name(), inst_b.get_class() );
+        let derived = self.is_derived_from( & inst_b.class ) || inst_b.is_derived_from( & self.class );
+        if strength == NONE && self.class != inst_b.class && derived {
             return CLASS;
         }
-        else {
-            return NONE;
+        strength
+    }
+
+    /**
+     * True if the interface can be passed through to the entity interface: the paired ports have
+     * the same directions, and interfaces with roles of the same class have the same role.
+     */
+    pub fn has_same_directions( & self, entity : & EntityInterface ) -> bool {
+        self.check_same_directions( entity ).unwrap_or( false )
+    }
+
+    /**
+     * Like `has_same_directions`, but fails if the ports can not be paired.
+     */
+    pub fn check_same_directions( & self, entity : & EntityInterface ) -> Result< bool, VhdlError > {
+        let pairs = self.get_member_pairs( & get_entity_members( entity ), entity.get_name() )?;
+        if self.has_roles_of( entity.get_class(), entity.get_role() ) && & self.role != entity.get_role() {
+            return Ok( false );
         }
+        Ok( self.ports.iter().zip( pairs ).all( | ( inner, pair ) | pair.is_none_or( | idx |
+                * inner.get_direction() == entity.get_ports()[ idx ].get_direction() ) ) )
     }
 
-    pub fn get_instance_matching( & self, inst_b : & EntityInterfaceBinding ) -> u32 {
-        if self.bound {
-            return NONE;
+    /**
+     * True if both interfaces can be connected by signals: the paired ports have inverted
+     * directions in the modes of both roles, and interfaces with roles of the same class have
+     * different roles.  Interfaces of fixed ports only are connected to shared nets whatever
+     * their roles.
+     */
+    pub fn is_complementary( & self, other : & EntityInterfaceBinding ) -> bool {
+        self.check_complementary( other ).unwrap_or( false )
+    }
+
+    /**
+     * Like `is_complementary`, but fails if the ports can not be paired.
+     */
+    pub fn check_complementary( & self, other : & EntityInterfaceBinding ) -> Result< bool, VhdlError > {
+        let pairs = self.get_binding_pairs( other )?;
+        let all_fixed = self.ports.iter().zip( & pairs ).all( | ( a, pair ) |
+                pair.is_none_or( | idx | a.is_fixed() && other.ports[ idx ].is_fixed() ) );
+        if self.has_roles_of( & other.class, & other.role ) && ! all_fixed && self.role == other.role {
+            return Ok( false );
         }
-        let inst_a_name = self.get_name().to_string().to_lowercase();
-        let inst_b_name = inst_b.get_name().to_string().to_lowercase();
-        let class_match : bool = self.get_class() == inst_b.get_class();
-        let name_match : bool = inst_a_name == inst_b_name;
-        let inst_a_in_inst_b = inst_b_name.contains( & inst_a_name );
-        let inst_b_in_inst_a = inst_a_name.contains( & inst_b_name );
-        if class_match && name_match {
-            return FULL;
+        Ok( self.ports.iter().zip( pairs ).all( | ( a, pair ) | pair.is_none_or( | idx | {
+            let b = & other.ports[ idx ];
+            ( a.is_fixed() && b.is_fixed() && a.get_direction() == b.get_direction() ) ||
+                    a.get_direction().get_inverted() == * b.get_direction() ||
+                    b.get_direction().get_inverted() == * a.get_direction()
+        } ) ) )
+    }
+
+    /**
+     * Roles are only compared between interfaces of the same class that both have one.
+     */
+    fn has_roles_of( & self, class : & str, role : & str ) -> bool {
+        ! self.role.is_empty() && ! role.is_empty() && self.class == class
+    }
+
+    /**
+     * An interface can be promoted to the parent entity as a whole if none of its ports is bound.
+     */
+    pub fn is_promotable( & self ) -> bool {
+        self.is_unbound() && self.class != "__default__" && ! self.ports.is_empty() &&
+                self.ports.iter().all( | p | ! p.is_bound() )
+    }
+
+    /**
+     * Creates an entity interface named `name` with the class and ports of this binding.  Unbound
+     * generics are kept and port data types are rewritten with `generic_map` and the names of
+     * the kept generics.
+     */
+    pub fn to_entity_interface( & self, name : & str, generic_map : & HashMap< String, String > )
+            -> EntityInterface {
+        let mut interface = EntityInterface::new( "", & self.class );
+        let unbound_generics = self.get_unbound_generics();
+        for generic in & unbound_generics {
+            interface.add_generic( Generic::new_with_default( generic.get_member(),
+                    generic.get_data_type(), generic.get_default() ) );
         }
-        else if class_match && inst_a_in_inst_b || inst_b_in_inst_a {
-            return PARTIAL;
+        interface.rename( name );
+        let mut map = generic_map.clone();
+        for ( binding, generic ) in unbound_generics.iter().zip( interface.get_generics() ) {
+            // port data types may refer to the generic with or without interface prefix
+            map.insert( binding.get_member().clone(), generic.get_name() );
+            map.insert( binding.get_inner().clone(), generic.get_name() );
         }
-        else if class_match {
-            return CLASS;
+        for port in & self.ports {
+            let data_type = substitute_identifiers( port.get_data_type(), & map );
+            interface.add_port( Port::new_with_default( port.get_member(), * port.get_direction(),
+                    & data_type, port.get_default() ) );
         }
-        else {
-            return NONE;
+        interface.rename( name );
+        interface
+    }
+
+    /**
+     * Binds all unbound generics and ports to the members of an interface created by
+     * `to_entity_interface`.
+     */
+    pub fn connect_to_promoted_interface( & mut self, interface : & EntityInterface ) {
+        for generic in & mut self.generics {
+            if generic.is_bound() {
+                continue;
+            }
+            if let Some( outer ) = interface.get_generics().iter()
+                    .find( | g | g.get_base_name() == generic.get_member() ) {
+                generic.connect( outer );
+            }
         }
+        for port in & mut self.ports {
+            if let Some( outer ) = interface.get_ports().iter()
+                    .find( | p | p.get_base_name() == port.get_member() ) {
+                port.connect_by_name( & outer.get_name() );
+            }
+        }
+        self.bound = true;
+    }
+
+    /**
+     * Checks that every unbound port can be tied off, see `PortBinding::requires_tie_off`.
+     */
+    pub fn requires_tie_offs( & self ) -> Result< (), VhdlError > {
+        for port in self.ports.iter().filter( | p | ! p.is_bound() ) {
+            port.requires_tie_off()?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Ties off all unbound ports, see `PortBinding::tie_off`.  Check `requires_tie_offs` first
+     * to change nothing on an error.
+     */
+    pub fn tie_off_unbound_ports( & mut self ) -> Result< (), VhdlError > {
+        for port in & mut self.ports {
+            if ! port.is_bound() {
+                port.tie_off()?;
+            }
+        }
+        Ok(())
     }
 
     pub fn get_connection_signal_list( & self, inst_name_a : & str, inst_name_b : & str )
@@ -167,6 +482,38 @@ impl EntityInterfaceBinding {
         return signal_list;
     }
 
+    /**
+     * Returns the signal names binding this interface of instance `inst_name_a` and the
+     * interface `other` of instance `inst_name_b`, see `get_member_pairs`, together with the
+     * signals to declare.  Unpaired ports get an empty name and are tied off by
+     * `connect_to_names` if optional, fixed ports are bound to a shared net named after this
+     * interface and their member, e.g. `sys_clk`, or just the member for an unnamed interface.
+     */
+    pub fn get_connection_names( & self, other : & EntityInterfaceBinding, inst_name_a : & str,
+            inst_name_b : & str ) -> Result< ConnectionNames, VhdlError > {
+        let pairs = self.get_binding_pairs( other )?;
+        let mut names = ConnectionNames { names_b : vec![ String::new(); other.ports.len() ], ..Default::default() };
+        let signal_list = self.get_connection_signal_list( inst_name_a, inst_name_b );
+        for ( ( port, signal ), pair ) in self.ports.iter().zip( signal_list ).zip( pairs ) {
+            match pair {
+                Some( idx ) if port.is_fixed() && other.ports[ idx ].is_fixed() => {
+                    let net = if self.name.is_empty() { port.get_member().clone() }
+                        else { format!( "{}_{}", self.name, port.get_member() ) };
+                    names.names_a.push( net.clone() );
+                    names.names_b[ idx ] = net.clone();
+                    names.shared.push( SignalDeclaraion::new( & net, port.get_data_type() ) );
+                },
+                Some( idx ) => {
+                    names.names_a.push( signal.get_name().clone() );
+                    names.names_b[ idx ] = signal.get_name().clone();
+                    names.signals.push( signal );
+                },
+                None => names.names_a.push( String::new() ),
+            }
+        }
+        Ok( names )
+    }
+
     pub fn connect_to_signal_list( & mut self, signal_list : & Vec< SignalDeclaraion > ) {
         for ( idx, port ) in self.ports.iter_mut().enumerate() {
             port.connect_by_name( signal_list[ idx ].get_name() );
@@ -174,6 +521,32 @@ impl EntityInterfaceBinding {
         self.bound = true;
     }
 
+    /**
+     * Binds the ports in order to the given names, e.g. the signals of a connection plan.
+     * Optional ports with an empty name are tied off, other ports with an empty name stay
+     * unbound, e.g. the clock of an interface merged with `clk_rst` connected to an interface
+     * without one.
+     */
+    pub fn connect_to_names( & mut self, names : & Vec< String > ) -> Result< (), VhdlError > {
+        if names.len() != self.ports.len() {
+            return Err( VhdlError::new( & format!(
+                    "error: Interface {:?} has {} ports but {} names are given!",
+                    self.name, self.ports.len(), names.len() ) ) );
+        }
+        for ( port, name ) in self.ports.iter_mut().zip( names ) {
+            if name.is_empty() {
+                if port.is_optional() {
+                    port.tie_off()?;
+                }
+            }
+            else {
+                port.connect_by_name( name );
+            }
+        }
+        self.bound = true;
+        Ok(())
+    }
+
     fn generic_bindings_from_interface( interface : & EntityInterface ) -> Vec< GenericBinding > {
         let mut bindings : Vec< GenericBinding > = Vec::new();
         for generic in interface.get_generics() {
@@ -182,15 +555,33 @@ impl EntityInterfaceBinding {
         return bindings;
     }
 
+    /**
+     * Binds the ports of an interface declared with records as the elements of its record
+     * ports, e.g. `axi.awaddr`.
+     */
     fn port_bindings_from_interface( interface : & EntityInterface ) -> Vec< PortBinding > {
+        let records = interface.get_record_ports();
         let mut bindings : Vec< PortBinding > = Vec::new();
         for port in interface.get_ports() {
-            bindings.push( PortBinding::from_port( port ) );
+            let mut binding = PortBinding::from_port( port );
+            let record = records.iter().find( | r | ! r.get_view().is_empty() ||
+                    ( r.get_direction() == Direction::IN ) == ( port.get_direction() == Direction::IN ) );
+            if let Some( record ) = record.filter( | _ | ! port.is_fixed() ) {
+                binding.set_inner( & format!( "{}.{}", record.get_name(), port.get_base_name() ) );
+            }
+            bindings.push( binding );
         }
         return bindings;
     }
 }
 
+/**
+ * Member names and optional flags of the ports of an entity interface.
+ */
+pub fn get_entity_members( entity : & EntityInterface ) -> Vec< ( String, bool ) > {
+    entity.get_ports().iter().map( | p | ( p.get_base_name().clone(), p.is_optional() ) ).collect()
+}
+
 //------------------------------------------------------------------------------
 #[cfg(test)]
 mod tests {
@@ -198,6 +589,7 @@ mod tests {
     use std::error::Error;
     use std::path::Path;
     use crate::vhdl::entity::Entity;
+    use crate::vhdl::role_view::RoleView;
 
     const UNBOUND : &'static str = concat!( "interface test\n",
             "    A integer \n",
@@ -219,6 +611,75 @@ mod tests {
             "    c inout boolean c\n",
             "    d buffer positive d\n" );
 
+    #[test]
+    fn complementary_roles() {
+        let mut interface = EntityInterface::new( "bus", "test" );
+        interface.add_port( Port::new( "data", Direction::INOUT, "std_logic" ) );
+        let without_roles = EntityInterfaceBinding::from_entity_interface( & interface );
+        assert!( without_roles.is_complementary( & without_roles ) );
+        interface.set_views( "host", "agent" );
+        let host = EntityInterfaceBinding::from_entity_interface( & interface );
+        let agent = EntityInterfaceBinding::from_entity_interface( & interface.clone_inverted() );
+        assert_eq!( agent.get_role(), "agent" );
+        assert!( ! host.is_complementary( & host ) );
+        assert!( host.is_complementary( & agent ) );
+        assert!( host.has_same_directions( & interface ) );
+        assert!( ! agent.has_same_directions( & interface ) );
+    }
+
+    #[test]
+    fn member_pairs_by_name() {
+        let mut interface = EntityInterface::new( "bus", "test" );
+        interface.add_port( Port::new( "rd_data", Direction::IN, "std_logic" ) );
+        interface.add_port( Port::new( "addr", Direction::OUT, "std_logic" ) );
+        let binding = EntityInterfaceBinding::from_entity_interface( & interface );
+        let reordered = vec![ ( "addr".to_string(), false ), ( "rd_data".to_string(), false ) ];
+        assert_eq!( binding.get_member_pairs( & reordered, "other" ).unwrap(), vec![ Some( 1 ), Some( 0 ) ] );
+        let renamed = vec![ ( "addr".to_string(), false ), ( "read_data".to_string(), false ) ];
+        assert!( binding.get_member_pairs( & renamed, "other" ).is_err() );
+    }
+
+    #[test]
+    fn complementary_modes() {
+        let mut interface = EntityInterface::new( "bus", "test" );
+        interface.add_port( Port::new( "valid", Direction::OUT, "std_logic" ) );
+        interface.add_port( Port::new( "ready", Direction::IN, "std_logic" ) );
+        interface.set_views( "source", "sink" );
+        let mut monitor = RoleView::new( "monitor" );
+        monitor.set_mode( "valid", Direction::IN );
+        monitor.set_mode( "ready", Direction::IN );
+        interface.add_view( monitor );
+        let source = EntityInterfaceBinding::from_entity_interface( & interface );
+        interface.set_role( "sink" ).unwrap();
+        let sink = EntityInterfaceBinding::from_entity_interface( & interface );
+        interface.set_role( "monitor" ).unwrap();
+        let monitor = EntityInterfaceBinding::from_entity_interface( & interface );
+        assert!( source.is_complementary( & sink ) );
+        assert!( ! monitor.is_complementary( & sink ) );
+        assert!( ! sink.is_complementary( & monitor ) );
+        assert!( ! monitor.is_complementary( & source ) );
+    }
+
+    #[test]
+    fn derived_class_matching() -> Result< (), Box< dyn Error > > {
+        let bus = crate::vhdl::interface_catalog::get_interface( "avalon_mmi" )?;
+        let clk_rst = crate::vhdl::interface_catalog::get_interface( "clk_rst" )?;
+        let clocked = EntityInterfaceBinding::from_entity_interface(
+                & bus.clone_merged( & clk_rst, "avalon_mmi_clocked" )? );
+        let agent = EntityInterfaceBinding::from_entity_interface( & bus.clone_inverted() );
+        let matcher = crate::vhdl::matcher::HeuristicMatcher;
+        assert!( clocked.is_derived_from( "avalon_mmi" ) );
+        assert_eq!( clocked.get_instance_matching( & agent, & matcher ), CLASS );
+        assert_eq!( agent.get_instance_matching( & clocked, & matcher ), CLASS );
+        assert!( clocked.is_complementary( & agent ) );
+        let names = clocked.get_connection_names( & agent, "u0", "u1" )?;
+        assert_eq!( names.names_a[ 8 ], "" );
+        assert_eq!( names.names_b[ 0 ], "u0_to_u1_address" );
+        let irq = EntityInterfaceBinding::from_entity_interface( & EntityInterface::new( "", "irq" ) );
+        assert_eq!( clocked.get_instance_matching( & irq, & matcher ), NONE );
+        Ok(())
+    }
+
     #[test]
     fn from_entity_interface() -> Result< (), Box< dyn Error > > {
         let interface = EntityInterface::from_file_unnamed(
@@ -235,12 +696,31 @@ mod tests {
                 Path::new( "tests/vhdl/interface.json" ) )?;
         let mut binding = EntityInterfaceBinding::from_entity_interface( & interface );
         let entity = Entity::with_interface( "test", & interface );
-        binding.connect_to_entity_interface( & entity.get_interfaces()[ 1 ] );
+        binding.connect_to_entity_interface( & entity.get_interfaces()[ 1 ] )?;
         assert_eq!( BOUND, to_string( & binding ) );
         Ok(())
     }
 
 
+    #[test]
+    fn connect_to_entity_interface_by_mapping() -> Result< (), VhdlError > {
+        let mut inner = EntityInterface::new( "host", "bus" );
+        inner.add_port( Port::new( "rd_data", Direction::IN, "std_logic" ) );
+        inner.add_port( Port::new( "addr", Direction::OUT, "std_logic" ) );
+        let mut outer = EntityInterface::new( "", "bus" );
+        outer.add_port( Port::new( "addr", Direction::OUT, "std_logic" ) );
+        outer.add_port( Port::new( "read_data", Direction::IN, "std_logic" ) );
+        outer.rename( "cpu" );
+        let mut binding = EntityInterfaceBinding::from_entity_interface( & inner );
+        assert!( binding.connect_to_entity_interface_by_mapping( & outer, & PortMapping::new() ).is_err() );
+        assert!( binding.is_unbound() );
+        binding.connect_to_entity_interface_by_mapping( & outer,
+                & PortMapping::from_pairs( & [ ( "rd_data", "read_data" ) ] ) )?;
+        let outers : Vec< String > = binding.get_ports().iter().map( | p | p.get_outer() ).collect();
+        assert_eq!( outers, vec![ "cpu_read_data", "cpu_addr" ] );
+        Ok(())
+    }
+
     fn to_string( binding : & EntityInterfaceBinding ) -> String {
         let mut s = String::new();
         s.push_str( & format!( "{} {}\n", binding.get_name(), binding.get_class() ) );
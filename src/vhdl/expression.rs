@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+/**
+ * Returns all identifiers used in a VHDL expression in order of appearance.
+ *
+ * Character, string and bit string literals, numbers and attribute names are skipped.
+ */
+pub fn get_identifiers( expression : & str ) -> Vec< String > {
+    let mut identifiers = Vec::new();
+    for token in tokenize( expression ) {
+        if let Token::Identifier( identifier ) = token {
+            identifiers.push( identifier );
+        }
+    }
+    identifiers
+}
+
+/**
+ * Returns the leading identifier of a name, e.g. `data` for `data( 7 downto 0 )`.
+ */
+pub fn get_base_identifier( name : & str ) -> Option< String > {
+    match tokenize( name ).into_iter().next() {
+        Some( Token::Identifier( identifier ) ) => Some( identifier ),
+        _ => None,
+    }
+}
+
+/**
+ * Replaces every identifier contained in `map` by its mapped value.
+ */
+pub fn substitute_identifiers( expression : & str, map : & HashMap< String, String > ) -> String {
+    let mut source = String::new();
+    for token in tokenize( expression ) {
+        match token {
+            Token::Identifier( identifier ) => {
+                match map.get( & identifier ) {
+                    Some( replacement ) => source.push_str( replacement ),
+                    None => source.push_str( & identifier ),
+                }
+            },
+            Token::Other( text ) => source.push_str( & text ),
+        }
+    }
+    source
+}
+
+/**
+ * Normalizes a data type for comparison by removing white space and converting it to lower case.
+ */
+pub fn normalize_data_type( data_type : & str ) -> String {
+    data_type.chars().filter( | c | ! c.is_whitespace() ).collect::< String >().to_lowercase()
+}
+
+/**
+ * Returns the bounds `( low, high )` of a constant range like `7 downto 4` or `0 to 3`, or of a
+ * constant index like `5`.
+ */
+pub fn get_constant_range( range : & str ) -> Option< ( i64, i64 ) > {
+    let words : Vec< String > = range.split_whitespace().map( | w | w.to_lowercase() ).collect();
+    let bound = | word : & String | word.parse::< i64 >().ok();
+    match words.len() {
+        1 => bound( & words[ 0 ] ).map( | index | ( index, index ) ),
+        3 if words[ 1 ] == "downto" => Some( ( bound( & words[ 2 ] )?, bound( & words[ 0 ] )? ) ),
+        3 if words[ 1 ] == "to" => Some( ( bound( & words[ 0 ] )?, bound( & words[ 2 ] )? ) ),
+        _ => None,
+    }
+}
+
+/**
+ * Returns the text between the outermost parentheses, e.g. the range `7 downto 0` of
+ * `std_logic_vector( 7 downto 0 )` or the index of `data( 3 )`.
+ */
+pub fn get_parenthesized( text : & str ) -> Option< String > {
+    let start = text.find( '(' )?;
+    let end = text.rfind( ')' )?;
+    if end <= start {
+        return None;
+    }
+    Some( text[ start + 1 .. end ].trim().to_string() )
+}
+
+enum Token {
+    Identifier( String ),
+    Other( String ),
+}
+
+fn tokenize( expression : & str ) -> Vec< Token > {
+    let chars : Vec< char > = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        let start = idx;
+        let c = chars[ idx ];
+        if c.is_alphabetic() {
+            while idx < chars.len() && ( chars[ idx ].is_alphanumeric() || chars[ idx ] == '_' ) {
+                idx += 1;
+            }
+            let word : String = chars[ start..idx ].iter().collect();
+            if idx < chars.len() && chars[ idx ] == '"' {
+                // bit string literal like x"00"
+                idx = skip_string( & chars, idx );
+                tokens.push( Token::Other( chars[ start..idx ].iter().collect() ) );
+            }
+            else {
+                tokens.push( Token::Identifier( word ) );
+            }
+        }
+        else if c.is_ascii_digit() {
+            while idx < chars.len() && ( chars[ idx ].is_alphanumeric() || chars[ idx ] == '_'
+                    || chars[ idx ] == '.' || chars[ idx ] == '#' ) {
+                idx += 1;
+            }
+            tokens.push( Token::Other( chars[ start..idx ].iter().collect() ) );
+        }
+        else if c == '"' {
+            idx = skip_string( & chars, idx );
+            tokens.push( Token::Other( chars[ start..idx ].iter().collect() ) );
+        }
+        else if c == '\'' {
+            if idx + 2 < chars.len() && chars[ idx + 2 ] == '\'' {
+                // character literal like '0'
+                idx += 3;
+            }
+            else {
+                // attribute like 'length
+                idx += 1;
+                while idx < chars.len() && ( chars[ idx ].is_alphanumeric() || chars[ idx ] == '_' ) {
+                    idx += 1;
+                }
+            }
+            tokens.push( Token::Other( chars[ start..idx ].iter().collect() ) );
+        }
+        else {
+            idx += 1;
+            tokens.push( Token::Other( c.to_string() ) );
+        }
+    }
+    tokens
+}
+
+fn skip_string( chars : & [ char ], start : usize ) -> usize {
+    let mut idx = start + 1;
+    while idx < chars.len() && chars[ idx ] != '"' {
+        idx += 1;
+    }
+    std::cmp::min( idx + 1, chars.len() )
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers() {
+        assert_eq!( get_identifiers( "a and b" ), vec![ "a", "and", "b" ] );
+    }
+
+    #[test]
+    fn identifiers_skip_literals() {
+        assert_eq!( get_identifiers( "x when sel = '1' else x\"0F\" & \"01\" & y'length & 16#FF#" ),
+                vec![ "x", "when", "sel", "else", "y" ] );
+    }
+
+    #[test]
+    fn base_identifier() {
+        assert_eq!( get_base_identifier( "data( 7 downto 0 )" ), Some( "data".to_string() ) );
+        assert_eq!( get_base_identifier( "'0'" ), None );
+    }
+
+    #[test]
+    fn substitute() {
+        let mut map = HashMap::new();
+        map.insert( "WIDTH".to_string(), "host_WIDTH".to_string() );
+        assert_eq!( substitute_identifiers( "std_logic_vector( WIDTH - 1 downto 0 )", & map ),
+                "std_logic_vector( host_WIDTH - 1 downto 0 )" );
+    }
+
+    #[test]
+    fn constant_range() {
+        assert_eq!( get_constant_range( "7 DOWNTO 4" ), Some( ( 4, 7 ) ) );
+        assert_eq!( get_constant_range( "0 to 3" ), Some( ( 0, 3 ) ) );
+        assert_eq!( get_constant_range( " 5 " ), Some( ( 5, 5 ) ) );
+        assert_eq!( get_constant_range( "WIDTH - 1 downto 0" ), None );
+        assert_eq!( get_parenthesized( "std_logic_vector( 7 downto 0 )" ), Some( "7 downto 0".to_string() ) );
+        assert_eq!( get_parenthesized( "std_logic" ), None );
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!( normalize_data_type( "STD_LOGIC_VECTOR( 7 downto 0 )" ),
+                "std_logic_vector(7downto0)" );
+    }
+}
@@ -39,6 +39,13 @@ impl Generic {
         self.name = name.to_string();
     }
 
+    /**
+     * Name of the generic without the interface prefix.
+     */
+    pub fn get_base_name( & self ) -> & String {
+        & self.name
+    }
+
     pub fn set_interface( & mut self, interface : & str ) {
         self.interface = interface.to_string();
     }
@@ -55,6 +62,14 @@ impl Generic {
         & self.data_type
     }
 
+    pub fn get_default( & self ) -> & String {
+        & self.default
+    }
+
+    pub fn set_default( & mut self, default : & str ) {
+        self.default = default.to_string();
+    }
+
     pub fn has_default( & self ) -> bool {
         ! self.default.is_empty()
     }
@@ -1,43 +1,77 @@
 use crate::element::Element;
+use crate::vhdl::actual::Actual;
 use crate::vhdl::generic::Generic;
 
 #[derive(Clone)]
 pub struct GenericBinding {
     inner : String,
+    member : String,
     data_type : String,
-    outer : String,
+    default : String,
+    actual : Option< Actual >,
     requires_binding : bool
 }
 
 impl GenericBinding {
     pub fn from_generic( generic : & Generic ) -> GenericBinding{
         GenericBinding { inner : generic.get_name().to_string(),
+                member : generic.get_base_name().to_string(),
                 data_type : generic.get_data_type().to_string(),
-                outer : String::new(), requires_binding : ! generic.has_default() }
+                default : generic.get_default().to_string(),
+                actual : None, requires_binding : ! generic.has_default() }
     }
 
     pub fn connect( & mut self, generic : & Generic ) {
-        self.outer = generic.get_name().to_string();
+        self.connect_by_name( & generic.get_name() );
     }
 
     pub fn connect_by_name( & mut self, outer : & str ) {
-        self.outer = outer.to_string();
+        self.actual = Some( Actual::name( outer ) );
+    }
+
+    /**
+     * Overrides the generic with a literal value like `32`.
+     */
+    pub fn connect_to_literal( & mut self, value : & str ) {
+        self.actual = Some( Actual::literal( value ) );
+    }
+
+    pub fn connect_to_actual( & mut self, actual : & Actual ) {
+        self.actual = Some( actual.clone() );
     }
 
     pub fn get_inner( & self ) -> & String {
         & self.inner
     }
 
+    /**
+     * Name of the generic within its interface, i.e. without the interface prefix.
+     */
+    pub fn get_member( & self ) -> & String {
+        & self.member
+    }
+
     pub fn get_data_type( & self ) -> & String {
         & self.data_type
     }
 
-    pub fn get_outer( & self ) -> & String {
-        & self.outer
+    pub fn get_default( & self ) -> & String {
+        & self.default
+    }
+
+    pub fn get_actual( & self ) -> Option< & Actual > {
+        self.actual.as_ref()
+    }
+
+    pub fn get_outer( & self ) -> String {
+        match & self.actual {
+            Some( actual ) => actual.to_source_code( 0 ),
+            None => String::new(),
+        }
     }
 
     pub fn is_bound( & self ) -> bool {
-        ! self.outer.is_empty()
+        self.actual.is_some()
     }
 
     pub fn requires_binding( & self ) -> bool {
@@ -48,11 +82,11 @@ impl GenericBinding {
 impl Element for GenericBinding {
     fn to_source_code( & self, indent : usize ) -> String {
         let mut source = String::new();
-        if self.outer.is_empty() && ! self.requires_binding {
+        if ! self.is_bound() && ! self.requires_binding {
             return source;
         }
         let indent_str = crate::util::indent( indent );
-        source.push_str( & format!( "{}{} => {}", indent_str, self.inner, self.outer ) );
+        source.push_str( & format!( "{}{} => {}", indent_str, self.inner, self.get_outer() ) );
         return source;
     }
 }
@@ -73,6 +107,13 @@ mod tests {
         binding.connect( & generic );
         assert_eq!( & binding.to_source_code( 0 ), "test => extern" );
     }
+
+    #[test]
+    fn connect_to_literal() {
+        let mut binding = GenericBinding::from_generic( & Generic::new( "DATA_WIDTH", "positive" ) );
+        binding.connect_to_literal( "32" );
+        assert_eq!( & binding.to_source_code( 0 ), "DATA_WIDTH => 32" );
+    }
 }
 
 
@@ -0,0 +1,16 @@
+/**
+ * What `Entity::add_interface` does with a generic whose base name is already declared by
+ * another interface of the entity, e.g. `DATA_WIDTH` of two `avalon_mmi` interfaces.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GenericPolicy {
+    /// Declare one generic without prefix, the data types and defaults have to be equal
+    Share,
+    /// Prefix each generic with the name of its interface, the generic of an unnamed interface,
+    /// e.g. a generic of the entity itself, keeps its name
+    #[default]
+    Prefix,
+    /// Refuse the interface
+    Error,
+}
+
@@ -0,0 +1,103 @@
+use crate::element::Element;
+use crate::vhdl::keywords::*;
+use crate::vhdl::sequential_statement::SequentialStatement;
+use crate::vhdl::expression::get_identifiers;
+
+/**
+ * An `if` statement with optional `elsif` branches and `else` branch.  Statements are added to
+ * the branch started last.
+ */
+pub struct IfStatement {
+    branches : Vec< ( String, Vec< Box< dyn SequentialStatement > > ) >,
+    else_statements : Option< Vec< Box< dyn SequentialStatement > > >,
+}
+
+impl IfStatement {
+    pub fn new( condition : & str ) -> IfStatement {
+        IfStatement { branches : vec![ ( condition.to_string(), Vec::new() ) ], else_statements : None }
+    }
+
+    /**
+     * Starts an `elsif` branch, must be called before `add_else`.
+     */
+    pub fn add_elsif( & mut self, condition : & str ) {
+        self.branches.push( ( condition.to_string(), Vec::new() ) );
+    }
+
+    pub fn add_else( & mut self ) {
+        self.else_statements = Some( Vec::new() );
+    }
+
+    pub fn add_statement< S : SequentialStatement + 'static >( & mut self, statement : S ) {
+        let statements = match & mut self.else_statements {
+            Some( statements ) => statements,
+            None => & mut self.branches.last_mut().unwrap().1,
+        };
+        statements.push( Box::new( statement ) );
+    }
+
+    fn get_statements( & self ) -> impl Iterator< Item = & Box< dyn SequentialStatement > > {
+        self.branches.iter().flat_map( | ( _, s ) | s.iter() )
+                .chain( self.else_statements.iter().flat_map( | s | s.iter() ) )
+    }
+}
+
+impl Element for IfStatement {
+    fn to_source_code( & self, indent : usize ) -> String {
+        let mut source = String::new();
+        let indent_str = crate::util::indent( indent );
+        for ( idx, ( condition, statements ) ) in self.branches.iter().enumerate() {
+            let keyword = if idx == 0 { IF } else { ELSIF };
+            source.push_str( & format!( "{}{} {} {}\n", indent_str, keyword, condition, THEN ) );
+            for statement in statements {
+                source.push_str( & statement.to_source_code( indent + 1 ) );
+            }
+        }
+        if let Some( statements ) = & self.else_statements {
+            source.push_str( & format!( "{}{}\n", indent_str, ELSE ) );
+            for statement in statements {
+                source.push_str( & statement.to_source_code( indent + 1 ) );
+            }
+        }
+        source.push_str( & format!( "{}{} {};\n", indent_str, END, IF ) );
+        source
+    }
+}
+
+impl SequentialStatement for IfStatement {
+    fn get_driven_signals( & self ) -> Vec< String > {
+        self.get_statements().flat_map( | s | s.get_driven_signals() ).collect()
+    }
+
+    fn get_read_signals( & self ) -> Vec< String > {
+        let mut signals : Vec< String > = self.branches.iter()
+                .flat_map( | ( condition, _ ) | get_identifiers( condition ) ).collect();
+        signals.extend( self.get_statements().flat_map( | s | s.get_read_signals() ) );
+        signals
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vhdl::signal_assignment::SignalAssignment;
+
+    #[test]
+    fn if_elsif_else() {
+        let mut statement = IfStatement::new( "a = '1'" );
+        statement.add_statement( SignalAssignment::new( "x", "b" ) );
+        statement.add_elsif( "c = '1'" );
+        statement.add_else();
+        statement.add_statement( SignalAssignment::new( "y", "'0'" ) );
+        assert_eq!( statement.to_source_code( 0 ), concat!(
+                "if a = '1' then\n",
+                "     x <= b;\n",
+                "elsif c = '1' then\n",
+                "else\n",
+                "     y <= '0';\n",
+                "end if;\n" ) );
+        assert_eq!( statement.get_driven_signals(), vec![ "x", "y" ] );
+        assert_eq!( statement.get_read_signals(), vec![ "a", "c", "b" ] );
+    }
+}
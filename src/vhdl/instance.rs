@@ -1,4 +1,6 @@
+use std::collections::HashMap;
 use crate::element::Element;
+use crate::vhdl::actual::Actual;
 use crate::vhdl::vhdl_error::VhdlError;
 use crate::element::to_source_code_list;
 use crate::vhdl::concurrent_statement::ConcurrentStatement;
@@ -9,6 +11,9 @@ use crate::vhdl::entity_interface_binding::EntityInterfaceBinding;
 use crate::vhdl::generic_binding::GenericBinding;
 use crate::vhdl::keywords::*;
 use crate::vhdl::match_index::*;
+use crate::vhdl::port_binding::PortBinding;
+use crate::vhdl::matcher::Matcher;
+use crate::vhdl::connection_report::*;
 use crate::vhdl::signal_declaration::SignalDeclaraion;
 
 #[derive(Clone)]
@@ -27,23 +32,47 @@ impl Instance {
                 bindings : EntityInterfaceBindingList::from_entity( entity ) }
     }
 
-    pub fn connect_to_entity( & mut self, entity : & Entity ) {
+    /**
+     * Pairs every unbound instance interface with the best matching free interface of the
+     * entity with the same directions without changing the instance.
+     */
+    pub fn get_entity_connection_report( & self, entity : & Entity, matcher : & dyn Matcher )
+            -> ConnectionReport {
+        let mut report = ConnectionReport::new();
         let entity_interfaces = entity.get_interfaces();
         let mut unbound_entity_interfaces = vec![ true; entity_interfaces.len() ];
-        for instance_interface in self.bindings.get_interfaces_mut() {
+        for ( idx, instance_interface ) in self.bindings.get_interfaces().iter().enumerate() {
+            if instance_interface.is_bound() || instance_interface.is_empty() {
+                continue;
+            }
             let mut match_index = MatchIndex::new();
             for ( entity_idx, entity_interface ) in entity_interfaces.iter().enumerate() {
-                if ! unbound_entity_interfaces[ entity_idx ] {
+                // interfaces that can not be paired are left to fail when connected
+                if ! unbound_entity_interfaces[ entity_idx ] ||
+                        ! instance_interface.check_same_directions( entity_interface ).unwrap_or( true ) {
                     continue;
                 }
-                match_index.update( entity_idx, Instance::get_match_strength(
-                        instance_interface, entity_interface ) );
+                match_index.update( entity_idx,
+                        instance_interface.get_entity_matching( entity_interface, matcher ) );
+            }
+            let source = get_member_path( & self.name, instance_interface.get_name() );
+            if ! match_index.is_match() {
+                report.add_unmatched( & source );
+                continue;
             }
-            if match_index.is_match() {
-                instance_interface.connect_to_entity_interface( & entity_interfaces[ match_index.position() ] );
-                unbound_entity_interfaces[ match_index.position() ] = false;
+            if match_index.is_tie() {
+                let candidates = match_index.candidates().iter()
+                        .map( | idx | get_member_path( "", entity_interfaces[ * idx ].get_name() ) )
+                        .collect();
+                report.add_tie( ConnectionTie::new( & source, candidates, match_index.strength() ) );
             }
+            let mut pair = ConnectionPair::new( & self.name, instance_interface.get_name(), "",
+                    entity_interfaces[ match_index.position() ].get_name(), match_index.strength() );
+            pair.set_indices( idx, match_index.position() );
+            report.add_pair( pair );
+            unbound_entity_interfaces[ match_index.position() ] = false;
         }
+        report
     }
 
     pub fn connect_interface_by_name_to_signal_list( & mut self, name : & str, signal_list : & Vec< SignalDeclaraion > ) {
@@ -55,18 +84,83 @@ impl Instance {
         self.bindings.get_interfaces_mut()[ index ].connect_to_signal_list( signal_list );
     }
 
+    /**
+     * Binds an interface by index to an entity interface of the same class, see
+     * `EntityInterfaceBinding::connect_to_entity_interface`.
+     */
+    pub fn connect_interface_by_index_to_entity_interface( & mut self, index : usize,
+            entity_interface : & EntityInterface ) -> Result< (), VhdlError > {
+        self.get_interface_by_index_mut( index )?.connect_to_entity_interface( entity_interface )
+    }
+
+    pub fn connect_interface_by_index_to_names( & mut self, index : usize, names : & Vec< String > )
+            -> Result< (), VhdlError > {
+        self.get_interface_by_index_mut( index )?.connect_to_names( names )
+    }
+
+    pub fn connect_interface_by_index_to_promoted_interface( & mut self, index : usize,
+            interface : & EntityInterface ) {
+        self.bindings.get_interfaces_mut()[ index ].connect_to_promoted_interface( interface );
+    }
+
+    /**
+     * Ties off every unbound port.  Nothing is changed if any input can not be resolved.
+     */
+    pub fn tie_off_unbound_ports( & mut self ) -> Result< (), VhdlError > {
+        self.requires_tie_offs()?;
+        for interface in self.bindings.get_interfaces_mut() {
+            interface.tie_off_unbound_ports()?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Checks that every unbound port can be tied off without changing the instance.
+     */
+    pub fn requires_tie_offs( & self ) -> Result< (), VhdlError > {
+        for interface in self.bindings.get_interfaces() {
+            interface.requires_tie_offs()?;
+        }
+        Ok(())
+    }
+
     pub fn connect_generic( & mut self, inner : & str, outer : & str ) -> Result< (), VhdlError > {
         let binding = self.bindings.get_generic_mut( inner )?;
         binding.connect_by_name( outer );
         Ok(())
     }
 
+    /**
+     * Overrides a generic with a literal value, e.g. `DATA_WIDTH => 32`.
+     */
+    pub fn set_generic_value( & mut self, inner : & str, value : & str ) -> Result< (), VhdlError > {
+        let binding = self.bindings.get_generic_mut( inner )?;
+        binding.connect_to_literal( value );
+        Ok(())
+    }
+
     pub fn connect_to_port( & mut self, inner : & str, outer : & str ) -> Result< (), VhdlError > {
         let binding = self.bindings.get_port_mut( inner )?;
         binding.connect_by_name( outer );
         Ok(())
     }
 
+    pub fn connect_port_to_actual( & mut self, inner : & str, actual : & Actual )
+            -> Result< (), VhdlError > {
+        let binding = self.bindings.get_port_mut( inner )?;
+        binding.connect_to_actual( actual )
+    }
+
+    pub fn connect_port_open( & mut self, inner : & str ) -> Result< (), VhdlError > {
+        self.connect_port_to_actual( inner, & Actual::Open )
+    }
+
+    pub fn connect_port_partial( & mut self, inner : & str, range : & str, actual : & Actual )
+            -> Result< (), VhdlError > {
+        let binding = self.bindings.get_port_mut( inner )?;
+        binding.connect_partial( range, actual )
+    }
+
     pub fn get_name( & self ) -> & String {
         & self.name
     }
@@ -79,18 +173,58 @@ impl Instance {
         self.bindings.get_interface_by_name( name )
     }
 
-    pub fn get_instance_interface_matches( & self, inst_b : & Instance ) -> Vec< ( usize, usize ) > {
-        let mut matches = Vec::new();
+    pub fn get_interface_by_index_mut( & mut self, index : usize )
+            -> Result< & mut EntityInterfaceBinding, VhdlError > {
+        let name = self.name.clone();
+        match self.bindings.get_interfaces_mut().get_mut( index ) {
+            Some( interface ) => Ok( interface ),
+            None => Err( VhdlError::new( & format!(
+                    "error: Instance {:?} has no interface with index {}!", name, index ) ) ),
+        }
+    }
+
+    pub fn get_interface_by_name_mut( & mut self, name : & str ) -> Option< & mut EntityInterfaceBinding > {
+        self.bindings.get_interface_by_name_mut( name )
+    }
+
+    /**
+     * Pairs every unbound interface with the best matching free complementary interface of
+     * `inst_b`.
+     */
+    pub fn get_instance_connection_report( & self, inst_b : & Instance, matcher : & dyn Matcher )
+            -> ConnectionReport {
+        let mut report = ConnectionReport::new();
+        let interfaces_b = inst_b.get_interfaces();
+        let mut unbound_b : Vec< bool > = interfaces_b.iter().map( | i | i.is_unbound() ).collect();
         for ( idx_a, interface_a ) in self.get_interfaces().iter().enumerate() {
+            if interface_a.is_bound() || interface_a.is_empty() {
+                continue;
+            }
             let mut match_index = MatchIndex::new();
-            for ( idx_b, interface_b ) in inst_b.get_interfaces().iter().enumerate() {
-                match_index.update( idx_b, interface_a.get_instance_matching( interface_b ) );
+            for ( idx_b, interface_b ) in interfaces_b.iter().enumerate() {
+                // interfaces that can not be paired are left to fail when connected
+                if unbound_b[ idx_b ] && interface_a.check_complementary( interface_b ).unwrap_or( true ) {
+                    match_index.update( idx_b, interface_a.get_instance_matching( interface_b, matcher ) );
+                }
+            }
+            let source = get_member_path( & self.name, interface_a.get_name() );
+            if ! match_index.is_match() {
+                report.add_unmatched( & source );
+                continue;
             }
-            if match_index.is_match() {
-                matches.push( ( idx_a, match_index.position() ) );
+            if match_index.is_tie() {
+                let candidates = match_index.candidates().iter()
+                        .map( | idx | get_member_path( inst_b.get_name(), interfaces_b[ * idx ].get_name() ) )
+                        .collect();
+                report.add_tie( ConnectionTie::new( & source, candidates, match_index.strength() ) );
             }
+            let mut pair = ConnectionPair::new( & self.name, interface_a.get_name(), inst_b.get_name(),
+                    interfaces_b[ match_index.position() ].get_name(), match_index.strength() );
+            pair.set_indices( idx_a, match_index.position() );
+            report.add_pair( pair );
+            unbound_b[ match_index.position() ] = false;
         }
-        return matches;
+        report
     }
 
     pub fn get_unbound_generics( & self ) -> Vec< GenericBinding > {
@@ -103,6 +237,21 @@ impl Instance {
         return generics;
     }
 
+    /**
+     * Maps the names of all bound generics to their actual values.
+     */
+    pub fn get_generic_map( & self ) -> HashMap< String, String > {
+        let mut map = HashMap::new();
+        for interface in self.bindings.get_interfaces() {
+            for generic in interface.get_generics() {
+                if generic.is_bound() {
+                    map.insert( generic.get_inner().clone(), generic.get_outer().clone() );
+                }
+            }
+        }
+        map
+    }
+
     pub fn get_port_data_type_by_name( & self, name : & str ) -> Option< & String > {
         for interface in self.bindings.get_interfaces() {
             for port in interface.get_ports() {
@@ -129,38 +278,29 @@ impl Instance {
     fn get_port_bindings( & self ) -> Vec< Box::< dyn Element > > {
         let mut bindings : Vec< Box< dyn Element > > = Vec::new();
         for interface in self.bindings.get_interfaces() {
+            let records : Vec< & PortBinding > = interface.get_records().iter().filter( | r | r.is_bound() ).collect();
+            for record in & records {
+                for association in record.get_associations() {
+                    bindings.push( Box::new( association ) );
+                }
+            }
             for port in interface.get_ports() {
-                bindings.push( Box::new( ( * port ).clone() ) );
+                if records.iter().any( | r | port.get_inner().starts_with( & format!( "{}.", r.get_inner() ) ) ) {
+                    continue;
+                }
+                if port.is_bound() {
+                    for association in port.get_associations() {
+                        bindings.push( Box::new( association ) );
+                    }
+                }
+                else {
+                    bindings.push( Box::new( port.clone() ) );
+                }
             }
         }
         return bindings;
     }
 
-    fn get_match_strength( instance : & EntityInterfaceBinding, entity : & EntityInterface )
-            -> u32 {
-        if instance.is_bound() {
-            return NONE;
-        }
-        let instance_name = instance.get_name().to_string().to_lowercase();
-        let entity_name = entity.get_name().to_string().to_lowercase();
-        let class_match : bool = instance.get_class() == entity.get_class();
-        let name_match : bool = instance_name == entity_name;
-        let instance_in_entity = entity_name.contains( & instance_name );
-        let entity_in_instance = instance_name.contains( & entity_name );
-        if class_match && name_match {
-            return FULL;
-        }
-        else if instance_in_entity || entity_in_instance {
-            return PARTIAL;
-        }
-        else if class_match {
-            return CLASS;
-        }
-        else {
-            return NONE;
-        }
-    }
-
     pub fn contains_interface( & self, name : & str ) -> bool {
         self.bindings.contains_interface( name )
     }
@@ -205,5 +345,63 @@ impl ConcurrentStatement for Instance {
 //------------------------------------------------------------------------------
 #[cfg(test)]
 mod tests {
-}
+    use super::*;
+    use crate::vhdl::direction::Direction;
+    use crate::vhdl::generic::Generic;
+    use crate::vhdl::interface_package::VhdlStandard;
+    use crate::vhdl::port::Port;
+
+    const INSTANCE : & str = concat!( "u0 : entity work.sub\n",
+        "    generic map (\n",
+        "        DATA_WIDTH => 32\n",
+        "    )\n",
+        "    port map (\n",
+        "        a => '0',\n",
+        "        b( 3 downto 0 ) => x( 7 downto 4 ),\n",
+        "        b( 7 downto 4 ) => to_stdlogicvector( y ),\n",
+        "        c => open\n",
+        "    );\n" );
+
+    /**
+     * Bind ports to a literal, slices, a conversion function and open and override a generic.
+     */
+    #[test]
+    fn literal_open_and_partial_bindings() -> Result< (), VhdlError > {
+        let mut sub = Entity::new( "sub" );
+        sub.add_generic( Generic::new( "DATA_WIDTH", "positive" ) );
+        sub.add_port( Port::new( "a", Direction::IN, "std_logic" ) );
+        sub.add_port( Port::new( "b", Direction::IN, "std_logic_vector( 7 downto 0 )" ) );
+        sub.add_port( Port::new( "c", Direction::OUT, "std_logic" ) );
+        let mut instance = Instance::from_entity( "u0", & sub );
+        instance.set_generic_value( "DATA_WIDTH", "32" )?;
+        instance.connect_port_to_actual( "a", & Actual::literal( "'0'" ) )?;
+        instance.connect_port_partial( "b", "3 downto 0", & Actual::indexed( "x", "7 downto 4" ) )?;
+        instance.connect_port_partial( "b", "7 downto 4",
+                & Actual::function_call( "to_stdlogicvector", "y" ) )?;
+        instance.connect_port_open( "c" )?;
+        assert_eq!( instance.to_source_code( 0 ), INSTANCE );
+        Ok(())
+    }
 
+    /**
+     * Bind record ports as a whole to an entity interface and as elements to signals.
+     */
+    #[test]
+    fn record_bindings() -> Result< (), VhdlError > {
+        let mut interface = EntityInterface::new( "s", "stream" );
+        interface.add_port( Port::new( "data", Direction::OUT, "std_logic_vector( 7 downto 0 )" ) );
+        interface.add_port( Port::new( "ready", Direction::IN, "std_logic" ) );
+        interface.set_views( "source", "sink" );
+        interface.rename( "s" );
+        interface.set_record_standard( Some( VhdlStandard::VHDL2008 ) )?;
+        let sub = Entity::with_interface( "sub", & interface );
+        let mut instance = Instance::from_entity( "u0", & sub );
+        instance.connect_interface_by_index_to_entity_interface( 1, & interface )?;
+        assert!( instance.to_source_code( 0 ).contains( "        s_in => s_in,\n        s_out => s_out\n" ) );
+        let mut instance = Instance::from_entity( "u1", & sub );
+        instance.connect_to_port( "s_out.data", "data" )?;
+        instance.connect_port_to_actual( "s_in.ready", & Actual::literal( "'1'" ) )?;
+        assert!( instance.to_source_code( 0 ).contains( "        s_out.data => data,\n        s_in.ready => '1'\n" ) );
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+pub mod address_map;
+pub mod avalon_mm;
+pub mod axi4_lite;
+pub mod apb;
+pub mod avalon_mm_to_axi4_lite;
+
+use crate::vhdl::architecture::Architecture;
+use crate::vhdl::constant_declaration::ConstantDeclaration;
+use crate::vhdl::entity_interface::EntityInterface;
+use crate::vhdl::interconnect::address_map::AddressMap;
+use crate::vhdl::interface_catalog::get_interface;
+
+pub const CLK_RST_CLASS : &str = "clk_rst";
+
+/**
+ * The unnamed clock and synchronous, active high reset inputs of the clocked generators.
+ */
+pub fn new_clk_rst_interface() -> EntityInterface {
+    get_interface( CLK_RST_CLASS ).unwrap()
+}
+
+/**
+ * Returns a conditional signal assignment expression choosing the first value whose condition
+ * holds, e.g. `a when sel_a = '1' else\n        b when sel_b = '1' else\n        '0'`.
+ */
+pub fn get_conditional_expression( choices : & [ ( String, String ) ], default : & str ) -> String {
+    let mut lines : Vec< String > = choices.iter()
+            .map( | ( value, condition ) | format!( "{} when {}", value, condition ) ).collect();
+    lines.push( default.to_string() );
+    lines.join( " else\n        " )
+}
+
+/**
+ * Returns the expression selecting the first requesting of `requests` over all others, e.g.
+ * `b_request and not ( a_request )` for the second of two.
+ */
+pub fn get_priority_expression( requests : & [ String ], index : usize ) -> String {
+    if index == 0 {
+        requests[ 0 ].clone()
+    }
+    else {
+        format!( "{} and not ( {} )", requests[ index ], requests[ ..index ].join( " or " ) )
+    }
+}
+
+fn get_constant_prefix( agent : & str ) -> String {
+    agent.to_uppercase()
+}
+
+/**
+ * Declares the constants `<AGENT>_BASE` and `<AGENT>_LAST` for every range of `address_map` as
+ * `unsigned` hex literals wide enough for the highest address of the map, so addresses are not
+ * limited to the range of `natural`.
+ */
+pub fn add_address_constants( architecture : & mut Architecture, address_map : & AddressMap ) {
+    let highest = address_map.get_ranges().iter().map( | r | r.get_last() ).max().unwrap_or( 0 );
+    let digits = ( ( 64 - highest.leading_zeros() as usize ).div_ceil( 4 ) ).max( 1 );
+    let data_type = format!( "unsigned( {} downto 0 )", digits * 4 - 1 );
+    for range in address_map.get_ranges() {
+        let prefix = get_constant_prefix( range.get_name() );
+        architecture.add_constant_declaration( & ConstantDeclaration::new( & format!( "{}_BASE", prefix ),
+                & data_type, & format!( "x\"{:0width$X}\"", range.get_base(), width = digits ) ) );
+        architecture.add_constant_declaration( & ConstantDeclaration::new( & format!( "{}_LAST", prefix ),
+                & data_type, & format!( "x\"{:0width$X}\"", range.get_last(), width = digits ) ) );
+    }
+}
+
+/**
+ * Returns the condition that `address` is within the range of `agent`.
+ */
+pub fn get_address_hit( address : & str, agent : & str ) -> String {
+    let prefix = get_constant_prefix( agent );
+    format!( "unsigned( {} ) >= {}_BASE and unsigned( {} ) <= {}_LAST", address, prefix, address, prefix )
+}
+
+/**
+ * Returns `address` relative to the base of `agent`, resized to the width of `address`.
+ */
+pub fn get_relative_address( address : & str, agent : & str ) -> String {
+    format!( "std_logic_vector( resize( unsigned( {} ) - {}_BASE, {}'length ) )",
+            address, get_constant_prefix( agent ), address )
+}
@@ -0,0 +1,112 @@
+use crate::vhdl::vhdl_error::VhdlError;
+
+/**
+ * Address window of one agent, `base` up to `base + size - 1`.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddressRange {
+    name : String,
+    base : u64,
+    size : u64,
+}
+
+impl AddressRange {
+    pub fn get_name( & self ) -> & String {
+        & self.name
+    }
+
+    pub fn get_base( & self ) -> u64 {
+        self.base
+    }
+
+    pub fn get_size( & self ) -> u64 {
+        self.size
+    }
+
+    pub fn get_last( & self ) -> u64 {
+        self.base + self.size - 1
+    }
+
+    pub fn contains( & self, address : u64 ) -> bool {
+        address >= self.base && address <= self.get_last()
+    }
+
+    fn overlaps( & self, other : & AddressRange ) -> bool {
+        self.base <= other.get_last() && other.base <= self.get_last()
+    }
+}
+
+/**
+ * Non-overlapping address ranges of the agents of an interconnect in declaration order.
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AddressMap {
+    ranges : Vec< AddressRange >,
+}
+
+impl AddressMap {
+    pub fn new() -> AddressMap {
+        AddressMap { ranges : Vec::new() }
+    }
+
+    pub fn add( & mut self, name : & str, base : u64, size : u64 ) -> Result< (), VhdlError > {
+        if size == 0 {
+            return Err( VhdlError::new( & format!( "error: Address range {:?} is empty!", name ) ) );
+        }
+        let range = AddressRange { name : name.to_string(), base, size };
+        if base.checked_add( size - 1 ).is_none() {
+            return Err( VhdlError::new( & format!(
+                    "error: Address range {:?} exceeds the highest address 0x{:x}!", name, u64::MAX ) ) );
+        }
+        if self.ranges.iter().any( | r | r.name == name ) {
+            return Err( VhdlError::new( & format!( "error: Address range {:?} already exists!", name ) ) );
+        }
+        if let Some( other ) = self.ranges.iter().find( | r | r.overlaps( & range ) ) {
+            return Err( VhdlError::new( & format!(
+                    "error: Address range {:?} (0x{:x}..0x{:x}) overlaps {:?} (0x{:x}..0x{:x})!",
+                    name, base, range.get_last(), other.name, other.base, other.get_last() ) ) );
+        }
+        self.ranges.push( range );
+        Ok(())
+    }
+
+    pub fn get_ranges( & self ) -> & Vec< AddressRange > {
+        & self.ranges
+    }
+
+    pub fn get_range( & self, address : u64 ) -> Option< & AddressRange > {
+        self.ranges.iter().find( | r | r.contains( address ) )
+    }
+
+    pub fn is_empty( & self ) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_find() -> Result< (), VhdlError > {
+        let mut map = AddressMap::new();
+        map.add( "ram", 0x0000, 0x1000 )?;
+        map.add( "uart", 0x1000, 0x10 )?;
+        assert_eq!( map.get_range( 0x0fff ).unwrap().get_name(), "ram" );
+        assert_eq!( map.get_range( 0x100f ).unwrap().get_name(), "uart" );
+        assert!( map.get_range( 0x1010 ).is_none() );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_ranges() {
+        let mut map = AddressMap::new();
+        map.add( "ram", 0x0000, 0x1000 ).unwrap();
+        assert!( map.add( "rom", 0x0800, 0x1000 ).is_err() );
+        assert!( map.add( "ram", 0x2000, 0x1000 ).is_err() );
+        assert!( map.add( "empty", 0x3000, 0 ).is_err() );
+        assert!( map.add( "high", u64::MAX, 2 ).is_err() );
+        assert!( map.add( "upper", 0x8000_0000, 0x8000_0000 ).is_ok() );
+    }
+}
@@ -0,0 +1,184 @@
+use crate::vhdl::architecture::Architecture;
+use crate::vhdl::constant_declaration::ConstantDeclaration;
+use crate::vhdl::entity::Entity;
+use crate::vhdl::entity_interface::EntityInterface;
+use crate::vhdl::if_statement::IfStatement;
+use crate::vhdl::interconnect::new_clk_rst_interface;
+use crate::vhdl::interface_catalog::get_interface;
+use crate::vhdl::interconnect::axi4_lite::new_axi4_lite_interface;
+use crate::vhdl::process::Process;
+use crate::vhdl::signal_assignment::SignalAssignment;
+use crate::vhdl::signal_declaration::SignalDeclaraion;
+use crate::vhdl::vhdl_error::VhdlError;
+
+pub const APB_CLASS : &str = "apb";
+
+/**
+ * Returns the unnamed APB4 interface in requester view with the generics `ADDR_WIDTH` and
+ * `DATA_WIDTH`.
+ */
+pub fn new_apb_interface() -> EntityInterface {
+    get_interface( APB_CLASS ).unwrap()
+}
+
+const AXI : &str = "axi";
+const APB : &str = "apb";
+const STATE_TYPE : &str = "std_logic_vector( 1 downto 0 )";
+
+/**
+ * Generates a bridge from an AXI4-Lite agent interface `axi` to an APB requester interface
+ * `apb`.  Each AXI4-Lite transaction becomes one APB transfer with setup and access phase,
+ * writes take precedence over reads.  `pslverr` is answered with a slave error response.
+ */
+pub struct Axi4LiteToApbBridge {
+    name : String,
+}
+
+impl Axi4LiteToApbBridge {
+    pub fn new( name : & str ) -> Axi4LiteToApbBridge {
+        Axi4LiteToApbBridge { name : name.to_string() }
+    }
+
+    pub fn get_entity( & self ) -> Result< Entity, VhdlError > {
+        let mut entity = Entity::new( & self.name );
+        entity.add_interface( & new_clk_rst_interface() )?;
+        let mut axi = new_axi4_lite_interface();
+        for generic in axi.get_generics() {
+            entity.add_generic( generic.clone() );
+        }
+        axi.remove_generics();
+        entity.add_interface( & axi.clone_inverted_with_rename( AXI ) )?;
+        let mut apb = new_apb_interface();
+        apb.remove_generics();
+        apb.rename( APB );
+        entity.add_interface( & apb )?;
+        Ok( entity )
+    }
+
+    pub fn get_architecture( & self ) -> Result< Architecture, VhdlError > {
+        let entity = self.get_entity()?;
+        let mut architecture = Architecture::new( "rtl", & entity );
+        architecture.add_constant_declaration( & ConstantDeclaration::new( "STATE_IDLE", STATE_TYPE, "\"00\"" ) );
+        architecture.add_constant_declaration( & ConstantDeclaration::new( "STATE_SETUP", STATE_TYPE, "\"01\"" ) );
+        architecture.add_constant_declaration( & ConstantDeclaration::new( "STATE_ACCESS", STATE_TYPE, "\"10\"" ) );
+        for ( name, data_type ) in [ ( "state", STATE_TYPE ),
+                ( "transfer_address", "std_logic_vector( ADDR_WIDTH - 1 downto 0 )" ),
+                ( "transfer_prot", "std_logic_vector( 2 downto 0 )" ),
+                ( "transfer_write", "std_logic" ),
+                ( "transfer_data", "std_logic_vector( DATA_WIDTH - 1 downto 0 )" ),
+                ( "transfer_strobe", "std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 )" ),
+                ( "write_request", "std_logic" ),
+                ( "read_request", "std_logic" ),
+                ( "write_response_valid", "std_logic" ),
+                ( "write_response", "std_logic_vector( 1 downto 0 )" ),
+                ( "read_response_valid", "std_logic" ),
+                ( "read_response", "std_logic_vector( 1 downto 0 )" ),
+                ( "read_data", "std_logic_vector( DATA_WIDTH - 1 downto 0 )" ) ] {
+            architecture.add_signal_declaration( & SignalDeclaraion::new( name, data_type ) );
+        }
+        for ( signal, expression ) in [
+                ( "write_request", "axi_awvalid and axi_wvalid and not write_response_valid" ),
+                ( "read_request", "axi_arvalid and not read_response_valid and not write_request" ),
+                ( "axi_awready", "write_request when state = STATE_IDLE else '0'" ),
+                ( "axi_wready", "write_request when state = STATE_IDLE else '0'" ),
+                ( "axi_bresp", "write_response" ),
+                ( "axi_bvalid", "write_response_valid" ),
+                ( "axi_arready", "read_request when state = STATE_IDLE else '0'" ),
+                ( "axi_rdata", "read_data" ),
+                ( "axi_rresp", "read_response" ),
+                ( "axi_rvalid", "read_response_valid" ),
+                ( "apb_paddr", "transfer_address" ),
+                ( "apb_pprot", "transfer_prot" ),
+                ( "apb_psel", "'0' when state = STATE_IDLE else '1'" ),
+                ( "apb_penable", "'1' when state = STATE_ACCESS else '0'" ),
+                ( "apb_pwrite", "transfer_write" ),
+                ( "apb_pwdata", "transfer_data" ),
+                ( "apb_pstrb", "transfer_strobe" ) ] {
+            architecture.add_signal_assignment( SignalAssignment::new( signal, expression ) );
+        }
+        architecture.add_process( get_bridge_process() );
+        Ok( architecture )
+    }
+}
+
+fn get_bridge_process() -> Process {
+    let mut start = IfStatement::new( "write_request = '1'" );
+    start.add_statement( SignalAssignment::new( "state", "STATE_SETUP" ) );
+    start.add_statement( SignalAssignment::new( "transfer_address", "axi_awaddr" ) );
+    start.add_statement( SignalAssignment::new( "transfer_prot", "axi_awprot" ) );
+    start.add_statement( SignalAssignment::new( "transfer_write", "'1'" ) );
+    start.add_statement( SignalAssignment::new( "transfer_data", "axi_wdata" ) );
+    start.add_statement( SignalAssignment::new( "transfer_strobe", "axi_wstrb" ) );
+    start.add_elsif( "read_request = '1'" );
+    start.add_statement( SignalAssignment::new( "state", "STATE_SETUP" ) );
+    start.add_statement( SignalAssignment::new( "transfer_address", "axi_araddr" ) );
+    start.add_statement( SignalAssignment::new( "transfer_prot", "axi_arprot" ) );
+    start.add_statement( SignalAssignment::new( "transfer_write", "'0'" ) );
+    start.add_statement( SignalAssignment::new( "transfer_strobe", "(others => '0')" ) );
+
+    let mut respond = IfStatement::new( "transfer_write = '1'" );
+    respond.add_statement( SignalAssignment::new( "write_response_valid", "'1'" ) );
+    respond.add_statement( SignalAssignment::new( "write_response", "apb_pslverr & '0'" ) );
+    respond.add_else();
+    respond.add_statement( SignalAssignment::new( "read_response_valid", "'1'" ) );
+    respond.add_statement( SignalAssignment::new( "read_response", "apb_pslverr & '0'" ) );
+    respond.add_statement( SignalAssignment::new( "read_data", "apb_prdata" ) );
+
+    let mut state = IfStatement::new( "state = STATE_IDLE" );
+    state.add_statement( start );
+    state.add_elsif( "state = STATE_SETUP" );
+    state.add_statement( SignalAssignment::new( "state", "STATE_ACCESS" ) );
+    state.add_elsif( "apb_pready = '1'" );
+    state.add_statement( SignalAssignment::new( "state", "STATE_IDLE" ) );
+    state.add_statement( respond );
+
+    let mut write_response = IfStatement::new( "axi_bready = '1'" );
+    write_response.add_statement( SignalAssignment::new( "write_response_valid", "'0'" ) );
+    let mut read_response = IfStatement::new( "axi_rready = '1'" );
+    read_response.add_statement( SignalAssignment::new( "read_response_valid", "'0'" ) );
+
+    let mut reset = IfStatement::new( "rst = '1'" );
+    reset.add_statement( SignalAssignment::new( "state", "STATE_IDLE" ) );
+    reset.add_statement( SignalAssignment::new( "write_response_valid", "'0'" ) );
+    reset.add_statement( SignalAssignment::new( "read_response_valid", "'0'" ) );
+    reset.add_else();
+    reset.add_statement( write_response );
+    reset.add_statement( read_response );
+    reset.add_statement( state );
+
+    let mut clocked = IfStatement::new( "rising_edge( clk )" );
+    clocked.add_statement( reset );
+    let mut process = Process::new( "bridge" );
+    process.add_sensitivity( "clk" );
+    process.add_statement( clocked );
+    process
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::Element;
+    use crate::vhdl::lint::lint;
+
+    #[test]
+    fn interface() {
+        let interface = new_apb_interface();
+        assert_eq!( interface.get_class(), APB_CLASS );
+        assert_eq!( interface.get_ports().len(), 10 );
+    }
+
+    #[test]
+    fn axi4_lite_to_apb() -> Result< (), VhdlError > {
+        let architecture = Axi4LiteToApbBridge::new( "bridge" ).get_architecture()?;
+        assert!( lint( & architecture ).is_empty() );
+        let source = architecture.to_source_code( 0 );
+        assert!( source.contains( "        axi_awvalid : in std_logic;\n" ) );
+        assert!( source.contains( "        apb_psel : out std_logic;\n" ) );
+        assert!( source.contains( "     apb_penable <= '1' when state = STATE_ACCESS else '0';\n" ) );
+        assert!( source.contains( concat!( "                elsif apb_pready = '1' then\n",
+                "                     state <= STATE_IDLE;\n",
+                "                    if transfer_write = '1' then\n" ) ) );
+        Ok(())
+    }
+}
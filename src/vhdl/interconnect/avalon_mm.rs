@@ -0,0 +1,404 @@
+use crate::vhdl::architecture::Architecture;
+use crate::vhdl::data_type::get_zero_value;
+use crate::vhdl::direction::Direction;
+use crate::vhdl::entity::Entity;
+use crate::vhdl::entity_interface::EntityInterface;
+use crate::vhdl::if_statement::IfStatement;
+use crate::vhdl::interconnect::{ new_clk_rst_interface, get_conditional_expression, get_priority_expression,
+        get_address_hit, get_relative_address, add_address_constants };
+use crate::vhdl::interconnect::address_map::AddressMap;
+use crate::vhdl::port::Port;
+use crate::vhdl::process::Process;
+use crate::vhdl::signal_assignment::SignalAssignment;
+use crate::vhdl::signal_declaration::SignalDeclaraion;
+use crate::vhdl::vhdl_error::VhdlError;
+
+pub const AVALON_MM_CLASS : &str = "avalon_mmi";
+
+const ADDRESS : &str = "address";
+const BYTEENABLE : &str = "byteenable";
+const READ : &str = "read";
+const WRITE : &str = "write";
+const WRITE_DATA : &str = "write_data";
+const READ_DATA : &str = "read_data";
+const RESPONSE : &str = "response";
+const WAITREQUEST : &str = "waitrequest";
+
+/**
+ * Returns the member name of an Avalon-MM port in lower case, `writedata` and `readdata` are
+ * returned as `write_data` and `read_data`.
+ */
+pub fn get_member( port : & Port ) -> String {
+    let member = port.get_base_name().to_lowercase();
+    match member.as_str() {
+        "writedata" => WRITE_DATA.to_string(),
+        "readdata" => READ_DATA.to_string(),
+        _ => member,
+    }
+}
+
+pub fn find_member< 'a >( interface : & 'a EntityInterface, member : & str ) -> Option< & 'a Port > {
+    interface.get_ports().iter().find( | p | get_member( p ) == member )
+}
+
+/**
+ * Checks that `interface`, given in host or agent view, is a supported Avalon-MM interface and
+ * returns it unnamed in host view.
+ */
+pub fn get_host_view( interface : & EntityInterface ) -> Result< EntityInterface, VhdlError > {
+    if interface.get_class() != AVALON_MM_CLASS {
+        return Err( VhdlError::new( & format!( "error: Interface class {:?} is not {:?}!",
+                interface.get_class(), AVALON_MM_CLASS ) ) );
+    }
+    let mut host_view = interface.clone();
+    host_view.rename( "" );
+    if find_member( & host_view, ADDRESS ).is_some_and( | p | p.get_direction() == Direction::IN ) {
+        host_view.invert();
+    }
+    for member in [ ADDRESS, READ, WRITE, WRITE_DATA, READ_DATA ] {
+        if find_member( & host_view, member ).is_none() {
+            return Err( VhdlError::new( & format!( "error: Interface has no {:?} member!", member ) ) );
+        }
+    }
+    let known = [ ADDRESS, BYTEENABLE, READ, WRITE, WRITE_DATA, READ_DATA, RESPONSE, WAITREQUEST ];
+    if let Some( port ) = host_view.get_ports().iter().find( | p | ! known.contains( & get_member( p ).as_str() ) ) {
+        return Err( VhdlError::new( & format!( "error: Interface member {:?} is not supported!",
+                port.get_base_name() ) ) );
+    }
+    Ok( host_view )
+}
+
+/**
+ * Response of a transfer to an address no agent is mapped to.
+ */
+const DECODE_ERROR : &str = "\"11\"";
+
+/**
+ * Prefix of the internal signals carrying the transfer of the granted host.
+ */
+const BUS : &str = "bus";
+
+/**
+ * Generates an interconnect entity between Avalon-MM hosts and agents.  Transfers of the host
+ * are decoded by the address map, the agent receives the address relative to its base and read
+ * data and response are multiplexed back.  Unmapped addresses return a decode error response.
+ * Several hosts are arbitrated with fixed priority in the order they were added, which requires
+ * a `waitrequest` member to stall the hosts not granted and adds clock and reset to hold the
+ * grant until the transfer completes.
+ */
+pub struct AvalonMmInterconnect {
+    name : String,
+    interface : EntityInterface,
+    hosts : Vec< String >,
+    address_map : AddressMap,
+}
+
+impl AvalonMmInterconnect {
+    /**
+     * Creates an interconnect for interfaces like `interface`, given in host or agent view.
+     */
+    pub fn new( name : & str, interface : & EntityInterface ) -> Result< AvalonMmInterconnect, VhdlError > {
+        Ok( AvalonMmInterconnect { name : name.to_string(), interface : get_host_view( interface )?,
+                hosts : Vec::new(), address_map : AddressMap::new() } )
+    }
+
+    pub fn add_host( & mut self, name : & str ) -> Result< (), VhdlError > {
+        self.requires_free_name( name )?;
+        self.hosts.push( name.to_string() );
+        Ok(())
+    }
+
+    pub fn add_agent( & mut self, name : & str, base : u64, size : u64 ) -> Result< (), VhdlError > {
+        self.requires_free_name( name )?;
+        self.address_map.add( name, base, size )
+    }
+
+    pub fn get_address_map( & self ) -> & AddressMap {
+        & self.address_map
+    }
+
+    /**
+     * The interconnect entity: clock and reset with several hosts, the generics of the
+     * interface, one agent view interface per host and one host view interface per agent, each
+     * named after the host or agent.
+     */
+    pub fn get_entity( & self ) -> Result< Entity, VhdlError > {
+        self.requires_hosts_and_agents()?;
+        let mut entity = Entity::new( & self.name );
+        if self.hosts.len() > 1 {
+            entity.add_interface( & new_clk_rst_interface() )?;
+        }
+        for generic in self.interface.get_generics() {
+            let mut generic = generic.clone();
+            generic.remove_interface();
+            entity.add_generic( generic );
+        }
+        let mut template = self.interface.clone();
+        template.remove_generics();
+        for host in & self.hosts {
+            entity.add_interface( & template.clone_inverted_with_rename( host ) )?;
+        }
+        for range in self.address_map.get_ranges() {
+            let mut agent = template.clone();
+            agent.rename( range.get_name() );
+            entity.add_interface( & agent )?;
+        }
+        entity.add_missing_library_use( "unsigned" );
+        Ok( entity )
+    }
+
+    pub fn get_architecture( & self ) -> Result< Architecture, VhdlError > {
+        let entity = self.get_entity()?;
+        let mut architecture = Architecture::new( "rtl", & entity );
+        for port in self.interface.get_ports() {
+            architecture.add_signal_declaration( & SignalDeclaraion::new(
+                    & self.get_name( BUS, port ), port.get_data_type() ) );
+        }
+        add_address_constants( & mut architecture, & self.address_map );
+        for range in self.address_map.get_ranges() {
+            architecture.add_signal_declaration( & SignalDeclaraion::new(
+                    & format!( "{}_select", range.get_name() ), "std_logic" ) );
+        }
+        self.add_arbitration( & mut architecture );
+        self.add_decoder( & mut architecture );
+        self.add_responses( & mut architecture );
+        Ok( architecture )
+    }
+
+    /**
+     * Drives the internal bus signals from the granted host.
+     */
+    fn add_arbitration( & self, architecture : & mut Architecture ) {
+        let host_outputs : Vec< & Port > = self.interface.get_ports().iter()
+                .filter( | p | p.get_direction() == Direction::OUT ).collect();
+        if self.hosts.len() == 1 {
+            for port in & host_outputs {
+                architecture.add_signal_assignment( SignalAssignment::new(
+                        & self.get_name( BUS, port ), & self.get_name( & self.hosts[ 0 ], port ) ) );
+            }
+            return;
+        }
+        let read = self.get_port( READ );
+        let write = self.get_port( WRITE );
+        for host in & self.hosts {
+            architecture.add_signal_declaration( & SignalDeclaraion::new( & format!( "{}_request", host ), "std_logic" ) );
+            architecture.add_signal_declaration( & SignalDeclaraion::new( & format!( "{}_grant", host ), "std_logic" ) );
+            architecture.add_signal_assignment( SignalAssignment::new( & format!( "{}_request", host ),
+                    & format!( "{} or {}", self.get_name( host, read ), self.get_name( host, write ) ) ) );
+        }
+        for port in & host_outputs {
+            let choices : Vec< ( String, String ) > = self.hosts.iter().map( | h |
+                    ( self.get_name( h, port ), format!( "{}_grant = '1'", h ) ) ).collect();
+            architecture.add_signal_assignment( SignalAssignment::new( & self.get_name( BUS, port ),
+                    & get_conditional_expression( & choices, & get_zero_value( port.get_data_type() ).unwrap_or_default() ) ) );
+        }
+        self.add_arbiter( architecture );
+    }
+
+    /**
+     * Grants the bus to the first requesting host and holds the grant until the transfer
+     * completes, i.e. until the cycle `waitrequest` is low.
+     */
+    fn add_arbiter( & self, architecture : & mut Architecture ) {
+        let requests : Vec< String > = self.hosts.iter().map( | h | format!( "{}_request", h ) ).collect();
+        let grants : Vec< String > = self.hosts.iter().map( | h | format!( "{}_grant", h ) ).collect();
+        let active = "active".to_string();
+        architecture.add_signal_declaration( & SignalDeclaraion::new( & active, "std_logic" ) );
+        let mut cleared = vec![ & active ];
+        cleared.extend( & grants );
+
+        let mut state = IfStatement::new( "rst = '1'" );
+        for signal in & cleared {
+            state.add_statement( SignalAssignment::new( signal, "'0'" ) );
+        }
+        state.add_elsif( & format!( "{} = '0'", active ) );
+        let conditions : Vec< String > = requests.iter().map( | r | format!( "{} = '1'", r ) ).collect();
+        let mut grant = IfStatement::new( & conditions.join( " or " ) );
+        grant.add_statement( SignalAssignment::new( & active, "'1'" ) );
+        for ( idx, signal ) in grants.iter().enumerate() {
+            grant.add_statement( SignalAssignment::new( signal, & get_priority_expression( & requests, idx ) ) );
+        }
+        state.add_statement( grant );
+        state.add_elsif( & format!( "{} = '0'", self.get_name( BUS, self.get_port( WAITREQUEST ) ) ) );
+        for signal in & cleared {
+            state.add_statement( SignalAssignment::new( signal, "'0'" ) );
+        }
+
+        let mut clocked = IfStatement::new( "rising_edge( clk )" );
+        clocked.add_statement( state );
+        let mut process = Process::new( "arbiter" );
+        process.add_sensitivity( "clk" );
+        process.add_statement( clocked );
+        architecture.add_process( process );
+    }
+
+    /**
+     * Selects the agent by address and forwards the transfer to it.
+     */
+    fn add_decoder( & self, architecture : & mut Architecture ) {
+        let bus_address = self.get_name( BUS, self.get_port( ADDRESS ) );
+        for range in self.address_map.get_ranges() {
+            let agent = range.get_name();
+            architecture.add_signal_assignment( SignalAssignment::new( & format!( "{}_select", agent ),
+                    & format!( "'1' when {} else '0'", get_address_hit( & bus_address, agent ) ) ) );
+            for port in self.interface.get_ports() {
+                if port.get_direction() != Direction::OUT {
+                    continue;
+                }
+                let member = get_member( port );
+                let expression = if member == ADDRESS {
+                    get_relative_address( & bus_address, agent )
+                }
+                else if member == READ || member == WRITE {
+                    format!( "{} and {}_select", self.get_name( BUS, port ), agent )
+                }
+                else {
+                    self.get_name( BUS, port )
+                };
+                architecture.add_signal_assignment( SignalAssignment::new( & self.get_name( agent, port ),
+                        & expression ) );
+            }
+        }
+    }
+
+    /**
+     * Multiplexes read data, response and wait request of the selected agent back to the hosts.
+     */
+    fn add_responses( & self, architecture : & mut Architecture ) {
+        for port in self.interface.get_ports() {
+            if port.get_direction() != Direction::IN {
+                continue;
+            }
+            let member = get_member( port );
+            let default = if member == RESPONSE {
+                DECODE_ERROR.to_string()
+            }
+            else {
+                get_zero_value( port.get_data_type() ).unwrap_or_default()
+            };
+            let choices : Vec< ( String, String ) > = self.address_map.get_ranges().iter().map( | r |
+                    ( self.get_name( r.get_name(), port ), format!( "{}_select = '1'", r.get_name() ) ) ).collect();
+            architecture.add_signal_assignment( SignalAssignment::new( & self.get_name( BUS, port ),
+                    & get_conditional_expression( & choices, & default ) ) );
+            for host in & self.hosts {
+                let expression = if member == WAITREQUEST && self.hosts.len() > 1 {
+                    format!( "{} when {}_grant = '1' else {}_request", self.get_name( BUS, port ), host, host )
+                }
+                else {
+                    self.get_name( BUS, port )
+                };
+                architecture.add_signal_assignment( SignalAssignment::new( & self.get_name( host, port ),
+                        & expression ) );
+            }
+        }
+    }
+
+    fn get_name( & self, interface : & str, port : & Port ) -> String {
+        format!( "{}_{}", interface, port.get_base_name() )
+    }
+
+    fn get_port( & self, member : & str ) -> & Port {
+        find_member( & self.interface, member ).unwrap()
+    }
+
+    fn requires_free_name( & self, name : & str ) -> Result< (), VhdlError > {
+        let taken = name == BUS || self.hosts.iter().any( | h | h == name ) ||
+                self.address_map.get_ranges().iter().any( | r | r.get_name() == name );
+        if taken {
+            return Err( VhdlError::new( & format!( "error: Name {:?} is already used in interconnect {:?}!",
+                    name, self.name ) ) );
+        }
+        Ok(())
+    }
+
+    fn requires_hosts_and_agents( & self ) -> Result< (), VhdlError > {
+        if self.hosts.is_empty() || self.address_map.is_empty() {
+            return Err( VhdlError::new( & format!( "error: Interconnect {:?} needs at least one host and one agent!",
+                    self.name ) ) );
+        }
+        if self.hosts.len() > 1 && find_member( & self.interface, WAITREQUEST ).is_none() {
+            return Err( VhdlError::new( & format!(
+                    "error: Interconnect {:?} with several hosts needs a {:?} member!", self.name, WAITREQUEST ) ) );
+        }
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::Element;
+    use crate::vhdl::lint::lint;
+    use std::path::Path;
+
+    fn new_interconnect() -> AvalonMmInterconnect {
+        let interface = EntityInterface::from_file_unnamed( Path::new( "tests/vhdl/avalon_mm.json" ) ).unwrap();
+        let mut interconnect = AvalonMmInterconnect::new( "interconnect", & interface ).unwrap();
+        interconnect.add_host( "cpu" ).unwrap();
+        interconnect.add_agent( "ram", 0x0000, 0x1000 ).unwrap();
+        interconnect.add_agent( "uart", 0x1000, 0x10 ).unwrap();
+        interconnect
+    }
+
+    #[test]
+    fn decoder_and_read_mux() -> Result< (), VhdlError > {
+        let source = new_interconnect().get_architecture()?.to_source_code( 0 );
+        assert!( source.contains( "constant UART_LAST : unsigned( 15 downto 0 ) := x\"100F\";" ) );
+        assert!( source.contains(
+                "uart_select <= '1' when unsigned( bus_address ) >= UART_BASE and unsigned( bus_address ) <= UART_LAST else '0';" ) );
+        assert!( source.contains(
+                "uart_address <= std_logic_vector( resize( unsigned( bus_address ) - UART_BASE, bus_address'length ) );" ) );
+        assert!( source.contains( "ram_read <= bus_read and ram_select;" ) );
+        assert!( source.contains( concat!( "bus_response <= ram_response when ram_select = '1' else\n",
+                "        uart_response when uart_select = '1' else\n",
+                "        \"11\";" ) ) );
+        assert!( source.contains( "cpu_read_data <= bus_read_data;" ) );
+        Ok(())
+    }
+
+    #[test]
+    fn several_hosts_need_waitrequest() {
+        let mut interconnect = new_interconnect();
+        interconnect.add_host( "dma" ).unwrap();
+        assert!( interconnect.get_entity().is_err() );
+        assert!( interconnect.add_host( "ram" ).is_err() );
+    }
+
+    #[test]
+    fn high_addresses() -> Result< (), VhdlError > {
+        let mut interconnect = new_interconnect();
+        interconnect.add_agent( "rom", 0xffff_0000, 0x1_0000 )?;
+        let source = interconnect.get_architecture()?.to_source_code( 0 );
+        assert!( source.contains( "constant UART_BASE : unsigned( 31 downto 0 ) := x\"00001000\";" ) );
+        assert!( source.contains( "constant ROM_LAST : unsigned( 31 downto 0 ) := x\"FFFFFFFF\";" ) );
+        Ok(())
+    }
+
+    #[test]
+    fn fixed_priority_arbitration() -> Result< (), VhdlError > {
+        let mut interface = EntityInterface::from_file_unnamed( Path::new( "tests/vhdl/avalon_mm.json" ) ).unwrap();
+        interface.add_port( Port::new( WAITREQUEST, Direction::IN, "std_logic" ) );
+        let mut interconnect = AvalonMmInterconnect::new( "interconnect", & interface.clone_inverted() )?;
+        interconnect.add_host( "cpu" )?;
+        interconnect.add_host( "dma" )?;
+        interconnect.add_agent( "ram", 0x0000, 0x1000 )?;
+        let architecture = interconnect.get_architecture()?;
+        assert!( lint( & architecture ).is_empty() );
+        let source = architecture.to_source_code( 0 );
+        assert!( source.contains( "        clk : in std_logic;\n" ) );
+        assert!( source.contains( concat!( "            elsif active = '0' then\n",
+                "                if cpu_request = '1' or dma_request = '1' then\n",
+                "                     active <= '1';\n",
+                "                     cpu_grant <= cpu_request;\n",
+                "                     dma_grant <= dma_request and not ( cpu_request );\n" ) ) );
+        assert!( source.contains( concat!( "            elsif bus_waitrequest = '0' then\n",
+                "                 active <= '0';\n",
+                "                 cpu_grant <= '0';\n" ) ) );
+        assert!( source.contains( "dma_waitrequest <= bus_waitrequest when dma_grant = '1' else dma_request;" ) );
+        assert!( source.contains( concat!( "bus_read <= cpu_read when cpu_grant = '1' else\n",
+                "        dma_read when dma_grant = '1' else\n",
+                "        '0';" ) ) );
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+use crate::vhdl::architecture::Architecture;
+use crate::vhdl::constant_declaration::ConstantDeclaration;
+use crate::vhdl::entity::Entity;
+use crate::vhdl::entity_interface::EntityInterface;
+use crate::vhdl::if_statement::IfStatement;
+use crate::vhdl::interconnect::new_clk_rst_interface;
+use crate::vhdl::interconnect::avalon_mm::{ get_host_view, find_member };
+use crate::vhdl::interconnect::axi4_lite::new_axi4_lite_interface;
+use crate::vhdl::port::Port;
+use crate::vhdl::process::Process;
+use crate::vhdl::signal_assignment::SignalAssignment;
+use crate::vhdl::signal_declaration::SignalDeclaraion;
+use crate::vhdl::vhdl_error::VhdlError;
+
+const AVALON : &str = "avalon";
+const AXI : &str = "axi";
+/**
+ * Avalon-MM members the bridge reads or drives, `byteenable` and `response` are optional.
+ */
+const REQUIRED : [ &str; 6 ] = [ "address", "read", "write", "write_data", "read_data", "waitrequest" ];
+
+const STATE_TYPE : &str = "std_logic_vector( 2 downto 0 )";
+
+const STATES : [ ( &str, &str ); 6 ] = [
+    ( "STATE_IDLE", "\"000\"" ),
+    ( "STATE_WRITE", "\"001\"" ),
+    ( "STATE_WRITE_RESPONSE", "\"010\"" ),
+    ( "STATE_READ", "\"011\"" ),
+    ( "STATE_READ_RESPONSE", "\"100\"" ),
+    ( "STATE_DONE", "\"101\"" ),
+];
+
+/**
+ * Generates a bridge from an Avalon-MM agent interface `avalon` to an AXI4-Lite host interface
+ * `axi`.  The Avalon-MM host is stalled by `waitrequest` until the AXI4-Lite response arrives,
+ * so the Avalon-MM interface needs a `waitrequest` member.  Both interfaces share the generics
+ * `ADDR_WIDTH` and `DATA_WIDTH` by name.
+ */
+pub struct AvalonMmToAxi4LiteBridge {
+    name : String,
+    interface : EntityInterface,
+}
+
+impl AvalonMmToAxi4LiteBridge {
+    /**
+     * Creates a bridge for Avalon-MM interfaces like `interface`, given in host or agent view.
+     */
+    pub fn new( name : & str, interface : & EntityInterface ) -> Result< AvalonMmToAxi4LiteBridge, VhdlError > {
+        let interface = get_host_view( interface )?;
+        for member in REQUIRED {
+            if find_member( & interface, member ).is_none() {
+                return Err( VhdlError::new( & format!( "error: Bridge {:?} needs a {:?} member!", name, member ) ) );
+            }
+        }
+        Ok( AvalonMmToAxi4LiteBridge { name : name.to_string(), interface } )
+    }
+
+    pub fn get_entity( & self ) -> Result< Entity, VhdlError > {
+        let mut entity = Entity::new( & self.name );
+        entity.add_interface( & new_clk_rst_interface() )?;
+        let mut avalon = self.interface.clone();
+        let mut axi = new_axi4_lite_interface();
+        for generic in avalon.get_generics() {
+            entity.add_generic( generic.clone() );
+        }
+        for generic in axi.get_generics() {
+            if ! avalon.get_generics().iter().any( | g | g.get_base_name() == generic.get_base_name() ) {
+                entity.add_generic( generic.clone() );
+            }
+        }
+        avalon.remove_generics();
+        entity.add_interface( & avalon.clone_inverted_with_rename( AVALON ) )?;
+        axi.remove_generics();
+        axi.rename( AXI );
+        entity.add_interface( & axi )?;
+        Ok( entity )
+    }
+
+    pub fn get_architecture( & self ) -> Result< Architecture, VhdlError > {
+        let entity = self.get_entity()?;
+        let mut architecture = Architecture::new( "rtl", & entity );
+        for ( name, value ) in STATES {
+            architecture.add_constant_declaration( & ConstantDeclaration::new( name, STATE_TYPE, value ) );
+        }
+        let read_data = self.get_member( "read_data" );
+        let response = find_member( & self.interface, "response" );
+        architecture.add_signal_declaration( & SignalDeclaraion::new( "state", STATE_TYPE ) );
+        architecture.add_signal_declaration( & SignalDeclaraion::new( "write_address_done", "std_logic" ) );
+        architecture.add_signal_declaration( & SignalDeclaraion::new( "write_data_done", "std_logic" ) );
+        architecture.add_signal_declaration( & SignalDeclaraion::new( "read_data", read_data.get_data_type() ) );
+        if let Some( response ) = response {
+            architecture.add_signal_declaration( & SignalDeclaraion::new( "response", response.get_data_type() ) );
+        }
+
+        let address = self.get_name( "address" );
+        let strobe = match find_member( & self.interface, "byteenable" ) {
+            Some( _ ) => self.get_name( "byteenable" ),
+            None => "(others => '1')".to_string(),
+        };
+        for ( signal, expression ) in [
+                ( "axi_awaddr", address.as_str() ),
+                ( "axi_awprot", "\"000\"" ),
+                ( "axi_awvalid", "'1' when state = STATE_WRITE and write_address_done = '0' else '0'" ),
+                ( "axi_wdata", & self.get_name( "write_data" ) ),
+                ( "axi_wstrb", & strobe ),
+                ( "axi_wvalid", "'1' when state = STATE_WRITE and write_data_done = '0' else '0'" ),
+                ( "axi_bready", "'1' when state = STATE_WRITE_RESPONSE else '0'" ),
+                ( "axi_araddr", address.as_str() ),
+                ( "axi_arprot", "\"000\"" ),
+                ( "axi_arvalid", "'1' when state = STATE_READ else '0'" ),
+                ( "axi_rready", "'1' when state = STATE_READ_RESPONSE else '0'" ),
+                ( & self.get_name( "read_data" ), "read_data" ),
+                ( & self.get_name( "waitrequest" ), "'0' when state = STATE_DONE else '1'" ) ] {
+            architecture.add_signal_assignment( SignalAssignment::new( signal, expression ) );
+        }
+        if response.is_some() {
+            architecture.add_signal_assignment( SignalAssignment::new( & self.get_name( "response" ), "response" ) );
+        }
+        architecture.add_process( self.get_bridge_process( response.is_some() ) );
+        Ok( architecture )
+    }
+
+    fn get_bridge_process( & self, has_response : bool ) -> Process {
+        let mut state = IfStatement::new( "rst = '1'" );
+        state.add_statement( SignalAssignment::new( "state", "STATE_IDLE" ) );
+
+        state.add_elsif( "state = STATE_IDLE" );
+        state.add_statement( SignalAssignment::new( "write_address_done", "'0'" ) );
+        state.add_statement( SignalAssignment::new( "write_data_done", "'0'" ) );
+        let mut start = IfStatement::new( & format!( "{} = '1'", self.get_name( "write" ) ) );
+        start.add_statement( SignalAssignment::new( "state", "STATE_WRITE" ) );
+        start.add_elsif( & format!( "{} = '1'", self.get_name( "read" ) ) );
+        start.add_statement( SignalAssignment::new( "state", "STATE_READ" ) );
+        state.add_statement( start );
+
+        state.add_elsif( "state = STATE_WRITE" );
+        let mut address_done = IfStatement::new( "axi_awready = '1'" );
+        address_done.add_statement( SignalAssignment::new( "write_address_done", "'1'" ) );
+        state.add_statement( address_done );
+        let mut data_done = IfStatement::new( "axi_wready = '1'" );
+        data_done.add_statement( SignalAssignment::new( "write_data_done", "'1'" ) );
+        state.add_statement( data_done );
+        let mut written = IfStatement::new(
+                "( write_address_done = '1' or axi_awready = '1' ) and ( write_data_done = '1' or axi_wready = '1' )" );
+        written.add_statement( SignalAssignment::new( "state", "STATE_WRITE_RESPONSE" ) );
+        state.add_statement( written );
+
+        state.add_elsif( "state = STATE_WRITE_RESPONSE" );
+        let mut write_response = IfStatement::new( "axi_bvalid = '1'" );
+        write_response.add_statement( SignalAssignment::new( "state", "STATE_DONE" ) );
+        if has_response {
+            write_response.add_statement( SignalAssignment::new( "response", "axi_bresp" ) );
+        }
+        state.add_statement( write_response );
+
+        state.add_elsif( "state = STATE_READ" );
+        let mut read_address = IfStatement::new( "axi_arready = '1'" );
+        read_address.add_statement( SignalAssignment::new( "state", "STATE_READ_RESPONSE" ) );
+        state.add_statement( read_address );
+
+        state.add_elsif( "state = STATE_READ_RESPONSE" );
+        let mut read_response = IfStatement::new( "axi_rvalid = '1'" );
+        read_response.add_statement( SignalAssignment::new( "state", "STATE_DONE" ) );
+        read_response.add_statement( SignalAssignment::new( "read_data", "axi_rdata" ) );
+        if has_response {
+            read_response.add_statement( SignalAssignment::new( "response", "axi_rresp" ) );
+        }
+        state.add_statement( read_response );
+
+        state.add_else();
+        state.add_statement( SignalAssignment::new( "state", "STATE_IDLE" ) );
+
+        let mut clocked = IfStatement::new( "rising_edge( clk )" );
+        clocked.add_statement( state );
+        let mut process = Process::new( "bridge" );
+        process.add_sensitivity( "clk" );
+        process.add_statement( clocked );
+        process
+    }
+
+    /**
+     * Returns a member of `REQUIRED`, checked by `new`.
+     */
+    fn get_member( & self, member : & str ) -> & Port {
+        find_member( & self.interface, member ).unwrap()
+    }
+
+    /**
+     * Returns the name of an Avalon-MM port of the bridge entity.
+     */
+    fn get_name( & self, member : & str ) -> String {
+        format!( "{}_{}", AVALON, self.get_member( member ).get_base_name() )
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use crate::element::Element;
+    use crate::vhdl::direction::Direction;
+    use crate::vhdl::lint::lint;
+
+    #[test]
+    fn avalon_mm_to_axi4_lite() -> Result< (), VhdlError > {
+        let mut interface = EntityInterface::from_file_unnamed( Path::new( "tests/vhdl/avalon_mm.json" ) ).unwrap();
+        assert!( AvalonMmToAxi4LiteBridge::new( "bridge", & interface ).is_err() );
+        interface.add_port( Port::new( "waitrequest", Direction::IN, "std_logic" ) );
+        let architecture = AvalonMmToAxi4LiteBridge::new( "bridge", & interface )?.get_architecture()?;
+        assert!( lint( & architecture ).is_empty() );
+        assert_eq!( architecture.get_entity().get_generics().len(), 3 );
+        let source = architecture.to_source_code( 0 );
+        assert!( source.contains( "        avalon_waitrequest : out std_logic;\n" ) );
+        assert!( source.contains( "        axi_awvalid : out std_logic;\n" ) );
+        assert!( source.contains( "     axi_wstrb <= avalon_byteenable;\n" ) );
+        assert!( source.contains( concat!( "                if axi_rvalid = '1' then\n",
+                "                     state <= STATE_DONE;\n",
+                "                     read_data <= axi_rdata;\n",
+                "                     response <= axi_rresp;\n" ) ) );
+        Ok(())
+    }
+
+    #[test]
+    fn requires_members() {
+        let mut interface = EntityInterface::from_file_unnamed( Path::new( "tests/vhdl/avalon_mm.json" ) ).unwrap();
+        interface.add_port( Port::new( "waitrequest", Direction::IN, "std_logic" ) );
+        let without_read = interface.clone_subset( | p | p.get_base_name() != "read" );
+        let err = AvalonMmToAxi4LiteBridge::new( "bridge", & without_read ).err().unwrap();
+        assert!( err.to_string().contains( "read" ) );
+    }
+}
@@ -0,0 +1,380 @@
+use crate::vhdl::architecture::Architecture;
+use crate::vhdl::data_type::get_zero_value;
+use crate::vhdl::direction::Direction;
+use crate::vhdl::entity::Entity;
+use crate::vhdl::entity_interface::EntityInterface;
+use crate::vhdl::if_statement::IfStatement;
+use crate::vhdl::interconnect::{ new_clk_rst_interface, get_conditional_expression, get_priority_expression,
+        get_address_hit, get_relative_address, add_address_constants };
+use crate::vhdl::interconnect::address_map::AddressMap;
+use crate::vhdl::interface_catalog::get_interface;
+use crate::vhdl::port::Port;
+use crate::vhdl::process::Process;
+use crate::vhdl::signal_assignment::SignalAssignment;
+use crate::vhdl::signal_declaration::SignalDeclaraion;
+use crate::vhdl::vhdl_error::VhdlError;
+
+pub const AXI4_LITE_CLASS : &str = "axi4_lite";
+
+/**
+ * Channel and member of the AXI4-Lite ports.
+ */
+const MEMBERS : [ ( &str, &str ); 19 ] = [
+    ( "aw", "addr" ),
+    ( "aw", "prot" ),
+    ( "aw", "valid" ),
+    ( "aw", "ready" ),
+    ( "w", "data" ),
+    ( "w", "strb" ),
+    ( "w", "valid" ),
+    ( "w", "ready" ),
+    ( "b", "resp" ),
+    ( "b", "valid" ),
+    ( "b", "ready" ),
+    ( "ar", "addr" ),
+    ( "ar", "prot" ),
+    ( "ar", "valid" ),
+    ( "ar", "ready" ),
+    ( "r", "data" ),
+    ( "r", "resp" ),
+    ( "r", "valid" ),
+    ( "r", "ready" ),
+];
+
+/**
+ * Response of a transfer to an address no agent is mapped to.
+ */
+const DECODE_ERROR : &str = "\"11\"";
+
+/**
+ * Prefix of the internal signals carrying the transfer of the granted host.
+ */
+const BUS : &str = "bus";
+
+/**
+ * The write and read path of the crossbar with their request channels and response channel.
+ */
+struct Path {
+    name : &'static str,
+    requests : &'static [ &'static str ],
+    response : &'static str,
+}
+
+const PATHS : [ Path; 2 ] = [
+    Path { name : "write", requests : & [ "aw", "w" ], response : "b" },
+    Path { name : "read", requests : & [ "ar" ], response : "r" },
+];
+
+/**
+ * Returns the unnamed AXI4-Lite interface in host view with the generics `ADDR_WIDTH` and
+ * `DATA_WIDTH`.
+ */
+pub fn new_axi4_lite_interface() -> EntityInterface {
+    get_interface( AXI4_LITE_CLASS ).unwrap()
+}
+
+/**
+ * Returns channel and member of an AXI4-Lite port, e.g. `aw` and `valid` for `awvalid`.
+ */
+fn split_member( port : & Port ) -> ( &'static str, &'static str ) {
+    let name = port.get_base_name().to_lowercase();
+    MEMBERS.iter().find( | ( c, m ) | format!( "{}{}", c, m ) == name )
+            .map( | ( c, m ) | ( * c, * m ) ).unwrap()
+}
+
+/**
+ * Generates an AXI4-Lite crossbar between hosts and agents.  Write and read path are arbitrated
+ * separately with fixed priority in the order the hosts were added, each path carries one
+ * transaction at a time.  The target agent is decoded from the address when the transaction is
+ * granted and receives the address relative to its base.  Unmapped addresses are answered with
+ * a decode error response.
+ */
+pub struct Axi4LiteCrossbar {
+    name : String,
+    hosts : Vec< String >,
+    address_map : AddressMap,
+}
+
+impl Axi4LiteCrossbar {
+    pub fn new( name : & str ) -> Axi4LiteCrossbar {
+        Axi4LiteCrossbar { name : name.to_string(), hosts : Vec::new(), address_map : AddressMap::new() }
+    }
+
+    pub fn add_host( & mut self, name : & str ) -> Result< (), VhdlError > {
+        self.requires_free_name( name )?;
+        self.hosts.push( name.to_string() );
+        Ok(())
+    }
+
+    pub fn add_agent( & mut self, name : & str, base : u64, size : u64 ) -> Result< (), VhdlError > {
+        self.requires_free_name( name )?;
+        self.address_map.add( name, base, size )
+    }
+
+    pub fn get_address_map( & self ) -> & AddressMap {
+        & self.address_map
+    }
+
+    /**
+     * The crossbar entity: clock and reset, the interface generics, one agent view interface
+     * per host and one host view interface per agent, each named after the host or agent.
+     */
+    pub fn get_entity( & self ) -> Result< Entity, VhdlError > {
+        if self.hosts.is_empty() || self.address_map.is_empty() {
+            return Err( VhdlError::new( & format!( "error: Crossbar {:?} needs at least one host and one agent!",
+                    self.name ) ) );
+        }
+        let mut entity = Entity::new( & self.name );
+        entity.add_interface( & new_clk_rst_interface() )?;
+        let mut template = new_axi4_lite_interface();
+        for generic in template.get_generics() {
+            entity.add_generic( generic.clone() );
+        }
+        template.remove_generics();
+        for host in & self.hosts {
+            entity.add_interface( & template.clone_inverted_with_rename( host ) )?;
+        }
+        for range in self.address_map.get_ranges() {
+            let mut agent = template.clone();
+            agent.rename( range.get_name() );
+            entity.add_interface( & agent )?;
+        }
+        entity.add_missing_library_use( "unsigned" );
+        Ok( entity )
+    }
+
+    pub fn get_architecture( & self ) -> Result< Architecture, VhdlError > {
+        let entity = self.get_entity()?;
+        let mut architecture = Architecture::new( "rtl", & entity );
+        let interface = new_axi4_lite_interface();
+        for port in interface.get_ports() {
+            architecture.add_signal_declaration( & SignalDeclaraion::new(
+                    & get_name( BUS, port ), port.get_data_type() ) );
+        }
+        add_address_constants( & mut architecture, & self.address_map );
+        for path in & PATHS {
+            let ports : Vec< & Port > = interface.get_ports().iter().filter( | p | {
+                let ( channel, _ ) = split_member( p );
+                path.requests.contains( & channel ) || path.response == channel
+            } ).collect();
+            self.add_decoder( & mut architecture, path, & ports );
+            self.add_forward( & mut architecture, path, & ports );
+            self.add_backward( & mut architecture, path, & ports );
+            self.add_arbiter( & mut architecture, path );
+        }
+        Ok( architecture )
+    }
+
+    /**
+     * Decodes the address of the host to be granted next.
+     */
+    fn add_decoder( & self, architecture : & mut Architecture, path : & Path, ports : & [ & Port ] ) {
+        let channel = path.requests[ 0 ];
+        let address = ports.iter().find( | p | split_member( p ) == ( channel, "addr" ) ).unwrap();
+        let request_address = format!( "{}_request_address", path.name );
+        architecture.add_signal_declaration( & SignalDeclaraion::new( & request_address, address.get_data_type() ) );
+        let choices : Vec< ( String, String ) > = self.hosts.iter().map( | h |
+                ( get_name( h, address ), format!( "{}_{}valid = '1'", h, channel ) ) ).collect();
+        architecture.add_signal_assignment( SignalAssignment::new( & request_address,
+                & get_conditional_expression( & choices, & get_zero_value( address.get_data_type() ).unwrap() ) ) );
+        for range in self.address_map.get_ranges() {
+            let hit = format!( "{}_{}_hit", range.get_name(), path.name );
+            architecture.add_signal_declaration( & SignalDeclaraion::new( & hit, "std_logic" ) );
+            architecture.add_signal_assignment( SignalAssignment::new( & hit,
+                    & format!( "'1' when {} else '0'", get_address_hit( & request_address, range.get_name() ) ) ) );
+        }
+    }
+
+    /**
+     * Drives the host outputs of the path from the granted host to the selected agent.
+     */
+    fn add_forward( & self, architecture : & mut Architecture, path : & Path, ports : & [ & Port ] ) {
+        for port in ports.iter().filter( | p | p.get_direction() == Direction::OUT ) {
+            let ( channel, member ) = split_member( port );
+            let bus = get_name( BUS, port );
+            let choices : Vec< ( String, String ) > = self.hosts.iter().map( | h |
+                    ( get_name( h, port ), format!( "{}_{}_grant = '1'", h, path.name ) ) ).collect();
+            architecture.add_signal_assignment( SignalAssignment::new( & bus,
+                    & get_conditional_expression( & choices, & get_zero_value( port.get_data_type() ).unwrap() ) ) );
+            for range in self.address_map.get_ranges() {
+                let agent = range.get_name();
+                let select = format!( "{}_{}_select", agent, path.name );
+                let expression = match member {
+                    "addr" => get_relative_address( & bus, agent ),
+                    "valid" => format!( "{} and {} and not {}_{}_done", bus, select, path.name, channel ),
+                    "ready" => format!( "{} and {}", bus, select ),
+                    _ => bus.clone(),
+                };
+                architecture.add_signal_assignment( SignalAssignment::new( & get_name( agent, port ), & expression ) );
+            }
+        }
+    }
+
+    /**
+     * Drives the host inputs of the path from the selected agent, or the decode error
+     * responder, to the granted host.
+     */
+    fn add_backward( & self, architecture : & mut Architecture, path : & Path, ports : & [ & Port ] ) {
+        for port in ports.iter().filter( | p | p.get_direction() == Direction::IN ) {
+            let ( channel, member ) = split_member( port );
+            let bus = get_name( BUS, port );
+            let default = match member {
+                "ready" => format!( "{}_error and not {}_{}_done", path.name, path.name, channel ),
+                "valid" => format!( "{}_error_response", path.name ),
+                "resp" => DECODE_ERROR.to_string(),
+                _ => get_zero_value( port.get_data_type() ).unwrap(),
+            };
+            let choices : Vec< ( String, String ) > = self.address_map.get_ranges().iter().map( | r |
+                    ( get_name( r.get_name(), port ), format!( "{}_{}_select = '1'", r.get_name(), path.name ) ) )
+                    .collect();
+            architecture.add_signal_assignment( SignalAssignment::new( & bus,
+                    & get_conditional_expression( & choices, & default ) ) );
+            for host in & self.hosts {
+                let grant = format!( "{}_{}_grant", host, path.name );
+                let expression = match member {
+                    "ready" => format!( "{} and {} and not {}_{}_done", bus, grant, path.name, channel ),
+                    "valid" => format!( "{} and {}", bus, grant ),
+                    _ => bus.clone(),
+                };
+                architecture.add_signal_assignment( SignalAssignment::new( & get_name( host, port ), & expression ) );
+            }
+        }
+    }
+
+    /**
+     * Grants the path to the first requesting host and holds grant and agent selection until
+     * the response handshake.  The decode error responder accepts the request channels and
+     * responds once all of them are done.
+     */
+    fn add_arbiter( & self, architecture : & mut Architecture, path : & Path ) {
+        let name = path.name;
+        let active = format!( "{}_active", name );
+        let error = format!( "{}_error", name );
+        let error_response = format!( "{}_error_response", name );
+        let dones : Vec< String > = path.requests.iter().map( | c | format!( "{}_{}_done", name, c ) ).collect();
+        let grants : Vec< String > = self.hosts.iter().map( | h | format!( "{}_{}_grant", h, name ) ).collect();
+        let selects : Vec< String > = self.address_map.get_ranges().iter()
+                .map( | r | format!( "{}_{}_select", r.get_name(), name ) ).collect();
+        let hits : Vec< String > = self.address_map.get_ranges().iter()
+                .map( | r | format!( "{}_{}_hit", r.get_name(), name ) ).collect();
+        let requests : Vec< String > = self.hosts.iter()
+                .map( | h | format!( "{}_{}valid", h, path.requests[ 0 ] ) ).collect();
+        let mut cleared : Vec< & String > = vec![ & active, & error, & error_response ];
+        cleared.extend( & dones );
+        cleared.extend( & grants );
+        cleared.extend( & selects );
+        for signal in & cleared {
+            architecture.add_signal_declaration( & SignalDeclaraion::new( signal, "std_logic" ) );
+        }
+
+        let mut state = IfStatement::new( "rst = '1'" );
+        for signal in & cleared {
+            state.add_statement( SignalAssignment::new( signal, "'0'" ) );
+        }
+        state.add_elsif( & format!( "{} = '0'", active ) );
+        let conditions : Vec< String > = requests.iter().map( | r | format!( "{} = '1'", r ) ).collect();
+        let mut grant = IfStatement::new( & conditions.join( " or " ) );
+        grant.add_statement( SignalAssignment::new( & active, "'1'" ) );
+        for ( idx, signal ) in grants.iter().enumerate() {
+            grant.add_statement( SignalAssignment::new( signal, & get_priority_expression( & requests, idx ) ) );
+        }
+        for ( select, hit ) in selects.iter().zip( & hits ) {
+            grant.add_statement( SignalAssignment::new( select, hit ) );
+        }
+        grant.add_statement( SignalAssignment::new( & error, & format!( "not ( {} )", hits.join( " or " ) ) ) );
+        state.add_statement( grant );
+        state.add_elsif( & format!( "{}_{}valid = '1' and {}_{}ready = '1'", BUS, path.response, BUS, path.response ) );
+        for signal in & cleared {
+            state.add_statement( SignalAssignment::new( signal, "'0'" ) );
+        }
+        state.add_else();
+        for ( channel, done ) in path.requests.iter().zip( & dones ) {
+            let mut handshake = IfStatement::new( & format!( "{}_{}valid = '1' and {}_{}ready = '1'",
+                    BUS, channel, BUS, channel ) );
+            handshake.add_statement( SignalAssignment::new( done, "'1'" ) );
+            state.add_statement( handshake );
+        }
+        let mut conditions = vec![ format!( "{} = '1'", error ) ];
+        conditions.extend( dones.iter().map( | d | format!( "{} = '1'", d ) ) );
+        let mut respond = IfStatement::new( & conditions.join( " and " ) );
+        respond.add_statement( SignalAssignment::new( & error_response, "'1'" ) );
+        state.add_statement( respond );
+
+        let mut clocked = IfStatement::new( "rising_edge( clk )" );
+        clocked.add_statement( state );
+        let mut process = Process::new( & format!( "{}_arbiter", name ) );
+        process.add_sensitivity( "clk" );
+        process.add_statement( clocked );
+        architecture.add_process( process );
+    }
+
+    fn requires_free_name( & self, name : & str ) -> Result< (), VhdlError > {
+        let taken = name == BUS || self.hosts.iter().any( | h | h == name ) ||
+                self.address_map.get_ranges().iter().any( | r | r.get_name() == name );
+        if taken {
+            return Err( VhdlError::new( & format!( "error: Name {:?} is already used in crossbar {:?}!",
+                    name, self.name ) ) );
+        }
+        Ok(())
+    }
+}
+
+fn get_name( interface : & str, port : & Port ) -> String {
+    format!( "{}_{}", interface, port.get_base_name() )
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::Element;
+    use crate::vhdl::lint::lint;
+
+    fn new_crossbar() -> Axi4LiteCrossbar {
+        let mut crossbar = Axi4LiteCrossbar::new( "crossbar" );
+        crossbar.add_host( "cpu" ).unwrap();
+        crossbar.add_host( "dma" ).unwrap();
+        crossbar.add_agent( "ram", 0x0000, 0x1000 ).unwrap();
+        crossbar.add_agent( "uart", 0x1000, 0x10 ).unwrap();
+        crossbar
+    }
+
+    #[test]
+    fn interface() {
+        let interface = new_axi4_lite_interface();
+        assert_eq!( interface.get_class(), AXI4_LITE_CLASS );
+        assert_eq!( interface.get_ports().len(), 19 );
+        assert_eq!( interface.get_ports()[ 3 ].get_name(), "awready" );
+        assert_eq!( interface.get_ports()[ 3 ].get_direction(), Direction::IN );
+    }
+
+    #[test]
+    fn crossbar() -> Result< (), VhdlError > {
+        let architecture = new_crossbar().get_architecture()?;
+        assert!( lint( & architecture ).is_empty() );
+        let source = architecture.to_source_code( 0 );
+        assert!( source.contains( "        dma_awvalid : in std_logic;\n" ) );
+        assert!( source.contains( "        uart_awvalid : out std_logic;\n" ) );
+        assert!( source.contains( concat!( "     write_request_address <= cpu_awaddr when cpu_awvalid = '1' else\n",
+                "        dma_awaddr when dma_awvalid = '1' else\n",
+                "        (others => '0');\n" ) ) );
+        assert!( source.contains( "     ram_wvalid <= bus_wvalid and ram_write_select and not write_w_done;\n" ) );
+        assert!( source.contains( "     dma_arready <= bus_arready and dma_read_grant and not read_ar_done;\n" ) );
+        assert!( source.contains( concat!( "     bus_rresp <= ram_rresp when ram_read_select = '1' else\n",
+                "        uart_rresp when uart_read_select = '1' else\n",
+                "        \"11\";\n" ) ) );
+        assert!( source.contains( "                     dma_write_grant <= dma_awvalid and not ( cpu_awvalid );\n" ) );
+        assert!( source.contains( "                     write_error <= not ( ram_write_hit or uart_write_hit );\n" ) );
+        assert!( source.contains( concat!( "                if write_error = '1' and write_aw_done = '1' and write_w_done = '1' then\n",
+                "                     write_error_response <= '1';\n" ) ) );
+        Ok(())
+    }
+
+    #[test]
+    fn requires_hosts_and_agents() {
+        let mut crossbar = Axi4LiteCrossbar::new( "crossbar" );
+        crossbar.add_host( "cpu" ).unwrap();
+        assert!( crossbar.get_entity().is_err() );
+        assert!( crossbar.add_agent( "cpu", 0, 16 ).is_err() );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::vhdl::entity_interface::EntityInterface;
+use crate::vhdl::role_view::split_role_reference;
+use crate::vhdl::vhdl_error::VhdlError;
+
+/**
+ * Class, version and JSON description of the built-in interface definitions, older versions
+ * of a class first.
+ */
+const DEFINITIONS : [ ( &str, &str, &str ); 9 ] = [
+    ( "avalon_mmi", "1.0", include_str!( "../../data/interfaces/avalon_mmi.json" ) ),
+    ( "avalon_st", "1.0", include_str!( "../../data/interfaces/avalon_st.json" ) ),
+    ( "axi4", "1.0", include_str!( "../../data/interfaces/axi4.json" ) ),
+    ( "axi4_lite", "1.0", include_str!( "../../data/interfaces/axi4_lite.json" ) ),
+    ( "axi4_stream", "1.0", include_str!( "../../data/interfaces/axi4_stream.json" ) ),
+    ( "apb", "1.0", include_str!( "../../data/interfaces/apb.json" ) ),
+    ( "wishbone", "1.0", include_str!( "../../data/interfaces/wishbone.json" ) ),
+    ( "clk_rst", "1.0", include_str!( "../../data/interfaces/clk_rst.json" ) ),
+    ( "irq", "1.0", include_str!( "../../data/interfaces/irq.json" ) ),
+];
+
+/**
+ * Classes of the built-in interface definitions.
+ */
+pub fn get_classes() -> Vec< &'static str > {
+    let mut classes : Vec< &'static str > = Vec::new();
+    for ( class, _, _ ) in DEFINITIONS {
+        if ! classes.contains( & class ) {
+            classes.push( class );
+        }
+    }
+    classes
+}
+
+/**
+ * Versions of the built-in definition of `class`, oldest first.
+ */
+pub fn get_versions( class : & str ) -> Vec< &'static str > {
+    DEFINITIONS.iter().filter( | ( c, _, _ ) | * c == class ).map( | ( _, v, _ ) | * v ).collect()
+}
+
+/**
+ * Returns the unnamed latest version of the built-in definition of `class` in its first view,
+ * or in the role view given like `avalon_mmi@agent`.
+ */
+pub fn get_interface( class : & str ) -> Result< EntityInterface, VhdlError > {
+    let ( class, role ) = split_role_reference( class );
+    let interface = match get_versions( class ).last() {
+        Some( version ) => get_interface_version( class, version )?,
+        None => return Err( VhdlError::new( & format!( "error: Unknown interface class {:?}!", class ) ) ),
+    };
+    match role {
+        Some( role ) => interface.clone_view( role ),
+        None => Ok( interface ),
+    }
+}
+
+pub fn get_interface_version( class : & str, version : & str ) -> Result< EntityInterface, VhdlError > {
+    match DEFINITIONS.iter().find( | ( c, v, _ ) | * c == class && * v == version ) {
+        Some( ( _, _, json ) ) => Ok( EntityInterface::from_json_str( json ).unwrap() ),
+        None => Err( VhdlError::new( & format!( "error: Unknown version {:?} of interface class {:?}!",
+            version, class ) ) ),
+    }
+}
+
+/**
+ * Returns the unnamed latest version of the built-in definition of `class` in the direction
+ * view `view`, e.g. `agent` for a bus interface.
+ */
+pub fn get_interface_view( class : & str, view : & str ) -> Result< EntityInterface, VhdlError > {
+    get_interface( class )?.clone_view( view )
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vhdl::direction::Direction;
+
+    #[test]
+    fn definitions() -> Result< (), VhdlError > {
+        assert_eq!( get_classes().len(), DEFINITIONS.len() );
+        for ( class, version, _ ) in DEFINITIONS {
+            let interface = get_interface_version( class, version )?;
+            assert_eq!( interface.get_class(), class );
+            assert_eq!( interface.get_version(), version );
+            assert_eq!( interface.get_views().len(), 2 );
+            assert!( interface.get_ports().iter().any( | p | ! p.is_optional() ) );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn views() -> Result< (), VhdlError > {
+        let host = get_interface( "axi4_lite" )?;
+        assert_eq!( host.get_view_names(), vec![ "host", "agent" ] );
+        assert_eq!( host.get_role(), "host" );
+        assert_eq!( host.get_ports()[ 0 ].get_direction(), Direction::OUT );
+        let agent = get_interface_view( "axi4_lite", "agent" )?;
+        assert_eq!( agent.get_role(), "agent" );
+        assert_eq!( agent.get_ports()[ 0 ].get_direction(), Direction::IN );
+        assert_eq!( get_interface( "axi4_lite@agent" )?.get_role(), "agent" );
+        assert!( agent.get_ports()[ 1 ].is_optional() );
+        assert_eq!( agent.clone_view( "host" )?.get_ports()[ 0 ].get_direction(), Direction::OUT );
+        assert!( get_interface_view( "axi4_lite", "source" ).is_err() );
+        Ok(())
+    }
+
+    #[test]
+    fn unknown() {
+        assert!( get_interface( "pci" ).is_err() );
+        assert!( get_interface_version( "apb", "0.1" ).is_err() );
+    }
+}
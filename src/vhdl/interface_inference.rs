@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use crate::vhdl::entity_interface::EntityInterface;
+use crate::vhdl::interface_registry::InterfaceRegistry;
+use crate::vhdl::port::Port;
+use crate::vhdl::vhdl_error::VhdlError;
+
+/**
+ * Ports of a flat port list grouped into an interface of a registered class, e.g. `m0_address`,
+ * `m0_read`, ... as `avalon_mmi` named `m0`.
+ */
+#[derive(Debug, Clone)]
+pub struct InferredInterface {
+    name : String,
+    class : String,
+    role : String,
+    ports : Vec< ( String, String ) >,
+    missing : Vec< String >,
+    mismatched : Vec< String >,
+    confidence : f64,
+}
+
+impl InferredInterface {
+    /**
+     * Name of the interface, the common prefix of its ports.
+     */
+    pub fn get_name( & self ) -> & String {
+        & self.name
+    }
+
+    pub fn get_class( & self ) -> & String {
+        & self.class
+    }
+
+    /**
+     * Role view the port directions agree with, empty for a class without views.
+     */
+    pub fn get_role( & self ) -> & String {
+        & self.role
+    }
+
+    /**
+     * Pairs of port name and class member grouped into the interface.
+     */
+    pub fn get_ports( & self ) -> & Vec< ( String, String ) > {
+        & self.ports
+    }
+
+    /**
+     * Mandatory members of the class without a port.
+     */
+    pub fn get_missing( & self ) -> & Vec< String > {
+        & self.missing
+    }
+
+    /**
+     * Ports with a matching name but a direction contradicting the role.  They are left out.
+     */
+    pub fn get_mismatched( & self ) -> & Vec< String > {
+        & self.mismatched
+    }
+
+    /**
+     * Share of the mandatory members found, reduced by the share of ports with a contradicting
+     * direction, between 0 and 1.  A single port is weak evidence and counts half.
+     */
+    pub fn get_confidence( & self ) -> f64 {
+        self.confidence
+    }
+
+    /**
+     * Creates the interface of the grouping from the class definition, with the data types and
+     * names of the grouped ports and without the class generics.
+     */
+    pub fn to_entity_interface( & self, ports : & [ Port ], registry : & InterfaceRegistry )
+            -> Result< EntityInterface, VhdlError > {
+        let template = match registry.get( & self.class ) {
+            Ok( template ) => template,
+            Err( err ) => return Err( VhdlError::new( & format!( "error: Interface class {:?} can not be read: {}!",
+                self.class, err ) ) ),
+        };
+        let members : Vec< & String > = self.ports.iter().map( | ( _, m ) | m ).collect();
+        let mut interface = template.clone_subset( | p | members.contains( & p.get_base_name() ) );
+        if ! self.role.is_empty() {
+            interface.set_role( & self.role )?;
+        }
+        interface.remove_generics();
+        let mut result = EntityInterface::new( & self.name, & self.class );
+        for view in interface.get_views() {
+            result.add_view( view.clone() );
+        }
+        for member in interface.get_ports() {
+            let ( name, _ ) = self.ports.iter().find( | ( _, m ) | m == member.get_base_name() ).unwrap();
+            let port = ports.iter().find( | p | & p.get_name() == name ).unwrap();
+            let mut inferred = Port::new_with_default( member.get_base_name(), port.get_direction(),
+                port.get_data_type(), port.get_default() );
+            inferred.set_optional( member.is_optional() );
+            inferred.set_tie_off( member.get_tie_off() );
+            inferred.set_fixed( member.is_fixed() );
+            result.add_port( inferred );
+        }
+        if ! self.role.is_empty() {
+            result.set_role( & self.role )?;
+        }
+        let mut result = result.clone_with_renamed_ports( | member | {
+            let ( name, _ ) = self.ports.iter().find( | ( _, m ) | m == member ).unwrap();
+            name[ name.len() - member.len() .. ].to_string()
+        } )?;
+        result.rename( & self.name );
+        Ok( result )
+    }
+}
+
+impl std::fmt::Display for InferredInterface {
+    fn fmt( & self, f : & mut std::fmt::Formatter ) -> std::fmt::Result {
+        let name = if self.name.is_empty() { "<unnamed>" } else { & self.name };
+        write!( f, "{} : {}", name, self.class )?;
+        if ! self.role.is_empty() {
+            write!( f, "@{}", self.role )?;
+        }
+        write!( f, " ({:.2}", self.confidence )?;
+        if ! self.missing.is_empty() {
+            write!( f, ", missing {}", self.missing.join( ", " ) )?;
+        }
+        if ! self.mismatched.is_empty() {
+            write!( f, ", direction of {}", self.mismatched.join( ", " ) )?;
+        }
+        write!( f, ")" )
+    }
+}
+
+/**
+ * Groups `ports` into interfaces of the classes of `registry` by name prefix.  A port named
+ * `<prefix>_<member>`, or just `<member>`, is a candidate for every class with that member,
+ * compared case-insensitively.  The role is the view most port directions agree with, or the
+ * class definition or its inversion for classes without views.  Candidates are taken by
+ * descending confidence and port count, each port goes to one interface at most, including the
+ * ports left out for their direction.
+ */
+pub fn infer_interfaces( ports : & [ Port ], registry : & InterfaceRegistry ) -> Vec< InferredInterface > {
+    let mut candidates = Vec::new();
+    for class in registry.get_classes() {
+        let template = match registry.get( & class ) {
+            Ok( template ) => template,
+            Err( _ ) => continue,
+        };
+        let mut groups : HashMap< String, Vec< ( String, String ) > > = HashMap::new();
+        let mut prefixes : Vec< String > = Vec::new();
+        for port in ports {
+            let name = port.get_name();
+            let lower = name.to_lowercase();
+            for member in template.get_ports() {
+                let suffix = member.get_base_name().to_lowercase();
+                let prefix = if lower == suffix {
+                    String::new()
+                }
+                else if lower.ends_with( & format!( "_{}", suffix ) ) {
+                    name[ .. name.len() - suffix.len() - 1 ].to_string()
+                }
+                else {
+                    continue;
+                };
+                if ! prefixes.contains( & prefix ) {
+                    prefixes.push( prefix.clone() );
+                }
+                groups.entry( prefix ).or_default().push( ( name.clone(), member.get_base_name().clone() ) );
+            }
+        }
+        for prefix in prefixes {
+            candidates.push( rate_group( & prefix, & template, & groups[ & prefix ], ports ) );
+        }
+    }
+    candidates.sort_by( | a, b | b.confidence.partial_cmp( & a.confidence ).unwrap()
+        .then( b.ports.len().cmp( & a.ports.len() ) ) );
+    let mut taken : Vec< String > = Vec::new();
+    let mut inferred = Vec::new();
+    for candidate in candidates {
+        let claimed : Vec< & String > = candidate.ports.iter().map( | ( n, _ ) | n )
+            .chain( candidate.mismatched.iter() ).collect();
+        if candidate.ports.is_empty() || claimed.iter().any( | n | taken.contains( n ) ) {
+            continue;
+        }
+        taken.extend( claimed.into_iter().cloned() );
+        inferred.push( candidate );
+    }
+    inferred
+}
+
+/**
+ * Picks the role of a group of ports named after members of `template` and rates it.
+ */
+fn rate_group( prefix : & str, template : & EntityInterface, group : & [ ( String, String ) ],
+        ports : & [ Port ] ) -> InferredInterface {
+    let mut roles : Vec< ( String, EntityInterface ) > = Vec::new();
+    if template.get_views().is_empty() {
+        roles.push( ( String::new(), template.clone() ) );
+        roles.push( ( String::new(), template.clone_inverted() ) );
+    }
+    for view in template.get_views() {
+        roles.push( ( view.get_name().clone(), template.clone_view( view.get_name() ).unwrap() ) );
+    }
+    let mut role = String::new();
+    let mut matched : Vec< ( String, String ) > = Vec::new();
+    let mut mismatched : Vec< String > = group.iter().map( | ( n, _ ) | n.clone() ).collect();
+    for ( view_name, interface ) in roles {
+        let mut view_matched = Vec::new();
+        let mut view_mismatched = Vec::new();
+        for ( name, member ) in group {
+            let port = ports.iter().find( | p | & p.get_name() == name ).unwrap();
+            let expected = interface.get_ports().iter().find( | p | p.get_base_name() == member ).unwrap();
+            if port.get_direction() == expected.get_direction() {
+                view_matched.push( ( name.clone(), member.clone() ) );
+            }
+            else {
+                view_mismatched.push( name.clone() );
+            }
+        }
+        if view_matched.len() > matched.len() {
+            role = view_name;
+            matched = view_matched;
+            mismatched = view_mismatched;
+        }
+    }
+    let mut required : Vec< & String > = template.get_ports().iter().filter( | p | ! p.is_optional() )
+        .map( | p | p.get_base_name() ).collect();
+    if required.is_empty() {
+        required = template.get_ports().iter().map( | p | p.get_base_name() ).collect();
+    }
+    let missing : Vec< String > = required.iter().filter( | r | ! matched.iter().any( | ( _, m ) | m == ** r ) )
+        .map( | r | r.to_string() ).collect();
+    let coverage = ( required.len() - missing.len() ) as f64 / required.len() as f64;
+    let consistency = matched.len() as f64 / group.len() as f64;
+    let support = ( matched.len() as f64 / required.len().min( 2 ) as f64 ).min( 1.0 );
+    InferredInterface { name : prefix.to_string(), class : template.get_class().clone(), role,
+        ports : matched, missing, mismatched, confidence : coverage * consistency * support }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vhdl::direction::Direction;
+
+    fn get_avalon_ports( prefix : & str, inverted : bool ) -> Vec< Port > {
+        let mut ports = vec![
+            Port::new( & format!( "{}_address", prefix ), Direction::OUT, "std_logic_vector( 15 downto 0 )" ),
+            Port::new( & format!( "{}_read", prefix ), Direction::OUT, "std_logic" ),
+            Port::new( & format!( "{}_read_data", prefix ), Direction::IN, "std_logic_vector( 31 downto 0 )" ),
+            Port::new( & format!( "{}_write", prefix ), Direction::OUT, "std_logic" ),
+            Port::new( & format!( "{}_write_data", prefix ), Direction::OUT, "std_logic_vector( 31 downto 0 )" ),
+            Port::new( & format!( "{}_waitrequest", prefix ), Direction::IN, "std_logic" ) ];
+        if inverted {
+            for port in & mut ports {
+                port.invert();
+            }
+        }
+        ports
+    }
+
+    #[test]
+    fn infer_both_directions() {
+        let mut ports = vec![ Port::new( "clk", Direction::IN, "std_logic" ),
+            Port::new( "rst", Direction::IN, "std_logic" ) ];
+        ports.extend( get_avalon_ports( "m0", false ) );
+        ports.extend( get_avalon_ports( "S1", true ) );
+        ports.push( Port::new( "led", Direction::OUT, "std_logic" ) );
+        let inferred = infer_interfaces( & ports, & InterfaceRegistry::new() );
+        let summary : Vec< String > = inferred.iter().map( | i | i.to_string() ).collect();
+        assert_eq!( summary, vec![ "m0 : avalon_mmi@host (1.00)", "S1 : avalon_mmi@agent (1.00)",
+            "<unnamed> : clk_rst@sink (1.00)" ] );
+        assert_eq!( inferred[ 0 ].get_ports()[ 2 ], ( "m0_read_data".to_string(), "read_data".to_string() ) );
+    }
+
+    #[test]
+    fn partial_match() {
+        let mut ports = get_avalon_ports( "m0", false );
+        ports.retain( | p | p.get_name() != "m0_write_data" );
+        ports[ 2 ].invert();
+        let inferred = infer_interfaces( & ports, & InterfaceRegistry::new() );
+        let summary : Vec< String > = inferred.iter().map( | i | i.to_string() ).collect();
+        assert_eq!( summary, vec![
+            "m0 : avalon_mmi@host (0.48, missing read_data, write_data, direction of m0_read_data)" ] );
+        assert!( ( inferred[ 0 ].get_confidence() - 0.6 * 0.8 ).abs() < 1e-9 );
+    }
+
+    #[test]
+    fn to_entity_interface() -> Result< (), VhdlError > {
+        let ports = get_avalon_ports( "S1", true );
+        let inferred = infer_interfaces( & ports, & InterfaceRegistry::new() );
+        let interface = inferred[ 0 ].to_entity_interface( & ports, & InterfaceRegistry::new() )?;
+        assert_eq!( interface.get_name(), "S1" );
+        assert_eq!( interface.get_role(), "agent" );
+        assert!( interface.get_generics().is_empty() );
+        assert_eq!( interface.get_ports()[ 0 ].get_name(), "S1_address" );
+        assert_eq!( interface.get_ports()[ 0 ].get_data_type(), "std_logic_vector( 15 downto 0 )" );
+        assert_eq!( interface.get_ports()[ 0 ].get_direction(), Direction::IN );
+        assert!( interface.get_ports()[ 5 ].is_optional() );
+        Ok(())
+    }
+}
@@ -0,0 +1,359 @@
+use crate::element::Element;
+use crate::vhdl::design_unit::DesignUnit;
+use crate::vhdl::direction::Direction;
+use crate::vhdl::entity_interface::EntityInterface;
+use crate::vhdl::keywords::*;
+use crate::vhdl::known_libraries::get_known_library_use;
+use crate::vhdl::library_list::LibraryList;
+use crate::vhdl::port::Port;
+use crate::vhdl::record_type::RecordType;
+use crate::vhdl::vhdl_error::VhdlError;
+
+/**
+ * VHDL revision the record ports of an interface are generated for.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VhdlStandard {
+    /// An input and an output record port per interface
+    VHDL2008,
+    /// One record port per interface with the mode view of its role
+    VHDL2019,
+}
+
+#[derive(Clone)]
+struct ModeView {
+    name : String,
+    record : String,
+    modes : Vec< ( String, Direction ) >,
+}
+
+/**
+ * Package with the record types of an interface class and, for VHDL-2019, a mode view for
+ * every role.  For VHDL-2008 the ports are split into the record `<class>_in_t` of the inputs
+ * and `<class>_out_t` of the outputs of the first role.  Fixed ports are no record elements,
+ * they stay single ports.
+ */
+#[derive(Clone)]
+pub struct InterfacePackage {
+    name : String,
+    standard : VhdlStandard,
+    libraries : LibraryList,
+    records : Vec< RecordType >,
+    views : Vec< ModeView >,
+    packing : bool,
+}
+
+impl InterfacePackage {
+    pub fn from_entity_interface( interface : & EntityInterface, standard : VhdlStandard )
+            -> Result< InterfacePackage, VhdlError > {
+        let class = interface.get_class();
+        if interface.get_views().is_empty() {
+            return Err( VhdlError::new( & format!(
+                "error: Interface class {:?} needs role views for record ports!", class ) ) );
+        }
+        let mut package = InterfacePackage { name : get_package_name( class ), standard,
+                libraries : LibraryList::new(), records : Vec::new(), views : Vec::new(),
+                packing : false };
+        let ports : Vec< & Port > = interface.get_ports().iter().filter( | p | ! p.is_fixed() ).collect();
+        for port in & ports {
+            if let Some( library_use ) = get_known_library_use( port.get_data_type() ) {
+                package.libraries.add_library_use( library_use );
+            }
+        }
+        match standard {
+            VhdlStandard::VHDL2019 => {
+                let record = format!( "{}_t", class );
+                let mut record_type = RecordType::new( & record );
+                for port in & ports {
+                    record_type.add_element( port.get_base_name(), port.get_data_type() );
+                }
+                package.records.push( record_type );
+                for view in interface.get_views() {
+                    let modes = ports.iter()
+                            .map( | p | ( p.get_base_name().clone(), view.get_mode( p.get_base_name() ).unwrap() ) )
+                            .collect();
+                    package.views.push( ModeView { name : get_view_name( class, view.get_name() ),
+                            record : record.clone(), modes } );
+                }
+            },
+            VhdlStandard::VHDL2008 => {
+                let first = & interface.get_views()[ 0 ];
+                let mut inputs = RecordType::new( & format!( "{}_in_t", class ) );
+                let mut outputs = RecordType::new( & format!( "{}_out_t", class ) );
+                for port in & ports {
+                    match first.get_mode( port.get_base_name() ).unwrap() {
+                        Direction::IN => inputs.add_element( port.get_base_name(), port.get_data_type() ),
+                        Direction::OUT | Direction::BUFFER => outputs.add_element( port.get_base_name(), port.get_data_type() ),
+                        Direction::INOUT => return Err( VhdlError::new( & format!(
+                            "error: Inout port {:?} of interface class {:?} can not be split into records!",
+                            port.get_base_name(), class ) ) ),
+                    }
+                }
+                package.records = vec![ inputs, outputs ].into_iter().filter( | r | ! r.get_elements().is_empty() ).collect();
+                for record in & package.records {
+                    for view in interface.get_views() {
+                        let inputs = record.get_elements().iter()
+                                .filter( | e | view.get_mode( e.get_name() ) == Some( Direction::IN ) ).count();
+                        if inputs != 0 && inputs != record.get_elements().len() {
+                            return Err( VhdlError::new( & format!(
+                                "error: Record {:?} has inputs and outputs in role {:?}!",
+                                record.get_name(), view.get_name() ) ) );
+                        }
+                    }
+                }
+            },
+        }
+        Ok( package )
+    }
+
+    pub fn get_name( & self ) -> & String {
+        & self.name
+    }
+
+    pub fn get_standard( & self ) -> VhdlStandard {
+        self.standard
+    }
+
+    pub fn get_records( & self ) -> & Vec< RecordType > {
+        & self.records
+    }
+
+    /**
+     * Adds a record type declared beside the interface records, e.g. for a payload.
+     */
+    pub fn add_record_type( & mut self, record : RecordType ) -> Result< (), VhdlError > {
+        if self.packing {
+            record.get_packing_declarations( 0 )?;
+        }
+        for element in record.get_elements() {
+            if let Some( library_use ) = get_known_library_use( element.get_data_type() ) {
+                self.libraries.add_library_use( library_use );
+            }
+        }
+        self.records.push( record );
+        Ok(())
+    }
+
+    /**
+     * Adds the functions `to_slv` and `from_slv` and the width of every record, see
+     * `RecordType::get_packing_declarations`.  All records have to be packable.
+     */
+    pub fn set_packing( & mut self, packing : bool ) -> Result< (), VhdlError > {
+        if packing {
+            for record in & self.records {
+                record.get_packing_declarations( 0 )?;
+            }
+        }
+        self.packing = packing;
+        Ok(())
+    }
+
+    pub fn has_packing( & self ) -> bool {
+        self.packing
+    }
+
+    /**
+     * Returns the record ports replacing the non-fixed ports of `interface`, which has to be of
+     * the class of the package.  The ports are named like the interface, or the class if it is
+     * unnamed, with suffix `_in` and `_out` for VHDL-2008.
+     */
+    pub fn get_record_ports( & self, interface : & EntityInterface ) -> Vec< Port > {
+        let name = match interface.get_name().is_empty() {
+            true => interface.get_class(),
+            false => interface.get_name(),
+        };
+        let mut ports = Vec::new();
+        for record in & self.records {
+            let data_type = record.get_subtype();
+            match self.standard {
+                VhdlStandard::VHDL2019 => {
+                    let view = get_view_name( interface.get_class(), interface.get_role() );
+                    ports.push( Port::new_with_view( name, & view, & data_type ) );
+                },
+                VhdlStandard::VHDL2008 => {
+                    let is_input = interface.get_ports().iter()
+                            .find( | p | p.get_base_name() == record.get_elements()[ 0 ].get_name() )
+                            .is_some_and( | p | p.get_direction() == Direction::IN );
+                    match is_input {
+                        true => ports.push( Port::new( & format!( "{}_{}", name, IN ), Direction::IN, & data_type ) ),
+                        false => ports.push( Port::new( & format!( "{}_{}", name, OUT ), Direction::OUT, & data_type ) ),
+                    }
+                },
+            }
+        }
+        ports
+    }
+}
+
+impl Element for InterfacePackage {
+    fn to_source_code( & self, indent : usize ) -> String {
+        let mut source = String::new();
+        let indent_str = crate::util::indent( indent );
+        let declaration_indent_str = crate::util::indent( indent + 1 );
+        let element_indent_str = crate::util::indent( indent + 2 );
+        source.push_str( & self.libraries.to_source_code( indent ) );
+        source.push_str( & format!( "{}{} {} {}\n", indent_str, PACKAGE, self.name, IS ) );
+        let mut declarations = Vec::new();
+        for record in & self.records {
+            declarations.push( record.to_source_code( indent + 1 ) );
+        }
+        for view in & self.views {
+            let mut declaration = format!( "{}{} {} {} {} {}\n", declaration_indent_str, VIEW, view.name,
+                    OF, view.record, IS );
+            for ( element, mode ) in & view.modes {
+                declaration.push_str( & format!( "{}{} : {};\n", element_indent_str, element, mode ) );
+            }
+            declaration.push_str( & format!( "{}{} {} {};\n", declaration_indent_str, END, VIEW,
+                    view.name ) );
+            declarations.push( declaration );
+        }
+        if self.packing {
+            for record in & self.records {
+                declarations.push( record.get_packing_declarations( indent + 1 ).unwrap() );
+            }
+        }
+        source.push_str( & declarations.join( "\n" ) );
+        source.push_str( & format!( "{}{} {} {};\n", indent_str, END, PACKAGE, self.name ) );
+        if self.packing {
+            let bodies : Vec< String > = self.records.iter()
+                    .map( | r | r.get_packing_bodies( indent + 1 ).unwrap() ).collect();
+            source.push_str( & format!( "\n{}{} {} {} {}\n{}{}{} {} {} {};\n", indent_str, PACKAGE, BODY,
+                    self.name, IS, bodies.join( "\n" ), indent_str, END, PACKAGE, BODY, self.name ) );
+        }
+        source
+    }
+}
+
+impl DesignUnit for InterfacePackage {
+}
+
+/**
+ * Name of the package with the record types of an interface class.
+ */
+pub fn get_package_name( class : & str ) -> String {
+    format!( "{}_pkg", class )
+}
+
+/**
+ * Name of the VHDL-2019 mode view of a role.
+ */
+pub fn get_view_name( class : & str, role : & str ) -> String {
+    format!( "{}_{}_view", class, role )
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vhdl::interface_catalog::get_interface;
+
+    const PACKAGE_2019 : & str = concat!( "library ieee;\n",
+        "    use ieee.std_logic_1164.all;\n",
+        "\n",
+        "package stream_pkg is\n",
+        "    type stream_t is record\n",
+        "        data : std_logic_vector;\n",
+        "        valid : std_logic;\n",
+        "        ready : std_logic;\n",
+        "    end record stream_t;\n",
+        "\n",
+        "    view stream_source_view of stream_t is\n",
+        "        data : out;\n",
+        "        valid : out;\n",
+        "        ready : in;\n",
+        "    end view stream_source_view;\n",
+        "\n",
+        "    view stream_sink_view of stream_t is\n",
+        "        data : in;\n",
+        "        valid : in;\n",
+        "        ready : out;\n",
+        "    end view stream_sink_view;\n",
+        "end package stream_pkg;\n" );
+
+    const PACKAGE_2008 : & str = concat!( "library ieee;\n",
+        "    use ieee.std_logic_1164.all;\n",
+        "\n",
+        "package stream_pkg is\n",
+        "    type stream_in_t is record\n",
+        "        ready : std_logic;\n",
+        "    end record stream_in_t;\n",
+        "\n",
+        "    type stream_out_t is record\n",
+        "        data : std_logic_vector;\n",
+        "        valid : std_logic;\n",
+        "    end record stream_out_t;\n",
+        "end package stream_pkg;\n" );
+
+    fn new_stream_interface() -> EntityInterface {
+        let mut interface = EntityInterface::new( "", "stream" );
+        let mut clk = Port::new( "clk", Direction::IN, "std_logic" );
+        clk.set_fixed( true );
+        interface.add_port( clk );
+        interface.add_port( Port::new( "data", Direction::OUT, "std_logic_vector( WIDTH - 1 downto 0 )" ) );
+        interface.add_port( Port::new( "valid", Direction::OUT, "std_logic" ) );
+        interface.add_port( Port::new( "ready", Direction::IN, "std_logic" ) );
+        interface.set_views( "source", "sink" );
+        interface.rename( "s" );
+        interface
+    }
+
+    #[test]
+    fn vhdl_2019() -> Result< (), VhdlError > {
+        let interface = new_stream_interface();
+        let package = InterfacePackage::from_entity_interface( & interface, VhdlStandard::VHDL2019 )?;
+        assert_eq!( package.to_source_code( 0 ), PACKAGE_2019 );
+        let ports = package.get_record_ports( & interface.clone_inverted() );
+        assert_eq!( ports.len(), 1 );
+        assert_eq!( ports[ 0 ].to_source_code( 0 ),
+                "s : view stream_sink_view of stream_t( data( WIDTH - 1 downto 0 ) )" );
+        Ok(())
+    }
+
+    #[test]
+    fn vhdl_2008() -> Result< (), VhdlError > {
+        let mut interface = new_stream_interface();
+        let package = InterfacePackage::from_entity_interface( & interface, VhdlStandard::VHDL2008 )?;
+        assert_eq!( package.to_source_code( 0 ), PACKAGE_2008 );
+        let ports : Vec< String > = package.get_record_ports( & interface.clone_inverted() ).iter()
+                .map( | p | p.to_source_code( 0 ) ).collect();
+        assert_eq!( ports, vec![ "s_out : out stream_in_t", "s_in : in stream_out_t( data( WIDTH - 1 downto 0 ) )" ] );
+        interface.add_port( Port::new( "error", Direction::INOUT, "std_logic" ) );
+        assert!( InterfacePackage::from_entity_interface( & interface, VhdlStandard::VHDL2008 ).is_err() );
+        Ok(())
+    }
+
+    #[test]
+    fn packing() -> Result< (), VhdlError > {
+        let mut package = InterfacePackage::from_entity_interface( & new_stream_interface(), VhdlStandard::VHDL2008 )?;
+        let mut payload = RecordType::new( "payload_t" );
+        payload.add_element( "value", "integer" );
+        package.add_record_type( payload.clone() )?;
+        assert!( package.set_packing( true ).is_err() );
+        package.records.pop();
+        package.set_packing( true )?;
+        assert!( package.add_record_type( payload ).is_err() );
+        let source = package.to_source_code( 0 );
+        assert!( source.contains( concat!( "    -- stream_in_t bit layout\n",
+                "    --   bits  element  type\n",
+                "    --   0     ready    std_logic\n",
+                "    constant STREAM_IN_WIDTH : natural := 1;\n" ) ) );
+        assert!( source.contains( "    function stream_out_width( WIDTH : natural ) return natural;\n" ) );
+        assert!( source.contains( concat!( "end package stream_pkg;\n", "\n", "package body stream_pkg is\n",
+                "    function to_slv( r : stream_in_t ) return std_logic_vector is\n",
+                "    begin\n",
+                "        return ( 0 => r.ready );\n" ) ) );
+        assert!( source.ends_with( "    end function from_slv;\nend package body stream_pkg;\n" ) );
+        Ok(())
+    }
+
+    #[test]
+    fn catalog() -> Result< (), VhdlError > {
+        let interface = get_interface( "axi4_lite" )?;
+        let package = InterfacePackage::from_entity_interface( & interface, VhdlStandard::VHDL2019 )?;
+        assert_eq!( package.get_name(), "axi4_lite_pkg" );
+        assert!( package.to_source_code( 0 ).contains( "    view axi4_lite_agent_view of axi4_lite_t is\n" ) );
+        assert!( InterfacePackage::from_entity_interface( & EntityInterface::new( "", "test" ),
+                VhdlStandard::VHDL2019 ).is_err() );
+        Ok(())
+    }
+}
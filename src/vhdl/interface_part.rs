@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+/**
+ * A sub-interface included into an interface description, kept for documentation after the
+ * ports were flattened into the including interface.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfacePart {
+    prefix : String,
+    class : String,
+    inverted : bool,
+    role : String,
+    ports : Vec< String >,
+}
+
+impl InterfacePart {
+    pub fn new( prefix : & str, class : & str, inverted : bool, role : & str ) -> InterfacePart {
+        InterfacePart { prefix : prefix.to_string(), class : class.to_string(), inverted,
+            role : role.to_string(), ports : Vec::new() }
+    }
+
+    /**
+     * Prefix of the port names of the sub-interface, e.g. `aw` for `awaddr`.
+     */
+    pub fn get_prefix( & self ) -> & String {
+        & self.prefix
+    }
+
+    pub fn get_class( & self ) -> & String {
+        & self.class
+    }
+
+    /**
+     * The sub-interface was included in its inverted view.
+     */
+    pub fn is_inverted( & self ) -> bool {
+        self.inverted
+    }
+
+    /**
+     * Role view the sub-interface was included in, empty if it has no views.
+     */
+    pub fn get_role( & self ) -> & String {
+        & self.role
+    }
+
+    /**
+     * Base names of the ports of the sub-interface in the including interface.
+     */
+    pub fn get_ports( & self ) -> & Vec< String > {
+        & self.ports
+    }
+
+    pub fn add_port( & mut self, name : & str ) {
+        self.ports.push( name.to_string() );
+    }
+
+    pub fn retain_ports< F : Fn( & str ) -> bool >( & mut self, predicate : F ) {
+        self.ports.retain( | p | predicate( p ) );
+    }
+
+    /**
+     * Renames the ports found in `names` all at once, so names may be swapped.
+     */
+    pub fn rename_ports( & mut self, names : & HashMap< String, String > ) {
+        for entry in & mut self.ports {
+            if let Some( name ) = names.get( entry ) {
+                * entry = name.clone();
+            }
+        }
+    }
+}
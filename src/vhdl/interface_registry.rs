@@ -0,0 +1,344 @@
+use std::cmp::Ordering;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{ Path, PathBuf };
+use crate::vhdl::entity_interface::EntityInterface;
+use crate::vhdl::interface_catalog;
+use crate::vhdl::role_view::split_role_reference;
+use crate::vhdl::vhdl_error::VhdlError;
+
+/**
+ * Environment variable with the interface search paths, separated like `PATH`.
+ */
+pub const INTERFACE_PATH_VARIABLE : &str = "SOURCE_GENERATOR_INTERFACE_PATH";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IssueKind {
+    /// The same definition was found twice
+    DUPLICATE,
+    /// Two different definitions share class and version
+    CONFLICT,
+    /// A file is not valid JSON
+    INVALID,
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt( & self, f : & mut fmt::Formatter ) -> fmt::Result {
+        match self {
+            IssueKind::DUPLICATE => write!( f, "duplicate" ),
+            IssueKind::CONFLICT => write!( f, "conflict" ),
+            IssueKind::INVALID => write!( f, "invalid" ),
+        }
+    }
+}
+
+/**
+ * A class definition found more than once, the registry keeps the `first` one, or a file
+ * `first` that can not be read.
+ */
+#[derive(Debug, Clone)]
+pub struct RegistryIssue {
+    kind : IssueKind,
+    class : String,
+    version : String,
+    first : PathBuf,
+    second : PathBuf,
+    message : String,
+}
+
+impl RegistryIssue {
+    pub fn get_kind( & self ) -> IssueKind {
+        self.kind
+    }
+
+    pub fn get_class( & self ) -> & String {
+        & self.class
+    }
+
+    pub fn get_version( & self ) -> & String {
+        & self.version
+    }
+
+    pub fn get_first( & self ) -> & PathBuf {
+        & self.first
+    }
+
+    pub fn get_second( & self ) -> & PathBuf {
+        & self.second
+    }
+
+    /**
+     * Parser error of an invalid file, empty otherwise.
+     */
+    pub fn get_message( & self ) -> & String {
+        & self.message
+    }
+}
+
+impl fmt::Display for RegistryIssue {
+    fn fmt( & self, f : & mut fmt::Formatter ) -> fmt::Result {
+        if self.kind == IssueKind::INVALID {
+            return write!( f, "{}: {:?}: {}", self.kind, self.first.to_string_lossy(), self.message );
+        }
+        write!( f, "{}: class {:?} version {:?} in {:?} and {:?}", self.kind, self.class, self.version,
+            self.first.to_string_lossy(), self.second.to_string_lossy() )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RegistryEntry {
+    class : String,
+    version : String,
+    path : PathBuf,
+    description : serde_json::Value,
+}
+
+/**
+ * Index of the interface descriptions in a list of search paths by class and version.  The
+ * descriptions are loaded on demand, classes not found on the search paths fall back to the
+ * built-in catalog.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct InterfaceRegistry {
+    search_paths : Vec< PathBuf >,
+    entries : Vec< RegistryEntry >,
+    issues : Vec< RegistryIssue >,
+}
+
+impl InterfaceRegistry {
+    pub fn new() -> InterfaceRegistry {
+        InterfaceRegistry::default()
+    }
+
+    /**
+     * Creates a registry with the search paths of `INTERFACE_PATH_VARIABLE`.
+     */
+    pub fn from_env() -> Result< InterfaceRegistry, Box< dyn Error > > {
+        let mut registry = InterfaceRegistry::new();
+        if let Ok( paths ) = env::var( INTERFACE_PATH_VARIABLE ) {
+            registry.add_search_path_list( & paths )?;
+        }
+        Ok( registry )
+    }
+
+    /**
+     * Adds the search paths of a list separated like `PATH`.
+     */
+    pub fn add_search_path_list( & mut self, paths : & str ) -> Result< (), Box< dyn Error > > {
+        for path in env::split_paths( paths ).filter( | p | ! p.as_os_str().is_empty() ) {
+            self.add_search_path( & path )?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Indexes the JSON files with a `class` in the directory `path`.  Search paths added first
+     * take precedence.  Files that are not valid JSON are recorded as issues.
+     */
+    pub fn add_search_path( & mut self, path : & Path ) -> Result< (), Box< dyn Error > > {
+        let mut files : Vec< PathBuf > = fs::read_dir( path )?
+                .filter_map( | entry | entry.ok().map( | e | e.path() ) )
+                .filter( | file | file.is_file() && file.extension().is_some_and( | e | e == "json" ) )
+                .collect();
+        files.sort();
+        for file in files {
+            let description : serde_json::Value = match serde_json::from_str( & fs::read_to_string( & file )? ) {
+                Ok( description ) => description,
+                Err( err ) => {
+                    self.issues.push( RegistryIssue { kind : IssueKind::INVALID, class : String::new(),
+                        version : String::new(), first : file, second : PathBuf::new(), message : err.to_string() } );
+                    continue;
+                },
+            };
+            let class = match description.get( "class" ).and_then( | c | c.as_str() ) {
+                Some( class ) => class.to_string(),
+                None => continue,
+            };
+            let version = description.get( "version" ).and_then( | v | v.as_str() ).unwrap_or( "" ).to_string();
+            self.add_entry( RegistryEntry { class, version, path : file, description } );
+        }
+        self.search_paths.push( path.to_path_buf() );
+        Ok(())
+    }
+
+    fn add_entry( & mut self, entry : RegistryEntry ) {
+        match self.entries.iter().find( | e | e.class == entry.class && e.version == entry.version ) {
+            Some( first ) => {
+                let kind = if first.description == entry.description { IssueKind::DUPLICATE }
+                    else { IssueKind::CONFLICT };
+                self.issues.push( RegistryIssue { kind, class : entry.class, version : entry.version,
+                    first : first.path.clone(), second : entry.path, message : String::new() } );
+            },
+            None => self.entries.push( entry ),
+        }
+    }
+
+    pub fn get_search_paths( & self ) -> & Vec< PathBuf > {
+        & self.search_paths
+    }
+
+    /**
+     * Duplicate and conflicting definitions and invalid files found on the search paths.
+     */
+    pub fn get_issues( & self ) -> & Vec< RegistryIssue > {
+        & self.issues
+    }
+
+    /**
+     * Classes on the search paths and in the built-in catalog.
+     */
+    pub fn get_classes( & self ) -> Vec< String > {
+        let mut classes : Vec< String > = Vec::new();
+        for class in self.entries.iter().map( | e | e.class.as_str() ).chain( interface_catalog::get_classes() ) {
+            if ! classes.iter().any( | c | c == class ) {
+                classes.push( class.to_string() );
+            }
+        }
+        classes
+    }
+
+    /**
+     * File of the latest version of `class` on the search paths.
+     */
+    pub fn get_path( & self, class : & str ) -> Option< & PathBuf > {
+        self.entries.iter().filter( | e | e.class == class )
+                .max_by( | a, b | compare_versions( & a.version, & b.version ) )
+                .map( | e | & e.path )
+    }
+
+    /**
+     * Loads the unnamed latest version of `class`, in the role view if given like
+     * `avalon_mmi@agent`.
+     */
+    pub fn get( & self, class : & str ) -> Result< EntityInterface, Box< dyn Error > > {
+        let ( class_name, role ) = split_role_reference( class );
+        let mut interface = match self.get_path( class_name ) {
+            Some( path ) => EntityInterface::from_file_unnamed( path )?,
+            None => return Ok( interface_catalog::get_interface( class )? ),
+        };
+        if let Some( role ) = role {
+            interface.set_role( role )?;
+        }
+        Ok( interface )
+    }
+
+    pub fn get_version( & self, class : & str, version : & str ) -> Result< EntityInterface, Box< dyn Error > > {
+        match self.entries.iter().find( | e | e.class == class && e.version == version ) {
+            Some( entry ) => EntityInterface::from_file_unnamed( & entry.path ),
+            None => Ok( interface_catalog::get_interface_version( class, version )? ),
+        }
+    }
+
+    /**
+     * Loads the unnamed latest version of `class` in the direction view `view`.
+     */
+    pub fn get_view( & self, class : & str, view : & str ) -> Result< EntityInterface, Box< dyn Error > > {
+        Ok( self.get( class )?.clone_view( view )? )
+    }
+
+    /**
+     * Loads the latest version of `class` with the interface name `name`.
+     */
+    pub fn get_named( & self, name : & str, class : & str ) -> Result< EntityInterface, Box< dyn Error > > {
+        let mut interface = self.get( class )?;
+        interface.rename( name );
+        Ok( interface )
+    }
+
+    /**
+     * Fails with all issues if the search paths contain conflicting definitions or invalid files.
+     */
+    pub fn check( & self ) -> Result< (), VhdlError > {
+        let conflicts : Vec< String > = self.issues.iter().filter( | i | i.kind != IssueKind::DUPLICATE )
+                .map( | i | i.to_string() ).collect();
+        if conflicts.is_empty() {
+            Ok(())
+        }
+        else {
+            Err( VhdlError::new( & format!( "error: Conflicting or invalid interface definitions!\n{}",
+                conflicts.join( "\n" ) ) ) )
+        }
+    }
+}
+
+/**
+ * Compares dotted version numbers component wise, e.g. `1.10` is newer than `1.9`.
+ */
+fn compare_versions( a : & str, b : & str ) -> Ordering {
+    let parse = | v : & str | -> Vec< u64 > { v.split( '.' ).map( | n | n.parse().unwrap_or( 0 ) ).collect() };
+    parse( a ).cmp( & parse( b ) )
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vhdl::direction::Direction;
+
+    fn new_test_registry() -> InterfaceRegistry {
+        let mut registry = InterfaceRegistry::new();
+        registry.add_search_path_list( "tests/vhdl/registry/a:tests/vhdl/registry/b" ).unwrap();
+        registry
+    }
+
+    #[test]
+    fn get() -> Result< (), Box< dyn Error > > {
+        let registry = new_test_registry();
+        assert_eq!( registry.get_search_paths().len(), 2 );
+        let interface = registry.get( "avalon_mmi" )?;
+        assert_eq!( interface.get_class(), "avalon_mmi" );
+        assert_eq!( interface.get_ports().len(), 7 );
+        assert_eq!( registry.get_named( "bus", "avalon_mmi" )?.get_ports()[ 0 ].get_name(), "bus_address" );
+        assert!( registry.get( "pci" ).is_err() );
+        Ok(())
+    }
+
+    #[test]
+    fn versions() -> Result< (), Box< dyn Error > > {
+        let registry = new_test_registry();
+        assert_eq!( registry.get( "stream" )?.get_version(), "2.0" );
+        assert_eq!( registry.get_version( "stream", "1.0" )?.get_ports().len(), 2 );
+        assert!( registry.get_version( "stream", "3.0" ).is_err() );
+        assert_eq!( compare_versions( "1.10", "1.9" ), Ordering::Greater );
+        Ok(())
+    }
+
+    #[test]
+    fn issues() {
+        let registry = new_test_registry();
+        let issues = registry.get_issues();
+        assert_eq!( issues.len(), 2 );
+        assert_eq!( issues[ 0 ].get_kind(), IssueKind::DUPLICATE );
+        assert_eq!( issues[ 0 ].get_class(), "avalon_mmi" );
+        assert_eq!( issues[ 1 ].get_kind(), IssueKind::CONFLICT );
+        assert_eq!( issues[ 1 ].get_class(), "stream" );
+        assert_eq!( issues[ 1 ].get_first(), & PathBuf::from( "tests/vhdl/registry/a/stream.json" ) );
+        assert!( registry.check().is_err() );
+    }
+
+    #[test]
+    fn invalid_files() {
+        let mut registry = InterfaceRegistry::new();
+        registry.add_search_path( Path::new( "tests/vhdl/registry/invalid" ) ).unwrap();
+        let issues = registry.get_issues();
+        assert_eq!( issues.len(), 1 );
+        assert_eq!( issues[ 0 ].get_kind(), IssueKind::INVALID );
+        assert_eq!( issues[ 0 ].get_first(), & PathBuf::from( "tests/vhdl/registry/invalid/stream.json" ) );
+        assert!( ! issues[ 0 ].get_message().is_empty() );
+        assert!( registry.get_path( "stream" ).is_none() );
+        assert!( registry.check().is_err() );
+    }
+
+    #[test]
+    fn catalog_fallback() -> Result< (), Box< dyn Error > > {
+        let registry = InterfaceRegistry::new();
+        assert!( registry.check().is_ok() );
+        assert!( registry.get_classes().contains( & "wishbone".to_string() ) );
+        let agent = registry.get_view( "wishbone", "agent" )?;
+        assert_eq!( agent.get_ports()[ 0 ].get_direction(), Direction::IN );
+        assert_eq!( registry.get( "wishbone@agent" )?.get_ports()[ 0 ].get_direction(), Direction::IN );
+        Ok(())
+    }
+}
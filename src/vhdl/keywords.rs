@@ -4,6 +4,7 @@ pub const SINGLE_LINE_COMMENT : &'static str = "--";
 pub const LIBRARY : &'static str = "library";
 pub const USE : &'static str = "use";
 pub const ALL : &'static str = "all";
+pub const WORK : & str = "work";
 
 pub const ENTITY : &'static str = "entity";
 pub const IS : &'static str = "is";
@@ -24,4 +25,19 @@ pub const SIGNAL : &'static str = "signal";
 pub const PROCESS : &'static str = "process";
 pub const VARIABLE : &'static str = "variable";
 pub const MAP : &'static str = "map";
+pub const OPEN : & str = "open";
 
+pub const IF : & str = "if";
+pub const ELSIF : & str = "elsif";
+pub const ELSE : & str = "else";
+pub const THEN : & str = "then";
+
+pub const PACKAGE : & str = "package";
+pub const BODY : & str = "body";
+pub const TYPE : & str = "type";
+pub const RECORD : & str = "record";
+pub const VIEW : & str = "view";
+pub const FUNCTION : & str = "function";
+pub const RETURN : & str = "return";
+pub const TO : & str = "to";
+pub const DOWNTO : & str = "downto";
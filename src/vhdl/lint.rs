@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use crate::vhdl::architecture::Architecture;
+use crate::vhdl::direction::Direction;
+use crate::vhdl::actual::Actual;
+use crate::vhdl::expression::{ normalize_data_type, substitute_identifiers };
+use crate::vhdl::lint_finding::LintFinding;
+use crate::vhdl::netlist::Netlist;
+
+/**
+ * Runs all design rule checks on the architecture and returns the findings in check order.
+ */
+pub fn lint( architecture : & Architecture ) -> Vec< LintFinding > {
+    let mut findings = Vec::new();
+    check_unbound_inputs( architecture, & mut findings );
+    check_unbound_generics( architecture, & mut findings );
+    let netlist = architecture.get_netlist();
+    check_multiple_drivers( & netlist, & mut findings );
+    check_signal_drivers_and_loads( architecture, & netlist, & mut findings );
+    check_data_types( architecture, & mut findings );
+    findings
+}
+
+pub fn has_errors( findings : & [ LintFinding ] ) -> bool {
+    findings.iter().any( | f | f.is_error() )
+}
+
+fn check_unbound_inputs( architecture : & Architecture, findings : & mut Vec< LintFinding > ) {
+    for ( name, instance ) in architecture.get_instances() {
+        for interface in instance.get_interfaces() {
+            for port in interface.get_ports() {
+                // unbound inputs with a default are left open
+                if matches!( port.get_direction(), Direction::IN ) && ! port.is_bound()
+                        && port.get_default().is_empty() {
+                    findings.push( LintFinding::error( & format!( "{}.{}", name, port.get_inner() ),
+                            "input port is not bound" ) );
+                }
+            }
+        }
+    }
+}
+
+fn check_unbound_generics( architecture : & Architecture, findings : & mut Vec< LintFinding > ) {
+    for ( name, instance ) in architecture.get_instances() {
+        for interface in instance.get_interfaces() {
+            for generic in interface.get_generics() {
+                if generic.requires_binding() && ! generic.is_bound() {
+                    findings.push( LintFinding::error(
+                            & format!( "{}.{}", name, generic.get_inner() ),
+                            "generic without default is not bound" ) );
+                }
+            }
+        }
+    }
+}
+
+fn check_multiple_drivers( netlist : & Netlist, findings : & mut Vec< LintFinding > ) {
+    for net in netlist.get_nets() {
+        let drivers = net.get_conflicting_drivers();
+        if drivers.len() > 1 {
+            let paths : Vec< String > = drivers.iter().map( | d | d.get_path() ).collect();
+            findings.push( LintFinding::error( net.get_name(),
+                    & format!( "driven by multiple outputs: {}", paths.join( ", " ) ) ) );
+        }
+    }
+}
+
+fn check_signal_drivers_and_loads( architecture : & Architecture, netlist : & Netlist,
+        findings : & mut Vec< LintFinding > ) {
+    for signal in architecture.get_declarations().get_signals() {
+        let ( has_driver, has_load ) = match netlist.get_net( signal.get_name() ) {
+            Some( net ) => ( ! net.get_drivers().is_empty(), ! net.get_loads().is_empty() ),
+            None => ( false, false ),
+        };
+        if ! has_driver {
+            findings.push( LintFinding::warning( signal.get_name(), "signal has no driver" ) );
+        }
+        if ! has_load {
+            findings.push( LintFinding::warning( signal.get_name(), "signal has no load" ) );
+        }
+    }
+}
+
+fn check_data_types( architecture : & Architecture, findings : & mut Vec< LintFinding > ) {
+    let mut data_types : HashMap< String, String > = HashMap::new();
+    for port in architecture.get_entity().get_ports() {
+        data_types.insert( port.get_name().to_lowercase(), port.get_data_type().clone() );
+    }
+    for signal in architecture.get_declarations().get_signals() {
+        data_types.insert( signal.get_name().to_lowercase(), signal.get_data_type().clone() );
+    }
+    for ( name, instance ) in architecture.get_instances() {
+        let generic_map = instance.get_generic_map();
+        for interface in instance.get_interfaces() {
+            for port in interface.get_ports() {
+                // only plain names can be resolved to a declaration
+                let net = match port.get_actual() {
+                    Some( Actual::Name( name ) ) => name.trim().to_string(),
+                    _ => continue,
+                };
+                let outer_type = match data_types.get( & net.to_lowercase() ) {
+                    Some( data_type ) => data_type,
+                    None => continue,
+                };
+                let inner_type = substitute_identifiers( port.get_data_type(), & generic_map );
+                if normalize_data_type( & inner_type ) != normalize_data_type( outer_type ) {
+                    findings.push( LintFinding::error(
+                            & format!( "{}.{}", name, port.get_inner() ),
+                            & format!( "data type {:?} does not match {:?} of {:?}",
+                                    inner_type, outer_type, net ) ) );
+                }
+            }
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vhdl::entity::Entity;
+    use crate::vhdl::instance::Instance;
+    use crate::vhdl::generic::Generic;
+    use crate::vhdl::port::Port;
+    use crate::vhdl::signal_declaration::SignalDeclaraion;
+    use crate::vhdl::signal_assignment::SignalAssignment;
+
+    fn new_sub() -> Entity {
+        let mut sub = Entity::new( "sub" );
+        sub.add_generic( Generic::new( "WIDTH", "positive" ) );
+        sub.add_port( Port::new( "a", Direction::IN, "std_logic_vector( WIDTH - 1 downto 0 )" ) );
+        sub.add_port( Port::new( "y", Direction::OUT, "std_logic" ) );
+        sub
+    }
+
+    fn paths( findings : & [ LintFinding ] ) -> Vec< String > {
+        findings.iter().map( | f | format!( "{} {}", f.get_severity(), f.get_path() ) ).collect()
+    }
+
+    #[test]
+    fn unbound_input_and_generic() {
+        let mut arch = Architecture::new( "rtl", & Entity::new( "top" ) );
+        arch.add_instance( Instance::from_entity( "u0", & new_sub() ) );
+        let findings = lint( & arch );
+        assert_eq!( paths( & findings ), vec![ "error u0.a", "error u0.WIDTH" ] );
+        assert!( has_errors( & findings ) );
+    }
+
+    #[test]
+    fn multiple_drivers() {
+        let mut top = Entity::new( "top" );
+        top.add_port( Port::new( "y", Direction::OUT, "std_logic" ) );
+        let mut arch = Architecture::new( "rtl", & top );
+        arch.add_instance( Instance::from_entity( "u0", & new_sub() ) );
+        arch.add_instance( Instance::from_entity( "u1", & new_sub() ) );
+        arch.connect_instance_to_port_by_name( "u0", "y", "y" ).unwrap();
+        arch.connect_instance_to_port_by_name( "u1", "y", "y" ).unwrap();
+        let findings : Vec< LintFinding > = lint( & arch ).into_iter()
+                .filter( | f | f.get_path() == "y" ).collect();
+        assert_eq!( findings.len(), 1 );
+        assert_eq!( findings[ 0 ].get_message(), "driven by multiple outputs: u0.y, u1.y" );
+    }
+
+    #[test]
+    fn drivers_of_disjoint_slices() {
+        let mut top = Entity::new( "top" );
+        top.add_port( Port::new( "data", Direction::OUT, "std_logic_vector( 7 downto 0 )" ) );
+        let mut sub = Entity::new( "sub" );
+        sub.add_port( Port::new( "y", Direction::OUT, "std_logic_vector( 3 downto 0 )" ) );
+        let mut arch = Architecture::new( "rtl", & top );
+        arch.add_instance( Instance::from_entity( "u0", & sub ) );
+        arch.add_instance( Instance::from_entity( "u1", & sub ) );
+        arch.add_instance( Instance::from_entity( "u2", & sub ) );
+        arch.connect_instance_port_to_actual( "u0", "y", & Actual::indexed( "data", "3 downto 0" ) ).unwrap();
+        arch.connect_instance_port_to_actual( "u1", "y", & Actual::indexed( "data", "7 downto 4" ) ).unwrap();
+        assert!( lint( & arch ).is_empty() );
+        let netlist = arch.get_netlist();
+        assert_eq!( netlist.get_net( "data" ).unwrap().get_data_type(), "std_logic_vector( 7 downto 0 )" );
+
+        arch.connect_instance_port_to_actual( "u2", "y", & Actual::indexed( "data", "5 downto 2" ) ).unwrap();
+        let findings = lint( & arch );
+        assert_eq!( findings.len(), 1 );
+        assert_eq!( findings[ 0 ].get_message(), "driven by multiple outputs: u0.y, u1.y, u2.y" );
+    }
+
+    #[test]
+    fn signal_without_driver_or_load() {
+        let mut arch = Architecture::new( "rtl", & Entity::new( "top" ) );
+        arch.add_signal_declaration( & SignalDeclaraion::new( "s", "std_logic" ) );
+        arch.add_signal_declaration( & SignalDeclaraion::new( "t", "std_logic" ) );
+        arch.add_signal_assignment( SignalAssignment::new( "t", "s" ) );
+        assert_eq!( paths( & lint( & arch ) ), vec![ "warning s", "warning t" ] );
+    }
+
+    #[test]
+    fn signal_missing_in_netlist() {
+        let mut top = Entity::new( "top" );
+        top.add_port( Port::new( "Data", Direction::IN, "std_logic" ) );
+        let mut arch = Architecture::new( "rtl", & top );
+        arch.add_signal_declaration( & SignalDeclaraion::new( "data", "std_logic" ) );
+        let mut findings = Vec::new();
+        check_signal_drivers_and_loads( & arch, & Netlist::new(), & mut findings );
+        assert_eq!( paths( & findings ), vec![ "warning data", "warning data" ] );
+        assert!( ! lint( & arch ).is_empty() );
+    }
+
+    #[test]
+    fn data_type_mismatch_by_name() {
+        let mut top = Entity::new( "top" );
+        top.add_generic( Generic::new( "WIDTH", "positive" ) );
+        top.add_port( Port::new( "a", Direction::IN, "std_logic_vector( WIDTH - 1 downto 0 )" ) );
+        top.add_port( Port::new( "b", Direction::IN, "std_logic" ) );
+        let mut arch = Architecture::new( "rtl", & top );
+        arch.add_instance( Instance::from_entity( "u0", & new_sub() ) );
+        arch.add_instance( Instance::from_entity( "u1", & new_sub() ) );
+        arch.connect_instance_unbound_by_name( "u0" ).unwrap();
+        arch.connect_instance_unbound_by_name( "u1" ).unwrap();
+        arch.connect_instance_to_port_by_name( "u0", "a", "a" ).unwrap();
+        arch.connect_instance_to_port_by_name( "u1", "a", "b" ).unwrap();
+        let findings : Vec< LintFinding > = lint( & arch ).into_iter()
+                .filter( | f | f.get_message().starts_with( "data type" ) ).collect();
+        assert_eq!( paths( & findings ), vec![ "error u1.a" ] );
+    }
+}
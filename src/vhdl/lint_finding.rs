@@ -0,0 +1,73 @@
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    ERROR,
+    WARNING,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt( & self, f : & mut std::fmt::Formatter ) -> std::fmt::Result {
+        match self {
+            Severity::ERROR => write!( f, "error" ),
+            Severity::WARNING => write!( f, "warning" ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LintFinding {
+    severity : Severity,
+    path : String,
+    message : String,
+}
+
+impl LintFinding {
+    pub fn new( severity : Severity, path : & str, message : & str ) -> LintFinding {
+        LintFinding { severity, path : path.to_string(), message : message.to_string() }
+    }
+
+    pub fn error( path : & str, message : & str ) -> LintFinding {
+        LintFinding::new( Severity::ERROR, path, message )
+    }
+
+    pub fn warning( path : & str, message : & str ) -> LintFinding {
+        LintFinding::new( Severity::WARNING, path, message )
+    }
+
+    pub fn get_severity( & self ) -> Severity {
+        self.severity
+    }
+
+    /**
+     * Location of the finding, e.g. `instance.port` or `signal`.
+     */
+    pub fn get_path( & self ) -> & String {
+        & self.path
+    }
+
+    pub fn get_message( & self ) -> & String {
+        & self.message
+    }
+
+    pub fn is_error( & self ) -> bool {
+        self.severity == Severity::ERROR
+    }
+}
+
+impl std::fmt::Display for LintFinding {
+    fn fmt( & self, f : & mut std::fmt::Formatter ) -> std::fmt::Result {
+        write!( f, "{}: {}: {}", self.severity, self.path, self.message )
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let finding = LintFinding::error( "inst.a", "input port is not bound" );
+        assert_eq!( finding.to_string(), "error: inst.a: input port is not bound" );
+    }
+}
@@ -6,18 +6,34 @@ pub static FULL : u32 = 3;
 pub struct MatchIndex {
     pub index : usize,
     pub strength : u32,
+    candidates : Vec< usize >,
 }
 
 impl MatchIndex {
     pub fn new() -> MatchIndex {
-        MatchIndex { index : 0, strength : 0 }
+        MatchIndex { index : 0, strength : 0, candidates : Vec::new() }
     }
 
     pub fn update( & mut self, index : usize, strength : u32 ) {
         if self.strength < strength {
             self.index = index;
             self.strength = strength;
+            self.candidates = vec![ index ];
         }
+        else if self.strength == strength && strength > 0 {
+            self.candidates.push( index );
+        }
+    }
+
+    /**
+     * All indices with the best strength, the first one is `position()`.
+     */
+    pub fn candidates( & self ) -> & Vec< usize > {
+        & self.candidates
+    }
+
+    pub fn is_tie( & self ) -> bool {
+        self.candidates.len() > 1
     }
 
     pub fn strength( & self ) -> u32 {
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use regex::Regex;
+use crate::vhdl::match_index::*;
+
+/**
+ * Rates how well two interfaces given by name and class fit together.  The strength is one of
+ * `NONE`, `CLASS`, `PARTIAL` or `FULL` from `match_index`, `NONE` means no connection.
+ */
+pub trait Matcher {
+    fn get_match_strength( & self, name_a : & str, class_a : & str, name_b : & str, class_b : & str )
+            -> u32;
+}
+
+/**
+ * Matches interfaces of the same class and the same name only.
+ */
+pub struct ExactMatcher;
+
+impl Matcher for ExactMatcher {
+    fn get_match_strength( & self, name_a : & str, class_a : & str, name_b : & str, class_b : & str )
+            -> u32 {
+        if class_a == class_b && name_a.to_lowercase() == name_b.to_lowercase() {
+            FULL
+        }
+        else {
+            NONE
+        }
+    }
+}
+
+/**
+ * Matches interfaces of the same class regardless of their names.
+ */
+pub struct ClassMatcher;
+
+impl Matcher for ClassMatcher {
+    fn get_match_strength( & self, _name_a : & str, class_a : & str, _name_b : & str, class_b : & str )
+            -> u32 {
+        if class_a == class_b {
+            CLASS
+        }
+        else {
+            NONE
+        }
+    }
+}
+
+/**
+ * Matches interfaces of the same class whose names are equal after replacing aliases by their
+ * canonical name, e.g. `cpu`, `host` and `master` all standing for `host`.
+ */
+pub struct AliasMatcher {
+    aliases : HashMap< String, String >,
+}
+
+impl AliasMatcher {
+    pub fn new() -> AliasMatcher {
+        AliasMatcher { aliases : HashMap::new() }
+    }
+
+    pub fn add_alias( & mut self, alias : & str, canonical : & str ) {
+        self.aliases.insert( alias.to_lowercase(), canonical.to_lowercase() );
+    }
+
+    fn get_canonical( & self, name : & str ) -> String {
+        let name = name.to_lowercase();
+        match self.aliases.get( & name ) {
+            Some( canonical ) => canonical.clone(),
+            None => name,
+        }
+    }
+}
+
+impl Default for AliasMatcher {
+    fn default() -> Self {
+        AliasMatcher::new()
+    }
+}
+
+impl Matcher for AliasMatcher {
+    fn get_match_strength( & self, name_a : & str, class_a : & str, name_b : & str, class_b : & str )
+            -> u32 {
+        if class_a != class_b {
+            return NONE;
+        }
+        if self.get_canonical( name_a ) == self.get_canonical( name_b ) {
+            FULL
+        }
+        else {
+            CLASS
+        }
+    }
+}
+
+/**
+ * Matches interfaces of the same class whose names are equal after applying a regular
+ * expression, e.g. `^(?:s_|m_)?(?P<name>.*)$` ignores `s_` and `m_` prefixes.  The capture group
+ * `name` or, without it, the whole match is compared.  Names not matching the expression are
+ * only matched by class.
+ */
+pub struct RegexMatcher {
+    pattern : Regex,
+}
+
+impl RegexMatcher {
+    pub fn new( pattern : & str ) -> Result< RegexMatcher, regex::Error > {
+        Ok( RegexMatcher { pattern : Regex::new( pattern )? } )
+    }
+
+    fn get_key( & self, name : & str ) -> Option< String > {
+        let captures = self.pattern.captures( name )?;
+        let key = captures.name( "name" ).or_else( || captures.get( 0 ) )?;
+        Some( key.as_str().to_lowercase() )
+    }
+}
+
+impl Matcher for RegexMatcher {
+    fn get_match_strength( & self, name_a : & str, class_a : & str, name_b : & str, class_b : & str )
+            -> u32 {
+        if class_a != class_b {
+            return NONE;
+        }
+        match ( self.get_key( name_a ), self.get_key( name_b ) ) {
+            ( Some( a ), Some( b ) ) if a == b => FULL,
+            _ => CLASS,
+        }
+    }
+}
+
+/**
+ * Default strategy: same class and name is a full match, same class and one name containing the
+ * other a partial match, same class only a class match.  Interfaces of different classes never
+ * match, even if one name contains the other, e.g. an unnamed interface, whose empty name is
+ * contained in every name.
+ */
+pub struct HeuristicMatcher;
+
+impl Matcher for HeuristicMatcher {
+    fn get_match_strength( & self, name_a : & str, class_a : & str, name_b : & str, class_b : & str )
+            -> u32 {
+        if class_a != class_b {
+            return NONE;
+        }
+        let name_a = name_a.to_lowercase();
+        let name_b = name_b.to_lowercase();
+        if name_a == name_b {
+            FULL
+        }
+        else if name_a.contains( & name_b ) || name_b.contains( & name_a ) {
+            PARTIAL
+        }
+        else {
+            CLASS
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heuristic() {
+        assert_eq!( HeuristicMatcher.get_match_strength( "host", "bus", "host", "bus" ), FULL );
+        assert_eq!( HeuristicMatcher.get_match_strength( "host", "bus", "cpu_host", "bus" ), PARTIAL );
+        assert_eq!( HeuristicMatcher.get_match_strength( "host", "bus", "mem", "bus" ), CLASS );
+        assert_eq!( HeuristicMatcher.get_match_strength( "host", "bus", "host", "irq" ), NONE );
+    }
+
+    #[test]
+    fn heuristic_requires_class() {
+        // names containing each other are no partial match across classes, in either order
+        assert_eq!( HeuristicMatcher.get_match_strength( "host", "bus", "cpu_host", "irq" ), NONE );
+        assert_eq!( HeuristicMatcher.get_match_strength( "cpu_host", "bus", "host", "irq" ), NONE );
+        assert_eq!( HeuristicMatcher.get_match_strength( "", "clk_rst", "host", "bus" ), NONE );
+        assert_eq!( HeuristicMatcher.get_match_strength( "cpu_host", "bus", "host", "bus" ), PARTIAL );
+    }
+
+    #[test]
+    fn exact_and_class() {
+        assert_eq!( ExactMatcher.get_match_strength( "host", "bus", "HOST", "bus" ), FULL );
+        assert_eq!( ExactMatcher.get_match_strength( "host", "bus", "cpu_host", "bus" ), NONE );
+        assert_eq!( ClassMatcher.get_match_strength( "host", "bus", "mem", "bus" ), CLASS );
+    }
+
+    #[test]
+    fn alias() {
+        let mut matcher = AliasMatcher::new();
+        matcher.add_alias( "cpu", "host" );
+        matcher.add_alias( "master", "host" );
+        assert_eq!( matcher.get_match_strength( "cpu", "bus", "master", "bus" ), FULL );
+        assert_eq!( matcher.get_match_strength( "cpu", "bus", "mem", "bus" ), CLASS );
+    }
+
+    #[test]
+    fn regex() {
+        let matcher = RegexMatcher::new( "^(?:s_|m_)?(?P<name>.*)$" ).unwrap();
+        assert_eq!( matcher.get_match_strength( "m_axi", "axi4", "s_axi", "axi4" ), FULL );
+        assert_eq!( matcher.get_match_strength( "m_axi", "axi4", "s_mem", "axi4" ), CLASS );
+        assert!( RegexMatcher::new( "(" ).is_err() );
+    }
+}
@@ -0,0 +1,404 @@
+use std::collections::VecDeque;
+use linked_hash_map::LinkedHashMap;
+use linked_hash_set::LinkedHashSet;
+use crate::vhdl::architecture::Architecture;
+use crate::vhdl::direction::Direction;
+use crate::vhdl::expression::get_constant_range;
+
+/**
+ * A point where a net is attached: a port of the architecture's entity, a port of an instance or
+ * a concurrent statement identified by its position.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Endpoint {
+    EntityPort( String ),
+    InstancePort( String, String ),
+    Statement( usize ),
+}
+
+impl Endpoint {
+    /**
+     * Parses a path of the form `instance.port` or `port`.
+     */
+    pub fn from_path( path : & str ) -> Endpoint {
+        match path.split_once( '.' ) {
+            Some( ( instance, port ) ) => Endpoint::InstancePort( instance.to_string(), port.to_string() ),
+            None => Endpoint::EntityPort( path.to_string() ),
+        }
+    }
+
+    pub fn get_path( & self ) -> String {
+        match self {
+            Endpoint::EntityPort( port ) => port.clone(),
+            Endpoint::InstancePort( instance, port ) => format!( "{}.{}", instance, port ),
+            Endpoint::Statement( idx ) => format!( "<statement {}>", idx ),
+        }
+    }
+
+    pub fn get_instance( & self ) -> Option< & String > {
+        match self {
+            Endpoint::InstancePort( instance, _ ) => Some( instance ),
+            _ => None,
+        }
+    }
+
+    fn matches( & self, other : & Endpoint ) -> bool {
+        self.get_path().to_lowercase() == other.get_path().to_lowercase()
+    }
+}
+
+impl std::fmt::Display for Endpoint {
+    fn fmt( & self, f : & mut std::fmt::Formatter ) -> std::fmt::Result {
+        write!( f, "{}", self.get_path() )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Net {
+    name : String,
+    data_type : String,
+    drivers : Vec< Endpoint >,
+    driver_ranges : Vec< Option< String > >,
+    loads : Vec< Endpoint >,
+}
+
+impl Net {
+    pub fn new( name : & str ) -> Net {
+        Net { name : name.to_string(), data_type : String::new(), drivers : Vec::new(),
+                driver_ranges : Vec::new(), loads : Vec::new() }
+    }
+
+    pub fn get_name( & self ) -> & String {
+        & self.name
+    }
+
+    pub fn get_data_type( & self ) -> & String {
+        & self.data_type
+    }
+
+    /**
+     * Endpoints writing the net.  Bidirectional ports are drivers and loads at the same time.
+     */
+    pub fn get_drivers( & self ) -> & Vec< Endpoint > {
+        & self.drivers
+    }
+
+    /**
+     * Drivers that are not bidirectional.
+     */
+    pub fn get_exclusive_drivers( & self ) -> Vec< & Endpoint > {
+        self.drivers.iter().filter( | d | ! self.loads.contains( d ) ).collect()
+    }
+
+    /**
+     * Index or range of the net written by the driver at position `idx` of `get_drivers`,
+     * `None` for the whole net.
+     */
+    pub fn get_driver_range( & self, idx : usize ) -> Option< & String > {
+        self.driver_ranges.get( idx ).and_then( | r | r.as_ref() )
+    }
+
+    /**
+     * Exclusive drivers writing overlapping parts of the net.  Slices with constant, disjoint
+     * ranges do not conflict.
+     */
+    pub fn get_conflicting_drivers( & self ) -> Vec< & Endpoint > {
+        let exclusive : Vec< usize > = ( 0 .. self.drivers.len() )
+                .filter( | idx | ! self.loads.contains( & self.drivers[ * idx ] ) ).collect();
+        let mut conflicting = Vec::new();
+        for & idx in & exclusive {
+            let range = self.get_driver_range( idx ).and_then( | r | get_constant_range( r ) );
+            let overlaps = exclusive.iter().filter( | other | ** other != idx ).any( | & other | {
+                let other_range = self.get_driver_range( other ).and_then( | r | get_constant_range( r ) );
+                match ( range, other_range ) {
+                    ( Some( ( low, high ) ), Some( ( other_low, other_high ) ) ) =>
+                            low <= other_high && other_low <= high,
+                    _ => true,
+                }
+            } );
+            if overlaps && ! conflicting.contains( & & self.drivers[ idx ] ) {
+                conflicting.push( & self.drivers[ idx ] );
+            }
+        }
+        conflicting
+    }
+
+    pub fn get_loads( & self ) -> & Vec< Endpoint > {
+        & self.loads
+    }
+
+    pub fn get_fanout( & self ) -> usize {
+        self.loads.len()
+    }
+
+    pub fn get_endpoints( & self ) -> Vec< & Endpoint > {
+        let mut endpoints : Vec< & Endpoint > = self.drivers.iter().collect();
+        for load in & self.loads {
+            if ! endpoints.contains( & load ) {
+                endpoints.push( load );
+            }
+        }
+        endpoints
+    }
+
+    pub fn contains( & self, endpoint : & Endpoint ) -> bool {
+        self.get_endpoints().iter().any( | e | e.matches( endpoint ) )
+    }
+
+    fn set_data_type( & mut self, data_type : & str ) {
+        if self.data_type.is_empty() {
+            self.data_type = data_type.to_string();
+        }
+    }
+
+    fn add_driver( & mut self, endpoint : Endpoint, range : Option< String > ) {
+        self.drivers.push( endpoint );
+        self.driver_ranges.push( range );
+    }
+
+    fn add_load( & mut self, endpoint : Endpoint ) {
+        if ! self.loads.contains( & endpoint ) {
+            self.loads.push( endpoint );
+        }
+    }
+}
+
+/**
+ * Connectivity view of an architecture.  The netlist is derived from the port bindings, the
+ * entity ports and the concurrent statements, so a freshly created netlist always reflects the
+ * connections made so far.
+ */
+pub struct Netlist {
+    nets : LinkedHashMap< String, Net >,
+}
+
+impl Netlist {
+    pub fn new() -> Netlist {
+        Netlist { nets : LinkedHashMap::new() }
+    }
+
+    pub fn from_architecture( architecture : & Architecture ) -> Netlist {
+        let mut netlist = Netlist::new();
+        for port in architecture.get_entity().get_ports() {
+            let name = port.get_name();
+            let endpoint = Endpoint::EntityPort( name.clone() );
+            let net = netlist.get_or_insert( & name );
+            net.set_data_type( port.get_data_type() );
+            // seen from inside the architecture an entity input drives its net
+            match port.get_direction() {
+                Direction::IN => net.add_driver( endpoint, None ),
+                Direction::OUT | Direction::BUFFER => net.add_load( endpoint ),
+                Direction::INOUT => { net.add_driver( endpoint.clone(), None ); net.add_load( endpoint ) },
+            }
+        }
+        for signal in architecture.get_declarations().get_signals() {
+            netlist.get_or_insert( signal.get_name() ).set_data_type( signal.get_data_type() );
+        }
+        for ( name, instance ) in architecture.get_instances() {
+            for interface in instance.get_interfaces() {
+                for port in interface.get_ports() {
+                    let endpoint = Endpoint::InstancePort( name.clone(), port.get_inner().clone() );
+                    for actual in port.get_actuals() {
+                        let net_name = match actual.get_net() {
+                            Some( net_name ) => net_name,
+                            None => {
+                                // expressions on inputs read the nets they refer to
+                                for identifier in actual.get_identifiers() {
+                                    if let Some( net ) = netlist.get_net_mut( & identifier ) {
+                                        net.add_load( endpoint.clone() );
+                                    }
+                                }
+                                continue;
+                            },
+                        };
+                        let range = actual.get_net_range();
+                        let net = netlist.get_or_insert( & net_name );
+                        // a slice tells nothing about the type of the whole net
+                        if range.is_none() && port.get_actual().is_some() {
+                            net.set_data_type( port.get_data_type() );
+                        }
+                        match port.get_direction() {
+                            Direction::IN => net.add_load( endpoint.clone() ),
+                            Direction::OUT | Direction::BUFFER => net.add_driver( endpoint.clone(), range ),
+                            Direction::INOUT => {
+                                net.add_driver( endpoint.clone(), range );
+                                net.add_load( endpoint.clone() )
+                            },
+                        }
+                    }
+                }
+            }
+        }
+        for ( idx, statement ) in architecture.get_statements().iter().enumerate() {
+            for net_name in statement.get_driven_signals() {
+                netlist.get_or_insert( & net_name ).add_driver( Endpoint::Statement( idx ), None );
+            }
+            for net_name in statement.get_read_signals() {
+                // identifiers that are neither ports nor signals are functions or keywords
+                if let Some( net ) = netlist.get_net_mut( & net_name ) {
+                    net.add_load( Endpoint::Statement( idx ) );
+                }
+            }
+        }
+        netlist
+    }
+
+    pub fn get_nets( & self ) -> Vec< & Net > {
+        self.nets.values().collect()
+    }
+
+    pub fn get_net( & self, name : & str ) -> Option< & Net > {
+        self.nets.get( & name.to_lowercase() )
+    }
+
+    /**
+     * Returns the net attached to a path like `instance.port` or `port`.
+     */
+    pub fn get_net_of( & self, path : & str ) -> Option< & Net > {
+        let endpoint = Endpoint::from_path( path );
+        self.nets.values().find( | n | n.contains( & endpoint ) )
+    }
+
+    /**
+     * Returns all other endpoints on the net attached to `path`.
+     */
+    pub fn get_connected( & self, path : & str ) -> Vec< Endpoint > {
+        let endpoint = Endpoint::from_path( path );
+        match self.get_net_of( path ) {
+            Some( net ) => net.get_endpoints().into_iter()
+                    .filter( | e | ! e.matches( & endpoint ) ).cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn get_fanout( & self, name : & str ) -> usize {
+        match self.get_net( name ) {
+            Some( net ) => net.get_fanout(),
+            None => 0,
+        }
+    }
+
+    /**
+     * Returns the nets an instance is attached to.
+     */
+    pub fn get_instance_nets( & self, instance : & str ) -> Vec< & Net > {
+        self.nets.values().filter( | n | n.get_endpoints().iter()
+                .any( | e | e.get_instance().is_some_and( | i | i == instance ) ) ).collect()
+    }
+
+    /**
+     * Returns the instances sharing at least one net with `instance`.
+     */
+    pub fn get_adjacent_instances( & self, instance : & str ) -> Vec< String > {
+        let mut adjacent : LinkedHashSet< String > = LinkedHashSet::new();
+        for net in self.get_instance_nets( instance ) {
+            for endpoint in net.get_endpoints() {
+                if let Some( other ) = endpoint.get_instance() {
+                    if other != instance {
+                        adjacent.insert_if_absent( other.clone() );
+                    }
+                }
+            }
+        }
+        adjacent.into_iter().collect()
+    }
+
+    /**
+     * Follows the nets driven by `instance` to their loading instances and continues from
+     * there.  Returns all reached instances in breadth first order.
+     */
+    pub fn get_downstream_instances( & self, instance : & str ) -> Vec< String > {
+        let mut visited : LinkedHashSet< String > = LinkedHashSet::new();
+        let mut queue : VecDeque< String > = VecDeque::new();
+        queue.push_back( instance.to_string() );
+        while let Some( current ) = queue.pop_front() {
+            for net in self.nets.values() {
+                let drives = net.get_drivers().iter()
+                        .any( | d | d.get_instance().is_some_and( | i | i == & current ) );
+                if ! drives {
+                    continue;
+                }
+                for load in net.get_loads() {
+                    if let Some( next ) = load.get_instance() {
+                        if next != instance && visited.insert_if_absent( next.clone() ) {
+                            queue.push_back( next.clone() );
+                        }
+                    }
+                }
+            }
+        }
+        visited.into_iter().collect()
+    }
+
+    fn get_net_mut( & mut self, name : & str ) -> Option< & mut Net > {
+        self.nets.get_mut( & name.to_lowercase() )
+    }
+
+    fn get_or_insert( & mut self, name : & str ) -> & mut Net {
+        self.nets.entry( name.to_lowercase() ).or_insert_with( || Net::new( name ) )
+    }
+}
+
+impl Default for Netlist {
+    fn default() -> Self {
+        Netlist::new()
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vhdl::entity::Entity;
+    use crate::vhdl::instance::Instance;
+    use crate::vhdl::port::Port;
+    use crate::vhdl::signal_assignment::SignalAssignment;
+
+    /**
+     * a -> u0 -> s -> u1 -> y, s also read by an assignment to z
+     */
+    fn new_test_architecture() -> Architecture {
+        let mut top = Entity::new( "top" );
+        top.add_port( Port::new( "a", Direction::IN, "std_logic" ) );
+        top.add_port( Port::new( "y", Direction::OUT, "std_logic" ) );
+        top.add_port( Port::new( "z", Direction::OUT, "std_logic" ) );
+        let mut sub = Entity::new( "sub" );
+        sub.add_port( Port::new( "i", Direction::IN, "std_logic" ) );
+        sub.add_port( Port::new( "o", Direction::OUT, "std_logic" ) );
+        let mut arch = Architecture::new( "rtl", & top );
+        arch.add_instance( Instance::from_entity( "u0", & sub ) );
+        arch.add_instance( Instance::from_entity( "u1", & sub ) );
+        arch.connect_instance_to_port_by_name( "u0", "i", "a" ).unwrap();
+        arch.connect_instance_to_signal_by_name( "u0", "o", "s" ).unwrap();
+        arch.connect_instance_to_port_by_name( "u1", "i", "s" ).unwrap();
+        arch.connect_instance_to_port_by_name( "u1", "o", "y" ).unwrap();
+        arch.add_signal_assignment( SignalAssignment::new( "z", "not s" ) );
+        arch
+    }
+
+    #[test]
+    fn drivers_and_loads() {
+        let netlist = Netlist::from_architecture( & new_test_architecture() );
+        let net = netlist.get_net( "s" ).unwrap();
+        assert_eq!( net.get_data_type(), "std_logic" );
+        assert_eq!( net.get_drivers(), & vec![ Endpoint::from_path( "u0.o" ) ] );
+        assert_eq!( net.get_loads(), & vec![ Endpoint::from_path( "u1.i" ), Endpoint::Statement( 0 ) ] );
+        assert_eq!( netlist.get_fanout( "s" ), 2 );
+    }
+
+    #[test]
+    fn connected() {
+        let netlist = Netlist::from_architecture( & new_test_architecture() );
+        assert_eq!( netlist.get_connected( "u0.i" ), vec![ Endpoint::EntityPort( "a".to_string() ) ] );
+        assert_eq!( netlist.get_net_of( "u1.o" ).unwrap().get_name(), "y" );
+        assert!( netlist.get_net_of( "u1.unknown" ).is_none() );
+    }
+
+    #[test]
+    fn traversal() {
+        let netlist = Netlist::from_architecture( & new_test_architecture() );
+        assert_eq!( netlist.get_adjacent_instances( "u1" ), vec![ "u0" ] );
+        assert_eq!( netlist.get_downstream_instances( "u0" ), vec![ "u1" ] );
+        assert!( netlist.get_downstream_instances( "u1" ).is_empty() );
+    }
+}
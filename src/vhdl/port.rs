@@ -1,6 +1,7 @@
 use serde_derive::Deserialize;
 use crate::vhdl::direction::Direction;
 use crate::element::Element;
+use crate::vhdl::keywords::{ OF, VIEW };
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct Port {
@@ -10,26 +11,51 @@ pub struct Port {
     direction : Direction,
     data_type : String,
     #[serde(default)]
-    default : String
+    default : String,
+    #[serde(default)]
+    optional : bool,
+    #[serde(default)]
+    tie_off : String,
+    #[serde(default, alias = "global")]
+    fixed : bool,
+    #[serde(skip)]
+    view : String,
 }
 
 impl Port {
     pub fn new( name : & str, direction : Direction, data_type : & str )
             -> Port  {
         Port{ name : name.to_string(), interface : String::new(), direction : direction,
-            data_type : data_type.to_string(), default : String::new() }
+            data_type : data_type.to_string(), default : String::new(), optional : false,
+            tie_off : String::new(), fixed : false, view : String::new() }
 
     }
 
     pub fn new_with_default( name : & str, direction : Direction, data_type : & str,
             default : & str ) -> Port  {
         Port{ name : name.to_string(), interface : String::new(), direction : direction,
-            data_type : data_type.to_string(), default : default.to_string() }
+            data_type : data_type.to_string(), default : default.to_string(), optional : false,
+            tie_off : String::new(), fixed : false, view : String::new() }
+    }
+
+    /**
+     * Creates a VHDL-2019 record port with the mode view `view`, e.g.
+     * `axi : view axi4_lite_host_view of axi4_lite_t`.
+     */
+    pub fn new_with_view( name : & str, view : & str, data_type : & str ) -> Port {
+        let mut port = Port::new( name, Direction::INOUT, data_type );
+        port.view = view.to_string();
+        port
     }
 
     pub fn clone_inverted( & self ) -> Port {
-        Port::new_with_default( & self.name, self.direction.get_inverted(), & self.data_type,
-            & self.default )
+        let mut port = Port::new_with_default( & self.name, self.direction,
+            & self.data_type, & self.default );
+        port.optional = self.optional;
+        port.tie_off = self.tie_off.clone();
+        port.fixed = self.fixed;
+        port.invert();
+        port
     }
 
     pub fn get_name( & self ) -> String {
@@ -45,6 +71,13 @@ impl Port {
         self.name = name.to_string();
     }
 
+    /**
+     * Name of the port without the interface prefix.
+     */
+    pub fn get_base_name( & self ) -> & String {
+        & self.name
+    }
+
     pub fn get_interface( & self ) -> & String {
         & self.interface
     }
@@ -57,12 +90,72 @@ impl Port {
         self.direction
     }
 
+    pub fn set_direction( & mut self, direction : Direction ) {
+        self.direction = direction;
+    }
+
     pub fn get_data_type( & self ) -> & String {
         & self.data_type
     }
 
+    pub fn set_data_type( & mut self, data_type : & str ) {
+        self.data_type = data_type.to_string();
+    }
+
+    pub fn get_default( & self ) -> & String {
+        & self.default
+    }
+
+    pub fn set_default( & mut self, default : & str ) {
+        self.default = default.to_string();
+    }
+
+    /**
+     * Optional ports may be missing on implementations of the interface.
+     */
+    pub fn is_optional( & self ) -> bool {
+        self.optional
+    }
+
+    pub fn set_optional( & mut self, optional : bool ) {
+        self.optional = optional;
+    }
+
+    /**
+     * Value an optional input is tied to if the connected interface lacks it, empty for the
+     * default or zero value.
+     */
+    pub fn get_tie_off( & self ) -> & String {
+        & self.tie_off
+    }
+
+    pub fn set_tie_off( & mut self, tie_off : & str ) {
+        self.tie_off = tie_off.to_string();
+    }
+
+    /**
+     * Fixed ports keep their direction when the interface is inverted, e.g. a clock fed to
+     * host and agent by a common source.
+     */
+    pub fn is_fixed( & self ) -> bool {
+        self.fixed
+    }
+
+    pub fn set_fixed( & mut self, fixed : bool ) {
+        self.fixed = fixed;
+    }
+
+    /**
+     * Mode view of a record port, empty for a port with a direction.
+     */
+    pub fn get_view( & self ) -> & String {
+        & self.view
+    }
+
     pub fn invert( & mut self ) {
-        self.direction.invert();
+        if ! self.fixed {
+            self.direction.invert();
+        }
     }
 }
 
@@ -70,8 +163,14 @@ impl Element for Port {
     fn to_source_code( & self, indent : usize ) -> String {
         let mut source = String::new();
         let indent_str = crate::util::indent( indent );
-        source.push_str( & format!( "{}{} : {} {}", indent_str, self.get_name(),
-            self.direction, self.data_type ) );
+        if self.view.is_empty() {
+            source.push_str( & format!( "{}{} : {} {}", indent_str, self.get_name(),
+                self.direction, self.data_type ) );
+        }
+        else {
+            source.push_str( & format!( "{}{} : {} {} {} {}", indent_str, self.get_name(),
+                VIEW, self.view, OF, self.data_type ) );
+        }
         if ! self.default.is_empty() {
             source.push_str( & format!( " := {}", self.default ) );
         }
@@ -92,6 +191,21 @@ mod tests {
     const INOUT : &'static str = "test : inout boolean";
     const BUFFER : &'static str = "test : buffer boolean";
 
+    #[test]
+    fn fixed_port() {
+        let mut port = Port::new( "test", Direction::IN, "boolean" );
+        port.set_fixed( true );
+        port.invert();
+        assert_eq!( port.to_source_code( 0 ), INPUT.to_string() );
+        assert_eq!( port.clone_inverted().to_source_code( 0 ), INPUT.to_string() );
+    }
+
+    #[test]
+    fn view_port() {
+        let port = Port::new_with_view( "axi", "axi4_lite_host_view", "axi4_lite_t" );
+        assert_eq!( port.to_source_code( 0 ), "axi : view axi4_lite_host_view of axi4_lite_t" );
+    }
+
     #[test]
     fn input_port() {
         let port = Port::new( "test", Direction::IN, "boolean" );
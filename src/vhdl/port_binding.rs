@@ -1,20 +1,34 @@
 use crate::element::Element;
+use crate::vhdl::actual::Actual;
+use crate::vhdl::association::Association;
+use crate::vhdl::data_type::get_zero_value;
 use crate::vhdl::direction::Direction;
+use crate::vhdl::expression::{ get_constant_range, get_parenthesized };
 use crate::vhdl::port::Port;
+use crate::vhdl::vhdl_error::VhdlError;
 
 #[derive(Clone)]
 pub struct PortBinding {
     inner : String,
+    member : String,
     direction : Direction,
     data_type : String,
-    outer : String,
+    default : String,
+    optional : bool,
+    tie_off : String,
+    fixed : bool,
+    actual : Option< Actual >,
+    partial : Vec< ( String, Actual ) >,
 }
 
 impl PortBinding {
     pub fn from_port( port : & Port ) -> PortBinding {
         PortBinding { inner : port.get_name().to_string(),
+                member : port.get_base_name().to_string(),
                 direction : port.get_direction(), data_type : port.get_data_type().to_string(),
-                outer : String::new() }
+                default : port.get_default().to_string(), optional : port.is_optional(),
+                tie_off : port.get_tie_off().to_string(), fixed : port.is_fixed(), actual : None,
+                partial : Vec::new() }
     }
 
     pub fn connect( & mut self, port : & Port ) {
@@ -25,17 +39,109 @@ impl PortBinding {
         if & self.data_type != port.get_data_type() {
             panic!( "error: port data type mismatch!" );
         }
-        self.outer = port.get_name().to_string();
+        self.connect_by_name( & port.get_name() );
     }
 
     pub fn connect_by_name( & mut self, outer : & str ) {
-        self.outer = outer.to_string();
+        self.actual = Some( Actual::name( outer ) );
+        self.partial.clear();
+    }
+
+    /**
+     * Binds the whole port to a name, slice, literal, expression or `open`.  Literals and
+     * expressions are only allowed for inputs, inputs without default can not be left open.
+     */
+    pub fn connect_to_actual( & mut self, actual : & Actual ) -> Result< (), VhdlError > {
+        self.requires_valid_actual( actual )?;
+        if actual.is_open() && matches!( self.direction, Direction::IN ) && self.default.is_empty() {
+            return Err( VhdlError::new( & format!(
+                    "error: Input port {:?} without default can not be left open!", self.inner ) ) );
+        }
+        self.actual = Some( actual.clone() );
+        self.partial.clear();
+        Ok(())
+    }
+
+    pub fn connect_open( & mut self ) -> Result< (), VhdlError > {
+        self.connect_to_actual( & Actual::Open )
+    }
+
+    /**
+     * Binds a slice or element of the port, e.g. `range` `3 downto 0` gives
+     * `port( 3 downto 0 ) => actual`.  Several partial associations may be added.  Constant
+     * ranges have to lie within a constant port range and must not overlap.
+     */
+    pub fn connect_partial( & mut self, range : & str, actual : & Actual ) -> Result< (), VhdlError > {
+        self.requires_valid_actual( actual )?;
+        if actual.is_open() {
+            return Err( VhdlError::new( & format!(
+                    "error: Partial association of port {:?} can not be open!", self.inner ) ) );
+        }
+        self.requires_valid_range( range )?;
+        self.actual = None;
+        self.partial.push( ( range.to_string(), actual.clone() ) );
+        Ok(())
+    }
+
+    /**
+     * Returns the actual an unbound port is tied to: outputs are left open, inputs are tied to
+     * their tie-off value, their default or the zero value of their data type.
+     */
+    pub fn get_tie_off( & self ) -> Option< Actual > {
+        if ! matches!( self.direction, Direction::IN ) {
+            return Some( Actual::Open );
+        }
+        if ! self.tie_off.is_empty() {
+            return Some( Actual::literal( & self.tie_off ) );
+        }
+        if ! self.default.is_empty() {
+            return Some( Actual::literal( & self.default ) );
+        }
+        get_zero_value( & self.data_type ).map( | value | Actual::literal( & value ) )
+    }
+
+    /**
+     * Returns the tie-off actual, an error for an input without default and zero value.
+     */
+    pub fn requires_tie_off( & self ) -> Result< Actual, VhdlError > {
+        match self.get_tie_off() {
+            Some( actual ) => Ok( actual ),
+            None => Err( VhdlError::new( & format!(
+                    "error: Input port {:?} of type {:?} has no default and no zero value!",
+                    self.inner, self.data_type ) ) ),
+        }
+    }
+
+    pub fn tie_off( & mut self ) -> Result< (), VhdlError > {
+        let actual = self.requires_tie_off()?;
+        self.connect_to_actual( & actual )
+    }
+
+    /**
+     * Unbound outputs and inputs with a default may be left open without a tie-off.
+     */
+    pub fn may_be_open( & self ) -> bool {
+        ! matches!( self.direction, Direction::IN ) || ! self.default.is_empty()
     }
 
     pub fn get_inner( & self ) -> & String {
         & self.inner
     }
 
+    /**
+     * Renames the formal, e.g. to the record element `axi.awaddr` of a record port.
+     */
+    pub fn set_inner( & mut self, inner : & str ) {
+        self.inner = inner.to_string();
+    }
+
+    /**
+     * Name of the port within its interface, i.e. without the interface prefix.
+     */
+    pub fn get_member( & self ) -> & String {
+        & self.member
+    }
+
     pub fn get_direction( & self ) -> & Direction {
         & self.direction
     }
@@ -44,21 +150,106 @@ impl PortBinding {
         & self.data_type
     }
 
-    pub fn get_outer( & self ) -> & String {
-        & self.outer
+    pub fn get_default( & self ) -> & String {
+        & self.default
+    }
+
+    /**
+     * The port may be missing on the interface it is connected to, see `Port::is_optional`.
+     */
+    pub fn is_optional( & self ) -> bool {
+        self.optional
+    }
+
+    /**
+     * The port keeps its direction on inversion and is connected to a shared net, see
+     * `Port::is_fixed`.
+     */
+    pub fn is_fixed( & self ) -> bool {
+        self.fixed
+    }
+
+    pub fn get_actual( & self ) -> Option< & Actual > {
+        self.actual.as_ref()
+    }
+
+    /**
+     * All actuals of the binding, either the whole port actual or the partial ones.
+     */
+    pub fn get_actuals( & self ) -> Vec< & Actual > {
+        match & self.actual {
+            Some( actual ) => vec![ actual ],
+            None => self.partial.iter().map( | ( _, actual ) | actual ).collect(),
+        }
+    }
+
+    pub fn get_outer( & self ) -> String {
+        let actuals : Vec< String > = self.get_actuals().iter()
+                .map( | a | a.to_source_code( 0 ) ).collect();
+        actuals.join( ", " )
+    }
+
+    pub fn get_associations( & self ) -> Vec< Association > {
+        match & self.actual {
+            Some( actual ) => vec![ Association::new( & self.inner, actual ) ],
+            None => self.partial.iter().map( | ( range, actual ) |
+                    Association::new( & format!( "{}( {} )", self.inner, range ), actual ) ).collect(),
+        }
     }
 
     pub fn is_bound( & self ) -> bool {
-        ! self.outer.is_empty()
+        self.actual.is_some() || ! self.partial.is_empty()
+    }
+
+    pub fn is_open( & self ) -> bool {
+        matches!( self.actual, Some( Actual::Open ) )
+    }
+
+    fn requires_valid_range( & self, range : & str ) -> Result< (), VhdlError > {
+        let ( low, high ) = match get_constant_range( range ) {
+            Some( bounds ) => bounds,
+            None => return Ok(()),
+        };
+        let port_range = get_parenthesized( & self.data_type ).and_then( | r | get_constant_range( & r ) );
+        if let Some( ( port_low, port_high ) ) = port_range {
+            if low < port_low || high > port_high {
+                return Err( VhdlError::new( & format!(
+                        "error: Range {:?} is outside of port {:?} of type {:?}!",
+                        range, self.inner, self.data_type ) ) );
+            }
+        }
+        let overlaps = self.partial.iter().filter_map( | ( r, _ ) | get_constant_range( r ) )
+                .any( | ( other_low, other_high ) | low <= other_high && other_low <= high );
+        if overlaps {
+            return Err( VhdlError::new( & format!(
+                    "error: Range {:?} of port {:?} overlaps an earlier partial association!",
+                    range, self.inner ) ) );
+        }
+        Ok(())
+    }
+
+    fn requires_valid_actual( & self, actual : & Actual ) -> Result< (), VhdlError > {
+        if ! matches!( self.direction, Direction::IN ) && ! actual.is_writable() {
+            return Err( VhdlError::new( & format!(
+                    "error: Port {:?} of mode {} can not be associated with {:?}!",
+                    self.inner, self.direction, actual.to_source_code( 0 ) ) ) );
+        }
+        Ok(())
     }
 }
 
 impl Element for PortBinding {
     fn to_source_code( & self, indent : usize ) -> String {
-        let mut source = String::new();
         let indent_str = crate::util::indent( indent );
-        source.push_str( & format!( "{}{} => {}", indent_str, self.inner, self.outer ) );
-        return source;
+        if ! self.is_bound() {
+            if ! self.may_be_open() {
+                panic!( "error: Input port {:?} is unbound, the architecture has to be finalized!", self.inner );
+            }
+            return format!( "{}{} => {}", indent_str, self.inner, crate::vhdl::keywords::OPEN );
+        }
+        let associations : Vec< String > = self.get_associations().iter()
+                .map( | a | a.to_source_code( indent ) ).collect();
+        associations.join( ", " )
     }
 }
 
@@ -68,7 +259,14 @@ mod tests {
     #[test]
     fn from_port() {
         let binding = PortBinding::from_port( & Port::new( "test", Direction::OUT, "boolean" ) );
-        assert_eq!( & binding.to_source_code( 0 ), "test => " );
+        assert_eq!( & binding.to_source_code( 0 ), "test => open" );
+    }
+
+    #[test]
+    #[should_panic]
+    fn unbound_input() {
+        let binding = PortBinding::from_port( & Port::new( "test", Direction::IN, "boolean" ) );
+        binding.to_source_code( 0 );
     }
 
     #[test]
@@ -78,7 +276,55 @@ mod tests {
         binding.connect( & port );
         assert_eq!( & binding.to_source_code( 0 ), "test => extern" );
     }
-}
 
+    #[test]
+    fn connect_to_literal() {
+        let mut binding = PortBinding::from_port( & Port::new( "test", Direction::IN, "std_logic" ) );
+        binding.connect_to_actual( & Actual::literal( "'0'" ) ).unwrap();
+        assert_eq!( & binding.to_source_code( 0 ), "test => '0'" );
+    }
+
+    #[test]
+    fn connect_output_to_literal() {
+        let mut binding = PortBinding::from_port( & Port::new( "test", Direction::OUT, "std_logic" ) );
+        assert!( binding.connect_to_actual( & Actual::literal( "'0'" ) ).is_err() );
+    }
+
+    #[test]
+    fn connect_open() {
+        let mut output = PortBinding::from_port( & Port::new( "test", Direction::OUT, "std_logic" ) );
+        output.connect_open().unwrap();
+        assert_eq!( & output.to_source_code( 0 ), "test => open" );
+        let mut input = PortBinding::from_port( & Port::new( "test", Direction::IN, "std_logic" ) );
+        assert!( input.connect_open().is_err() );
+    }
 
+    #[test]
+    fn tie_off() {
+        let mut output = PortBinding::from_port( & Port::new( "o", Direction::OUT, "std_logic" ) );
+        let mut with_default = PortBinding::from_port(
+                & Port::new_with_default( "d", Direction::IN, "std_logic", "'1'" ) );
+        let mut vector = PortBinding::from_port(
+                & Port::new( "v", Direction::IN, "std_logic_vector( 7 downto 0 )" ) );
+        let mut unknown = PortBinding::from_port( & Port::new( "p", Direction::IN, "positive" ) );
+        output.tie_off().unwrap();
+        with_default.tie_off().unwrap();
+        vector.tie_off().unwrap();
+        assert_eq!( & output.to_source_code( 0 ), "o => open" );
+        assert_eq!( & with_default.to_source_code( 0 ), "d => '1'" );
+        assert_eq!( & vector.to_source_code( 0 ), "v => (others => '0')" );
+        assert!( unknown.tie_off().is_err() );
+    }
 
+    #[test]
+    fn connect_partial() {
+        let mut binding = PortBinding::from_port(
+                & Port::new( "test", Direction::IN, "std_logic_vector( 7 downto 0 )" ) );
+        binding.connect_partial( "3 downto 0", & Actual::name( "x" ) ).unwrap();
+        binding.connect_partial( "7 downto 4", & Actual::literal( "\"0000\"" ) ).unwrap();
+        assert_eq!( & binding.to_source_code( 0 ),
+                "test( 3 downto 0 ) => x, test( 7 downto 4 ) => \"0000\"" );
+        assert!( binding.connect_partial( "8", & Actual::name( "y" ) ).is_err() );
+        assert!( binding.connect_partial( "5 downto 2", & Actual::name( "y" ) ).is_err() );
+    }
+}
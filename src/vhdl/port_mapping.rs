@@ -0,0 +1,125 @@
+use linked_hash_map::LinkedHashMap;
+use crate::vhdl::direction::Direction;
+use crate::vhdl::vhdl_error::VhdlError;
+
+/**
+ * Maps member names of one interface to member names of another, e.g. `rd_data` to
+ * `read_data`.  Members without an entry are mapped to the member of the same name.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct PortMapping {
+    members : LinkedHashMap< String, String >,
+}
+
+impl PortMapping {
+    pub fn new() -> PortMapping {
+        PortMapping { members : LinkedHashMap::new() }
+    }
+
+    pub fn from_pairs( pairs : & [ ( & str, & str ) ] ) -> PortMapping {
+        let mut mapping = PortMapping::new();
+        for ( from, to ) in pairs {
+            mapping.add( from, to );
+        }
+        mapping
+    }
+
+    pub fn add( & mut self, from : & str, to : & str ) {
+        self.members.insert( from.to_string(), to.to_string() );
+    }
+
+    pub fn get( & self, from : & str ) -> String {
+        match self.members.get( from ) {
+            Some( to ) => to.clone(),
+            None => from.to_string(),
+        }
+    }
+
+    pub fn get_members( & self ) -> & LinkedHashMap< String, String > {
+        & self.members
+    }
+
+    /**
+     * Pairs every member of `from` with a member of `to` by mapped name and returns the index
+     * into `to` for each member of `from`.  Directions have to be equal or, if `complementary`,
+     * inverted.  Unknown entries, missing members and members of `to` left over are errors.
+     */
+    pub fn pair( & self, from : & [ ( String, Direction ) ], to : & [ ( String, Direction ) ],
+            complementary : bool ) -> Result< Vec< usize >, VhdlError > {
+        for ( key, value ) in & self.members {
+            if ! PortMapping::contains( from, key ) {
+                return Err( VhdlError::new( & format!(
+                        "error: Mapped member {:?} does not exist!", key ) ) );
+            }
+            if ! PortMapping::contains( to, value ) {
+                return Err( VhdlError::new( & format!(
+                        "error: Mapped member {:?} of {:?} does not exist!", value, key ) ) );
+            }
+        }
+        let mut pairs = Vec::new();
+        let mut used = vec![ false; to.len() ];
+        for ( member, direction ) in from {
+            let target = self.get( member );
+            let idx = match to.iter().position( | ( m, _ ) | m.to_lowercase() == target.to_lowercase() ) {
+                Some( idx ) => idx,
+                None => return Err( VhdlError::new( & format!(
+                        "error: Member {:?} has no counterpart {:?}!", member, target ) ) ),
+            };
+            if used[ idx ] {
+                return Err( VhdlError::new( & format!(
+                        "error: Member {:?} is mapped more than once!", target ) ) );
+            }
+            let expected = if complementary { direction.get_inverted() } else { * direction };
+            let actual = to[ idx ].1;
+            let matching = expected == actual ||
+                    ( complementary && * direction == Direction::BUFFER && actual == Direction::IN );
+            if ! matching {
+                return Err( VhdlError::new( & format!(
+                        "error: Direction {} of member {:?} does not match direction {} of {:?}!",
+                        direction, member, actual, target ) ) );
+            }
+            used[ idx ] = true;
+            pairs.push( idx );
+        }
+        let leftover : Vec< & String > = to.iter().zip( & used )
+                .filter( | ( _, used ) | ! ** used ).map( | ( ( m, _ ), _ ) | m ).collect();
+        if ! leftover.is_empty() {
+            return Err( VhdlError::new( & format!( "error: Members {:?} are not mapped!", leftover ) ) );
+        }
+        Ok( pairs )
+    }
+
+    fn contains( members : & [ ( String, Direction ) ], name : & str ) -> bool {
+        members.iter().any( | ( m, _ ) | m.to_lowercase() == name.to_lowercase() )
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn members( list : & [ ( & str, Direction ) ] ) -> Vec< ( String, Direction ) > {
+        list.iter().map( | ( m, d ) | ( m.to_string(), * d ) ).collect()
+    }
+
+    #[test]
+    fn pair_by_name() {
+        let from = members( & [ ( "rd_data", Direction::OUT ), ( "addr", Direction::IN ) ] );
+        let to = members( & [ ( "addr", Direction::OUT ), ( "read_data", Direction::IN ) ] );
+        let mapping = PortMapping::from_pairs( & [ ( "rd_data", "read_data" ) ] );
+        assert_eq!( mapping.pair( & from, & to, true ).unwrap(), vec![ 1, 0 ] );
+        assert!( mapping.pair( & from, & to, false ).is_err() );
+    }
+
+    #[test]
+    fn missing_and_leftover_members() {
+        let from = members( & [ ( "rd_data", Direction::OUT ) ] );
+        let to = members( & [ ( "read_data", Direction::IN ), ( "addr", Direction::OUT ) ] );
+        assert!( PortMapping::new().pair( & from, & to, true ).is_err() );
+        let mapping = PortMapping::from_pairs( & [ ( "rd_data", "read_data" ) ] );
+        assert!( mapping.pair( & from, & to, true ).is_err() );
+        let unknown = PortMapping::from_pairs( & [ ( "wr_data", "read_data" ) ] );
+        assert!( unknown.pair( & from, & to[ ..1 ], true ).is_err() );
+    }
+}
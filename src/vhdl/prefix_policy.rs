@@ -0,0 +1,51 @@
+
+/**
+ * Naming rule for ports and interfaces created on behalf of an instance.
+ */
+#[derive(Debug, Clone)]
+pub enum PrefixPolicy {
+    NoPrefix,
+    InstanceName,
+    Custom( String ),
+}
+
+impl PrefixPolicy {
+    pub fn apply( & self, instance : & str, name : & str ) -> String {
+        let prefix = match self {
+            PrefixPolicy::NoPrefix => "",
+            PrefixPolicy::InstanceName => instance,
+            PrefixPolicy::Custom( prefix ) => prefix,
+        };
+        if prefix.is_empty() {
+            name.to_string()
+        }
+        else if name.is_empty() {
+            prefix.to_string()
+        }
+        else {
+            format!( "{}_{}", prefix, name )
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_prefix() {
+        assert_eq!( PrefixPolicy::NoPrefix.apply( "inst", "host" ), "host" );
+    }
+
+    #[test]
+    fn instance_name() {
+        assert_eq!( PrefixPolicy::InstanceName.apply( "inst", "host" ), "inst_host" );
+        assert_eq!( PrefixPolicy::InstanceName.apply( "inst", "" ), "inst" );
+    }
+
+    #[test]
+    fn custom() {
+        assert_eq!( PrefixPolicy::Custom( "top".to_string() ).apply( "inst", "host" ), "top_host" );
+    }
+}
@@ -3,16 +3,31 @@ use crate::vhdl::keywords::*;
 use crate::vhdl::sensitivity_list::SensitivityList;
 use crate::vhdl::process_declarative_item::ProcessDeclarativeItem;
 use crate::vhdl::concurrent_statement::ConcurrentStatement;
+use crate::vhdl::sequential_statement::SequentialStatement;
 
 pub struct Process {
     label : String,
     sensitivity_list : SensitivityList,
     declarations : Vec< Box< dyn ProcessDeclarativeItem > >,
+    statements : Vec< Box< dyn SequentialStatement > >,
 }
 
 impl Process {
     pub fn new( label : & str ) -> Process {
-        Process { label : label.to_string(), sensitivity_list : SensitivityList::new(), declarations : Vec::new() }
+        Process { label : label.to_string(), sensitivity_list : SensitivityList::new(), declarations : Vec::new(),
+                statements : Vec::new() }
+    }
+
+    pub fn add_sensitivity( & mut self, signal : & str ) {
+        self.sensitivity_list.add_signal( signal );
+    }
+
+    pub fn add_declaration< D : ProcessDeclarativeItem + 'static >( & mut self, declaration : D ) {
+        self.declarations.push( Box::new( declaration ) );
+    }
+
+    pub fn add_statement< S : SequentialStatement + 'static >( & mut self, statement : S ) {
+        self.statements.push( Box::new( statement ) );
     }
 }
 
@@ -30,12 +45,31 @@ impl Element for Process {
             source.push_str( & declaration.to_source_code( indent + 1 ) );
         }
         source.push_str( & format!( "{}{}\n", indent_str, BEGIN ) );
+        for statement in & self.statements {
+            source.push_str( & statement.to_source_code( indent + 1 ) );
+        }
         source.push_str( & format!( "{}{} {} {};\n", indent_str, END, PROCESS, self.label ) );
         return source;
     }
 }
 
 impl ConcurrentStatement for Process {
+    /**
+     * Each signal once, a process is a single driver however often it assigns the signal.
+     */
+    fn get_driven_signals( & self ) -> Vec< String > {
+        let mut signals : Vec< String > = Vec::new();
+        for signal in self.statements.iter().flat_map( | s | s.get_driven_signals() ) {
+            if ! signals.contains( & signal ) {
+                signals.push( signal );
+            }
+        }
+        signals
+    }
+
+    fn get_read_signals( & self ) -> Vec< String > {
+        self.statements.iter().flat_map( | s | s.get_read_signals() ).collect()
+    }
 }
 
 
@@ -43,6 +77,8 @@ impl ConcurrentStatement for Process {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::vhdl::if_statement::IfStatement;
+    use crate::vhdl::signal_assignment::SignalAssignment;
     const HEADER : &'static str = "test : process is\n";
     const BEGIN : &'static str = "begin\n";
     const END : &'static str = "end process test;\n";
@@ -59,5 +95,25 @@ mod tests {
             format!( "{}{}{}", HEADER, BEGIN, END )
         );
     }
+
+    /**
+     * Create a clocked process with a statement.
+     */
+    #[test]
+    fn process_with_statement() {
+        let mut process = Process::new( "test" );
+        process.add_sensitivity( "clk" );
+        let mut clocked = IfStatement::new( "rising_edge( clk )" );
+        clocked.add_statement( SignalAssignment::new( "q", "d" ) );
+        process.add_statement( clocked );
+
+        assert_eq!(
+            process.to_source_code( 0 ),
+            format!( "test : process( clk ) is\n{}    if rising_edge( clk ) then\n         q <= d;\n    end if;\n{}",
+                    BEGIN, END )
+        );
+        assert_eq!( process.get_driven_signals(), vec![ "q" ] );
+        assert_eq!( process.get_read_signals(), vec![ "rising_edge", "clk", "d" ] );
+    }
 }
 
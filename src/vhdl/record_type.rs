@@ -0,0 +1,425 @@
+use crate::element::Element;
+use crate::vhdl::constant_declaration::ConstantDeclaration;
+use crate::vhdl::expression::{ get_base_identifier, get_identifiers };
+use crate::vhdl::keywords::*;
+use crate::vhdl::single_line_comment::SingleLineComment;
+use crate::vhdl::vhdl_error::VhdlError;
+
+/**
+ * Element of a record type.  Elements whose range uses identifiers, e.g. generics, are declared
+ * unconstrained and keep the range as constraint for the record subtype.
+ */
+#[derive(Clone)]
+pub struct RecordElement {
+    name : String,
+    data_type : String,
+    constraint : String,
+}
+
+impl RecordElement {
+    pub fn get_name( & self ) -> & String {
+        & self.name
+    }
+
+    pub fn get_data_type( & self ) -> & String {
+        & self.data_type
+    }
+
+    pub fn get_constraint( & self ) -> & String {
+        & self.constraint
+    }
+
+    /**
+     * Width as constant part and symbolic terms, `None` for types that can not be packed.
+     */
+    fn get_width( & self ) -> Option< Width > {
+        let base = get_base_identifier( & self.data_type )?.to_lowercase();
+        if [ "std_logic", "std_ulogic" ].contains( & base.as_str() ) {
+            return Some( Width::new( 1 ) );
+        }
+        if ! [ "std_logic_vector", "std_ulogic_vector", "unsigned", "signed" ].contains( & base.as_str() ) {
+            return None;
+        }
+        let range = match self.constraint.is_empty() {
+            true => & self.data_type[ self.data_type.find( '(' )?.. ],
+            false => & self.constraint,
+        };
+        let range = range.trim().strip_prefix( '(' )?.strip_suffix( ')' )?.trim();
+        let ( high, low ) = match range.to_lowercase().find( " downto " ) {
+            Some( idx ) => ( range[ ..idx ].trim(), range[ idx + 8.. ].trim() ),
+            None => {
+                let idx = range.to_lowercase().find( " to " )?;
+                ( range[ idx + 4.. ].trim(), range[ ..idx ].trim() )
+            },
+        };
+        match ( high.parse::< i64 >(), low.parse::< i64 >() ) {
+            ( Ok( high ), Ok( low ) ) => Some( Width::new( high - low + 1 ) ),
+            _ if low == "0" && high.ends_with( " - 1" ) =>
+                Some( Width { constant : 0, terms : vec![ high[ ..high.len() - 4 ].to_string() ] } ),
+            _ => Some( Width { constant : 1, terms : vec![ format!( "( {} ) - ( {} )", high, low ) ] } ),
+        }
+    }
+
+    fn is_scalar( & self ) -> bool {
+        self.constraint.is_empty() && ! self.data_type.contains( '(' )
+    }
+
+    fn is_numeric( & self ) -> bool {
+        get_base_identifier( & self.data_type )
+                .is_some_and( | b | [ "unsigned", "signed" ].contains( & b.to_lowercase().as_str() ) )
+    }
+}
+
+/**
+ * A number of bits given as constant and a sum of expressions, e.g. `DATA_WIDTH + 1`.
+ */
+#[derive(Clone)]
+struct Width {
+    constant : i64,
+    terms : Vec< String >,
+}
+
+impl Width {
+    fn new( constant : i64 ) -> Width {
+        Width { constant, terms : Vec::new() }
+    }
+
+    fn add( & self, other : & Width ) -> Width {
+        let mut terms = self.terms.clone();
+        terms.extend( other.terms.iter().cloned() );
+        Width { constant : self.constant + other.constant, terms }
+    }
+
+    fn is_one( & self ) -> bool {
+        self.constant == 1 && self.terms.is_empty()
+    }
+}
+
+impl std::fmt::Display for Width {
+    fn fmt( & self, f : & mut std::fmt::Formatter ) -> std::fmt::Result {
+        if self.terms.is_empty() {
+            return write!( f, "{}", self.constant );
+        }
+        write!( f, "{}", self.terms.join( " + " ) )?;
+        match self.constant {
+            0 => Ok(()),
+            c if c > 0 => write!( f, " + {}", c ),
+            c => write!( f, " - {}", -c ),
+        }
+    }
+}
+
+/**
+ * A record type declaration.  Records of `std_logic`, `std_logic_vector`, `unsigned` and
+ * `signed` elements can be packed into a `std_logic_vector`, the first element taking the most
+ * significant bits.
+ */
+#[derive(Clone)]
+pub struct RecordType {
+    name : String,
+    elements : Vec< RecordElement >,
+}
+
+impl RecordType {
+    pub fn new( name : & str ) -> RecordType {
+        RecordType { name : name.to_string(), elements : Vec::new() }
+    }
+
+    pub fn add_element( & mut self, name : & str, data_type : & str ) {
+        if let Some( idx ) = data_type.find( '(' ) {
+            let constraint = & data_type[ idx.. ];
+            if get_identifiers( constraint ).iter().any( | i | ! [ TO, DOWNTO ].contains( & i.to_lowercase().as_str() ) ) {
+                self.elements.push( RecordElement { name : name.to_string(),
+                        data_type : data_type[ ..idx ].trim_end().to_string(), constraint : constraint.to_string() } );
+                return;
+            }
+        }
+        self.elements.push( RecordElement { name : name.to_string(), data_type : data_type.to_string(),
+                constraint : String::new() } );
+    }
+
+    pub fn get_name( & self ) -> & String {
+        & self.name
+    }
+
+    pub fn get_elements( & self ) -> & Vec< RecordElement > {
+        & self.elements
+    }
+
+    /**
+     * The record type with the constraints of its unconstrained elements.
+     */
+    pub fn get_subtype( & self ) -> String {
+        let constraints : Vec< String > = self.elements.iter().filter( | e | ! e.constraint.is_empty() )
+                .map( | e | format!( "{}{}", e.name, e.constraint ) ).collect();
+        match constraints.is_empty() {
+            true => self.name.clone(),
+            false => format!( "{}( {} )", self.name, constraints.join( ", " ) ),
+        }
+    }
+
+    /**
+     * Identifiers used by the element constraints, they become parameters of the width
+     * function and of `from_slv`.
+     */
+    pub fn get_parameters( & self ) -> Vec< String > {
+        let mut parameters : Vec< String > = Vec::new();
+        for element in & self.elements {
+            for identifier in get_identifiers( & element.constraint ) {
+                if ! [ TO, DOWNTO ].contains( & identifier.to_lowercase().as_str() ) && ! parameters.contains( & identifier ) {
+                    parameters.push( identifier );
+                }
+            }
+        }
+        parameters
+    }
+
+    /**
+     * Number of bits of the packed record, an expression of the parameters for unconstrained
+     * elements.
+     */
+    pub fn get_width( & self ) -> Result< String, VhdlError > {
+        Ok( self.get_widths()?.iter().fold( Width::new( 0 ), | sum, w | sum.add( w ) ).to_string() )
+    }
+
+    /**
+     * Name of the width constant, or of the width function if the record has parameters,
+     * e.g. `STREAM_OUT_WIDTH` or `stream_out_width` for `stream_out_t`.
+     */
+    pub fn get_width_name( & self ) -> String {
+        let base = self.name.strip_suffix( "_t" ).unwrap_or( & self.name );
+        match self.get_parameters().is_empty() {
+            true => format!( "{}_WIDTH", base.to_uppercase() ),
+            false => format!( "{}_width", base ),
+        }
+    }
+
+    /**
+     * Bit range, element name and data type of every element of the packed record.
+     */
+    pub fn get_layout( & self ) -> Result< Vec< ( String, String, String ) >, VhdlError > {
+        let widths = self.get_widths()?;
+        let mut layout = Vec::new();
+        let mut low = Width::new( 0 );
+        for ( element, width ) in self.elements.iter().zip( widths.iter() ).rev() {
+            let high = low.add( width ).add( & Width::new( -1 ) );
+            let bits = match width.is_one() {
+                true => low.to_string(),
+                false => format!( "{} {} {}", high, DOWNTO, low ),
+            };
+            layout.push( ( bits, element.name.clone(), format!( "{}{}", element.data_type, element.constraint ) ) );
+            low = low.add( width );
+        }
+        layout.reverse();
+        Ok( layout )
+    }
+
+    /**
+     * Returns the layout comment table, the width constant and the declarations of `to_slv`
+     * and `from_slv` for a package.
+     */
+    pub fn get_packing_declarations( & self, indent : usize ) -> Result< String, VhdlError > {
+        let indent_str = crate::util::indent( indent );
+        let layout = self.get_layout()?;
+        let mut source = SingleLineComment::new_with_text( & format!( "{} bit layout", self.name ) )
+                .to_source_code( indent );
+        let bits_width = layout.iter().map( | l | l.0.len() ).max().unwrap_or( 0 ).max( 4 );
+        let name_width = layout.iter().map( | l | l.1.len() ).max().unwrap_or( 0 ).max( 7 );
+        for ( bits, name, data_type ) in [ ( "bits".to_string(), "element".to_string(), "type".to_string() ) ]
+                .into_iter().chain( layout ) {
+            source.push_str( & SingleLineComment::new_with_text(
+                    & format!( "  {:<bw$}  {:<nw$}  {}", bits, name, data_type, bw = bits_width, nw = name_width ) )
+                    .to_source_code( indent ) );
+        }
+        let parameters = self.get_parameters();
+        if parameters.is_empty() {
+            source.push_str( & ConstantDeclaration::new( & self.get_width_name(), "natural", & self.get_width()? )
+                    .to_source_code( indent ) );
+        }
+        else {
+            source.push_str( & format!( "{}{};\n", indent_str, self.get_width_function_header() ) );
+        }
+        source.push_str( & format!( "{}{};\n", indent_str, self.get_to_slv_header() ) );
+        source.push_str( & format!( "{}{};\n", indent_str, self.get_from_slv_header() ) );
+        Ok( source )
+    }
+
+    /**
+     * Returns the bodies of the width function, `to_slv` and `from_slv` for a package body.
+     */
+    pub fn get_packing_bodies( & self, indent : usize ) -> Result< String, VhdlError > {
+        let indent_str = crate::util::indent( indent );
+        let statement_indent_str = crate::util::indent( indent + 1 );
+        let widths = self.get_widths()?;
+        let mut functions = Vec::new();
+        if ! self.get_parameters().is_empty() {
+            functions.push( format!( "{}{} {}\n{}{}\n{}{} {};\n{}{} {} {};\n", indent_str,
+                    self.get_width_function_header(), IS, indent_str, BEGIN, statement_indent_str, RETURN,
+                    self.get_width()?, indent_str, END, FUNCTION, self.get_width_name() ) );
+        }
+
+        let elements : Vec< String > = self.elements.iter().map( | e | match e.is_numeric() {
+            true => format!( "std_logic_vector( r.{} )", e.name ),
+            false => format!( "r.{}", e.name ),
+        } ).collect();
+        let value = match self.elements.len() == 1 && self.elements[ 0 ].is_scalar() {
+            true => format!( "( 0 => {} )", elements[ 0 ] ),
+            false => elements.join( " & " ),
+        };
+        functions.push( format!( "{}{} {}\n{}{}\n{}{} {};\n{}{} {} to_slv;\n", indent_str,
+                self.get_to_slv_header(), IS, indent_str, BEGIN, statement_indent_str, RETURN, value,
+                indent_str, END, FUNCTION ) );
+
+        let mut from_slv = format!( "{}{} {}\n", indent_str, self.get_from_slv_header(), IS );
+        from_slv.push_str( & format!( "{}{} r : {};\n", statement_indent_str, VARIABLE, self.get_subtype() ) );
+        from_slv.push_str( & format!( "{}{} s : std_logic_vector( v'length - 1 {} 0 ) := v;\n",
+                statement_indent_str, VARIABLE, DOWNTO ) );
+        from_slv.push_str( & format!( "{}{} hi : natural := v'length;\n", statement_indent_str, VARIABLE ) );
+        from_slv.push_str( & format!( "{}{}\n", indent_str, BEGIN ) );
+        for ( idx, ( element, width ) ) in self.elements.iter().zip( widths.iter() ).enumerate() {
+            let length = match width.is_one() {
+                true => "1".to_string(),
+                false => format!( "r.{}'length", element.name ),
+            };
+            let bits = match element.is_scalar() {
+                true => "s( hi - 1 )".to_string(),
+                false => format!( "s( hi - 1 {} hi - {} )", DOWNTO, length ),
+            };
+            let bits = match element.is_numeric() {
+                true => format!( "{}( {} )", get_base_identifier( & element.data_type ).unwrap(), bits ),
+                false => bits,
+            };
+            from_slv.push_str( & format!( "{}r.{} := {};\n", statement_indent_str, element.name, bits ) );
+            if idx + 1 < self.elements.len() {
+                from_slv.push_str( & format!( "{}hi := hi - {};\n", statement_indent_str, length ) );
+            }
+        }
+        from_slv.push_str( & format!( "{}{} r;\n{}{} {} from_slv;\n", statement_indent_str, RETURN,
+                indent_str, END, FUNCTION ) );
+        functions.push( from_slv );
+        Ok( functions.join( "\n" ) )
+    }
+
+    fn get_widths( & self ) -> Result< Vec< Width >, VhdlError > {
+        self.elements.iter().map( | e | e.get_width().ok_or_else( || VhdlError::new( & format!(
+                "error: Element {:?} of record {:?} has type {:?} which can not be packed!",
+                e.name, self.name, e.data_type ) ) ) ).collect()
+    }
+
+    fn get_parameter_list( & self ) -> String {
+        self.get_parameters().iter().map( | p | format!( "{} : natural", p ) ).collect::< Vec< String > >().join( "; " )
+    }
+
+    fn get_width_function_header( & self ) -> String {
+        format!( "{} {}( {} ) {} natural", FUNCTION, self.get_width_name(), self.get_parameter_list(), RETURN )
+    }
+
+    fn get_to_slv_header( & self ) -> String {
+        format!( "{} to_slv( r : {} ) {} std_logic_vector", FUNCTION, self.name, RETURN )
+    }
+
+    fn get_from_slv_header( & self ) -> String {
+        match self.get_parameters().is_empty() {
+            true => format!( "{} from_slv( v : std_logic_vector ) {} {}", FUNCTION, RETURN, self.name ),
+            false => format!( "{} from_slv( v : std_logic_vector; {} ) {} {}", FUNCTION,
+                    self.get_parameter_list(), RETURN, self.name ),
+        }
+    }
+}
+
+impl Element for RecordType {
+    fn to_source_code( & self, indent : usize ) -> String {
+        let indent_str = crate::util::indent( indent );
+        let element_indent_str = crate::util::indent( indent + 1 );
+        let mut source = format!( "{}{} {} {} {}\n", indent_str, TYPE, self.name, IS, RECORD );
+        for element in & self.elements {
+            source.push_str( & format!( "{}{} : {};\n", element_indent_str, element.name, element.data_type ) );
+        }
+        source.push_str( & format!( "{}{} {} {};\n", indent_str, END, RECORD, self.name ) );
+        source
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARATIONS : & str = concat!(
+        "-- sample_t bit layout\n",
+        "--   bits         element  type\n",
+        "--   12 downto 5  data     std_logic_vector( 7 downto 0 )\n",
+        "--   4 downto 1   count    unsigned( 3 downto 0 )\n",
+        "--   0            valid    std_logic\n",
+        "constant SAMPLE_WIDTH : natural := 13;\n",
+        "function to_slv( r : sample_t ) return std_logic_vector;\n",
+        "function from_slv( v : std_logic_vector ) return sample_t;\n" );
+
+    const BODIES : & str = concat!(
+        "function to_slv( r : sample_t ) return std_logic_vector is\n",
+        "begin\n",
+        "    return r.data & std_logic_vector( r.count ) & r.valid;\n",
+        "end function to_slv;\n",
+        "\n",
+        "function from_slv( v : std_logic_vector ) return sample_t is\n",
+        "    variable r : sample_t;\n",
+        "    variable s : std_logic_vector( v'length - 1 downto 0 ) := v;\n",
+        "    variable hi : natural := v'length;\n",
+        "begin\n",
+        "    r.data := s( hi - 1 downto hi - r.data'length );\n",
+        "    hi := hi - r.data'length;\n",
+        "    r.count := unsigned( s( hi - 1 downto hi - r.count'length ) );\n",
+        "    hi := hi - r.count'length;\n",
+        "    r.valid := s( hi - 1 );\n",
+        "    return r;\n",
+        "end function from_slv;\n" );
+
+    #[test]
+    fn record_type() {
+        let mut record = RecordType::new( "sample_t" );
+        record.add_element( "data", "std_logic_vector( 7 downto 0 )" );
+        record.add_element( "count", "unsigned( 3 downto 0 )" );
+        record.add_element( "valid", "std_logic" );
+        assert_eq!( record.to_source_code( 0 ), concat!( "type sample_t is record\n",
+                "    data : std_logic_vector( 7 downto 0 );\n", "    count : unsigned( 3 downto 0 );\n",
+                "    valid : std_logic;\n", "end record sample_t;\n" ) );
+    }
+
+    #[test]
+    fn packing() -> Result< (), VhdlError > {
+        let mut record = RecordType::new( "sample_t" );
+        record.add_element( "data", "std_logic_vector( 7 downto 0 )" );
+        record.add_element( "count", "unsigned( 3 downto 0 )" );
+        record.add_element( "valid", "std_logic" );
+        assert_eq!( record.get_packing_declarations( 0 )?, DECLARATIONS );
+        assert_eq!( record.get_packing_bodies( 0 )?, BODIES );
+        Ok(())
+    }
+
+    #[test]
+    fn packing_with_parameters() -> Result< (), VhdlError > {
+        let mut record = RecordType::new( "bus_t" );
+        record.add_element( "address", "std_logic_vector( ADDR_WIDTH - 1 downto 0 )" );
+        record.add_element( "strobe", "std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 )" );
+        record.add_element( "write", "std_logic" );
+        assert_eq!( record.get_parameters(), vec![ "ADDR_WIDTH", "DATA_WIDTH" ] );
+        assert_eq!( record.get_width()?, "ADDR_WIDTH + DATA_WIDTH / 8 + 1" );
+        assert_eq!( record.get_layout()?[ 0 ].0, "DATA_WIDTH / 8 + ADDR_WIDTH downto DATA_WIDTH / 8 + 1" );
+        let declarations = record.get_packing_declarations( 0 )?;
+        assert!( declarations.contains( "function bus_width( ADDR_WIDTH : natural; DATA_WIDTH : natural ) return natural;\n" ) );
+        assert!( declarations.contains(
+                "function from_slv( v : std_logic_vector; ADDR_WIDTH : natural; DATA_WIDTH : natural ) return bus_t;\n" ) );
+        let bodies = record.get_packing_bodies( 0 )?;
+        assert!( bodies.contains( "    return ADDR_WIDTH + DATA_WIDTH / 8 + 1;\n" ) );
+        assert!( bodies.contains(
+                "    variable r : bus_t( address( ADDR_WIDTH - 1 downto 0 ), strobe( DATA_WIDTH / 8 - 1 downto 0 ) );\n" ) );
+        Ok(())
+    }
+
+    #[test]
+    fn unpackable() {
+        let mut record = RecordType::new( "test_t" );
+        record.add_element( "count", "integer" );
+        assert!( record.get_packing_declarations( 0 ).is_err() );
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use serde_derive::Deserialize;
+use crate::vhdl::direction::Direction;
+
+/**
+ * A named role of an interface class, e.g. `host` or `agent`, with the mode of every port in
+ * that role like a VHDL-2019 mode view.
+ */
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(from = "RoleViewDescription")]
+pub struct RoleView {
+    name : String,
+    modes : Vec< ( String, Direction ) >,
+}
+
+/**
+ * A view is given by its name only or by name and the modes of some ports, the modes of the
+ * other ports are derived, see `EntityInterface::add_view`.
+ */
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RoleViewDescription {
+    Name( String ),
+    View {
+        name : String,
+        #[serde(default)]
+        modes : HashMap< String, Direction >,
+    },
+}
+
+impl From< RoleViewDescription > for RoleView {
+    fn from( description : RoleViewDescription ) -> RoleView {
+        match description {
+            RoleViewDescription::Name( name ) => RoleView::new( & name ),
+            RoleViewDescription::View { name, modes } => {
+                let mut view = RoleView::new( & name );
+                let mut modes : Vec< ( String, Direction ) > = modes.into_iter().collect();
+                modes.sort_by( | a, b | a.0.cmp( & b.0 ) );
+                view.modes = modes;
+                view
+            },
+        }
+    }
+}
+
+impl RoleView {
+    pub fn new( name : & str ) -> RoleView {
+        RoleView { name : name.to_string(), modes : Vec::new() }
+    }
+
+    pub fn get_name( & self ) -> & String {
+        & self.name
+    }
+
+    /**
+     * Port base names and their modes in this view.
+     */
+    pub fn get_modes( & self ) -> & Vec< ( String, Direction ) > {
+        & self.modes
+    }
+
+    pub fn get_mode( & self, port : & str ) -> Option< Direction > {
+        self.modes.iter().find( | ( p, _ ) | p == port ).map( | ( _, mode ) | * mode )
+    }
+
+    pub fn retain_modes< F : Fn( & str ) -> bool >( & mut self, predicate : F ) {
+        self.modes.retain( | ( p, _ ) | predicate( p ) );
+    }
+
+    /**
+     * Renames the ports found in `names` all at once, so names may be swapped.
+     */
+    pub fn rename_modes( & mut self, names : & HashMap< String, String > ) {
+        for entry in & mut self.modes {
+            if let Some( name ) = names.get( & entry.0 ) {
+                entry.0 = name.clone();
+            }
+        }
+    }
+
+    pub fn set_mode( & mut self, port : & str, mode : Direction ) {
+        match self.modes.iter_mut().find( | ( p, _ ) | p == port ) {
+            Some( entry ) => entry.1 = mode,
+            None => self.modes.push( ( port.to_string(), mode ) ),
+        }
+    }
+}
+
+/**
+ * Splits an interface reference like `avalon_mmi@agent` into class and role.
+ */
+pub fn split_role_reference( reference : & str ) -> ( & str, Option< & str > ) {
+    match reference.split_once( '@' ) {
+        Some( ( class, role ) ) => ( class, Some( role ) ),
+        None => ( reference, None ),
+    }
+}
+
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn deserialize() -> Result< (), Box< dyn Error > > {
+        let views : Vec< RoleView > = serde_json::from_str(
+                "[ \"host\", { \"name\" : \"agent\", \"modes\" : { \"data\" : \"buffer\" } } ]" )?;
+        assert_eq!( views[ 0 ], RoleView::new( "host" ) );
+        assert_eq!( views[ 1 ].get_name(), "agent" );
+        assert_eq!( views[ 1 ].get_mode( "data" ), Some( Direction::BUFFER ) );
+        assert_eq!( views[ 1 ].get_mode( "valid" ), None );
+        Ok(())
+    }
+
+    #[test]
+    fn role_reference() {
+        assert_eq!( split_role_reference( "avalon_mmi@agent" ), ( "avalon_mmi", Some( "agent" ) ) );
+        assert_eq!( split_role_reference( "avalon_mmi" ), ( "avalon_mmi", None ) );
+    }
+}
@@ -1,6 +1,8 @@
 use crate::element::Element;
 use crate::vhdl::operators::*;
 use crate::vhdl::concurrent_statement::ConcurrentStatement;
+use crate::vhdl::sequential_statement::SequentialStatement;
+use crate::vhdl::expression::{ get_base_identifier, get_identifiers };
 
 pub struct SignalAssignment {
     label : String,
@@ -37,5 +39,22 @@ impl Element for SignalAssignment {
 }
 
 impl ConcurrentStatement for SignalAssignment {
+    fn get_driven_signals( & self ) -> Vec< String > {
+        get_base_identifier( & self.signal ).into_iter().collect()
+    }
+
+    fn get_read_signals( & self ) -> Vec< String > {
+        get_identifiers( & self.expression )
+    }
+}
+
+impl SequentialStatement for SignalAssignment {
+    fn get_driven_signals( & self ) -> Vec< String > {
+        ConcurrentStatement::get_driven_signals( self )
+    }
+
+    fn get_read_signals( & self ) -> Vec< String > {
+        ConcurrentStatement::get_read_signals( self )
+    }
 }
 
@@ -3,6 +3,7 @@ use crate::element::Element;
 use crate::vhdl::design_unit::DesignUnit;
 use crate::vhdl::entity::Entity;
 use crate::vhdl::architecture::Architecture;
+use crate::vhdl::interface_package::InterfacePackage;
 use crate::vhdl::single_line_comment::SingleLineComment;
 
 pub struct VhdlFile {
@@ -19,6 +20,10 @@ impl VhdlFile {
         self.design_units.push( Box::< Entity >::new( design_unit ) );
     }
 
+    pub fn add_package( & mut self, design_unit : InterfacePackage ) {
+        self.design_units.push( Box::< InterfacePackage >::new( design_unit ) );
+    }
+
     pub fn add_architecture( & mut self, design_unit : Architecture ) {
         self.design_units.push( Box::< Architecture >::new( design_unit ) );
     }
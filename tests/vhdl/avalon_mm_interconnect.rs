@@ -0,0 +1,86 @@
+use std::path::Path;
+use std::error::Error;
+use source_generator::source_file::SourceFile;
+use source_generator::vhdl::vhdl_file::VhdlFile;
+use source_generator::vhdl::entity::Entity;
+use source_generator::vhdl::entity_interface::EntityInterface;
+use source_generator::vhdl::architecture::Architecture;
+use source_generator::vhdl::instance::Instance;
+use source_generator::vhdl::interconnect::avalon_mm::AvalonMmInterconnect;
+
+/**
+ * This example connects the host cpu to the agents ram and uart through a generated
+ * interconnect.  The interconnect decodes the address, ram is mapped to 0x0000 - 0x0fff and
+ * uart to 0x1000 - 0x100f.
+ *
+ *                +-------------------------------------------------------------+
+ *                |                             main                            |
+ *                |                                                             |
+ *                |  +-------+               +--------------+     +-------+     |
+ *                |  |  cpu  |<= avalon_mm =>| interconnect |<===>|  ram  |     |
+ *                |  +-------+               |              |     +-------+     |
+ *                |                          |              |     +-------+     |
+ *                |                          |              |<===>|  uart |     |
+ *                |                          +--------------+     +-------+     |
+ *                |                                                             |
+ *                +-------------------------------------------------------------+
+ */
+const OUTPUT_FILE : & str =  "tests/vhdl/test_avalon_mm_interconnect.vhd";
+const EXPECTED_FILE : & str =  "tests/vhdl/expected_avalon_mm_interconnect.vhd";
+
+fn main() -> Result< (), Box< dyn Error > > {
+    let avalon_mm = EntityInterface::from_file_unnamed(
+        Path::new( "tests/vhdl/avalon_mm.json" ) )?;
+    let mut main = Entity::new( "main" );
+    for generic in avalon_mm.get_generics() {
+        main.add_generic( generic.clone() );
+    }
+    let mut interconnect = AvalonMmInterconnect::new( "interconnect", & avalon_mm )?;
+    interconnect.add_host( "cpu" )?;
+    interconnect.add_agent( "ram", 0x0000, 0x1000 )?;
+    interconnect.add_agent( "uart", 0x1000, 0x10 )?;
+    let interconnect_arch = interconnect.get_architecture()?;
+    let mut host = avalon_mm.clone();
+    host.rename( "host" );
+    let mut cpu = Entity::new( "cpu" );
+    cpu.add_interface( & host )?;
+    let mut ram = Entity::new( "ram" );
+    ram.add_interface( & avalon_mm.clone_inverted_with_rename( "agent" ) )?;
+    let mut uart = Entity::new( "uart" );
+    uart.add_interface( & avalon_mm.clone_inverted_with_rename( "agent" ) )?;
+
+    let mut arch = Architecture::new( "struct", & main );
+    arch.add_instance( Instance::from_entity( "cpu", & cpu ) );
+    arch.add_instance( Instance::from_entity( "interconnect", interconnect_arch.get_entity() ) );
+    arch.add_instance( Instance::from_entity( "ram", & ram ) );
+    arch.add_instance( Instance::from_entity( "uart", & uart ) );
+    arch.connect_instance_to_instance_by_interface( "cpu", "interconnect", "host", "cpu" )?;
+    arch.connect_instance_to_instance_by_interface( "interconnect", "ram", "ram", "agent" )?;
+    arch.connect_instance_to_instance_by_interface( "interconnect", "uart", "uart", "agent" )?;
+    for instance in [ "cpu", "interconnect", "ram", "uart" ] {
+        arch.connect_instance_unbound_by_name( instance )?;
+    }
+
+    let mut vhdl_file = VhdlFile::new( OUTPUT_FILE );
+    vhdl_file.add_entity( cpu );
+    vhdl_file.add_entity( ram );
+    vhdl_file.add_entity( uart );
+    vhdl_file.add_architecture( interconnect_arch );
+    vhdl_file.add_architecture( arch );
+    vhdl_file.write()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utility::*;
+    #[test]
+    fn avalon_mm_interconnect() -> Result< (), Box< dyn Error > > {
+        {
+            main()?;
+            compare_files_with_header( OUTPUT_FILE, EXPECTED_FILE )?;
+        }
+        Ok(())
+    }
+}
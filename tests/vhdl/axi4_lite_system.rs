@@ -0,0 +1,107 @@
+use std::path::Path;
+use std::error::Error;
+use source_generator::source_file::SourceFile;
+use source_generator::vhdl::vhdl_file::VhdlFile;
+use source_generator::vhdl::direction::Direction;
+use source_generator::vhdl::entity::Entity;
+use source_generator::vhdl::entity_interface::EntityInterface;
+use source_generator::vhdl::architecture::Architecture;
+use source_generator::vhdl::instance::Instance;
+use source_generator::vhdl::port::Port;
+use source_generator::vhdl::auto_connect::LeftoverPolicy;
+use source_generator::vhdl::interconnect::new_clk_rst_interface;
+use source_generator::vhdl::interconnect::axi4_lite::{ Axi4LiteCrossbar, new_axi4_lite_interface };
+use source_generator::vhdl::interconnect::apb::{ Axi4LiteToApbBridge, new_apb_interface };
+use source_generator::vhdl::interconnect::avalon_mm_to_axi4_lite::AvalonMmToAxi4LiteBridge;
+
+/**
+ * The Avalon-MM host cpu reaches the AXI4-Lite agent ram and the APB completer uart through
+ * generated bridges and a crossbar.  All interfaces are connected by `auto_connect`, the
+ * bridges and the crossbar only by the classes and names of their interfaces.
+ *
+ *              +------------------------------------------------------------------------------+
+ *              |                                     main                                     |
+ *              |  +-----+   avalon_mm  +--------+ axi4_lite +----------+ axi4_lite +-----+     |
+ *              |  | cpu |<============>| bridge |<=========>| crossbar |<=========>| ram |     |
+ *              |  +-----+              +--------+           |          |           +-----+     |
+ *              |                                            |          | axi4_lite +------------+ apb +------+
+ *              |                                            |          |<=========>| apb_bridge |<===>| uart |
+ *              |                                            +----------+           +------------+     +------+
+ * clk, rst-----|--> bridge, crossbar, apb_bridge                                              |
+ *              +------------------------------------------------------------------------------+
+ */
+const OUTPUT_FILE : & str =  "tests/vhdl/test_axi4_lite_system.vhd";
+const EXPECTED_FILE : & str =  "tests/vhdl/expected_axi4_lite_system.vhd";
+
+fn main() -> Result< (), Box< dyn Error > > {
+    let mut avalon_mm = EntityInterface::from_file_unnamed( Path::new( "tests/vhdl/avalon_mm.json" ) )?;
+    avalon_mm.add_port( Port::new( "waitrequest", Direction::IN, "std_logic" ) );
+    avalon_mm.rename( "host" );
+    avalon_mm.remove_interface_from_generics();
+    let mut main = Entity::new( "main" );
+    main.add_interface( & new_clk_rst_interface() )?;
+    for generic in avalon_mm.get_generics() {
+        main.add_generic( generic.clone() );
+    }
+
+    let mut cpu = Entity::new( "cpu" );
+    cpu.add_interface( & avalon_mm )?;
+    let bridge = AvalonMmToAxi4LiteBridge::new( "avalon_mm_to_axi4_lite", & avalon_mm )?;
+    let mut crossbar = Axi4LiteCrossbar::new( "crossbar" );
+    crossbar.add_host( "axi" )?;
+    crossbar.add_agent( "ram", 0x0000, 0x1000 )?;
+    crossbar.add_agent( "peripherals", 0x1000, 0x1000 )?;
+    let apb_bridge = Axi4LiteToApbBridge::new( "axi4_lite_to_apb" );
+    let mut ram = Entity::new( "ram" );
+    let mut axi = new_axi4_lite_interface();
+    for generic in axi.get_generics() {
+        ram.add_generic( generic.clone() );
+    }
+    axi.remove_generics();
+    ram.add_interface( & axi.clone_inverted_with_rename( "ram" ) )?;
+    let mut uart = Entity::new( "uart" );
+    let mut apb = new_apb_interface();
+    for generic in apb.get_generics() {
+        uart.add_generic( generic.clone() );
+    }
+    apb.remove_generics();
+    uart.add_interface( & apb.clone_inverted_with_rename( "apb" ) )?;
+
+    let bridge_arch = bridge.get_architecture()?;
+    let crossbar_arch = crossbar.get_architecture()?;
+    let apb_bridge_arch = apb_bridge.get_architecture()?;
+    let mut arch = Architecture::new( "struct", & main );
+    arch.add_instance( Instance::from_entity( "cpu", & cpu ) );
+    arch.add_instance( Instance::from_entity( "bridge", bridge_arch.get_entity() ) );
+    arch.add_instance( Instance::from_entity( "crossbar", crossbar_arch.get_entity() ) );
+    arch.add_instance( Instance::from_entity( "ram", & ram ) );
+    arch.add_instance( Instance::from_entity( "apb_bridge", apb_bridge_arch.get_entity() ) );
+    arch.add_instance( Instance::from_entity( "uart", & uart ) );
+    let report = arch.auto_connect( & LeftoverPolicy::Keep )?;
+    assert!( report.get_unmatched().is_empty() );
+
+    let mut vhdl_file = VhdlFile::new( OUTPUT_FILE );
+    vhdl_file.add_entity( cpu );
+    vhdl_file.add_entity( ram );
+    vhdl_file.add_entity( uart );
+    vhdl_file.add_architecture( bridge_arch );
+    vhdl_file.add_architecture( crossbar_arch );
+    vhdl_file.add_architecture( apb_bridge_arch );
+    vhdl_file.add_architecture( arch );
+    vhdl_file.write()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utility::*;
+    #[test]
+    fn axi4_lite_system() -> Result< (), Box< dyn Error > > {
+        {
+            main()?;
+            compare_files_with_header( OUTPUT_FILE, EXPECTED_FILE )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+--
+-- Automatically generated on 2026-10-19 06:51:28.
+-- Do _NOT_ edit!
+--
+library ieee;
+    use ieee.std_logic_1164.all;
+
+entity cpu is
+    generic (
+        host_ADDR_WIDTH : positive;
+        host_DATA_WIDTH : positive;
+        host_BYTE_COUNT : positive
+    );
+    port (
+        host_address : out std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        host_byteenable : out std_logic_vector( BYTE_COUNT - 1 downto 0 );
+        host_read : out std_logic;
+        host_read_data : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        host_response : in std_logic_vector( 1 downto 0 );
+        host_write : out std_logic;
+        host_write_data : out std_logic_vector( DATA_WIDTH - 1 downto 0 )
+    );
+begin
+end entity cpu;
+
+library ieee;
+    use ieee.std_logic_1164.all;
+
+entity ram is
+    generic (
+        agent_ADDR_WIDTH : positive;
+        agent_DATA_WIDTH : positive;
+        agent_BYTE_COUNT : positive
+    );
+    port (
+        agent_address : in std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        agent_byteenable : in std_logic_vector( BYTE_COUNT - 1 downto 0 );
+        agent_read : in std_logic;
+        agent_read_data : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        agent_response : out std_logic_vector( 1 downto 0 );
+        agent_write : in std_logic;
+        agent_write_data : in std_logic_vector( DATA_WIDTH - 1 downto 0 )
+    );
+begin
+end entity ram;
+
+library ieee;
+    use ieee.std_logic_1164.all;
+
+entity uart is
+    generic (
+        agent_ADDR_WIDTH : positive;
+        agent_DATA_WIDTH : positive;
+        agent_BYTE_COUNT : positive
+    );
+    port (
+        agent_address : in std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        agent_byteenable : in std_logic_vector( BYTE_COUNT - 1 downto 0 );
+        agent_read : in std_logic;
+        agent_read_data : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        agent_response : out std_logic_vector( 1 downto 0 );
+        agent_write : in std_logic;
+        agent_write_data : in std_logic_vector( DATA_WIDTH - 1 downto 0 )
+    );
+begin
+end entity uart;
+
+library ieee;
+    use ieee.numeric_std.all;
+    use ieee.std_logic_1164.all;
+
+entity interconnect is
+    generic (
+        ADDR_WIDTH : positive;
+        DATA_WIDTH : positive;
+        BYTE_COUNT : positive
+    );
+    port (
+        cpu_address : in std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        cpu_byteenable : in std_logic_vector( BYTE_COUNT - 1 downto 0 );
+        cpu_read : in std_logic;
+        cpu_read_data : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        cpu_response : out std_logic_vector( 1 downto 0 );
+        cpu_write : in std_logic;
+        cpu_write_data : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        ram_address : out std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        ram_byteenable : out std_logic_vector( BYTE_COUNT - 1 downto 0 );
+        ram_read : out std_logic;
+        ram_read_data : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        ram_response : in std_logic_vector( 1 downto 0 );
+        ram_write : out std_logic;
+        ram_write_data : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        uart_address : out std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        uart_byteenable : out std_logic_vector( BYTE_COUNT - 1 downto 0 );
+        uart_read : out std_logic;
+        uart_read_data : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        uart_response : in std_logic_vector( 1 downto 0 );
+        uart_write : out std_logic;
+        uart_write_data : out std_logic_vector( DATA_WIDTH - 1 downto 0 )
+    );
+begin
+end entity interconnect;
+
+architecture rtl of interconnect is
+    signal bus_address : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal bus_byteenable : std_logic_vector( BYTE_COUNT - 1 downto 0 );
+    signal bus_read : std_logic;
+    signal bus_read_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal bus_response : std_logic_vector( 1 downto 0 );
+    signal bus_write : std_logic;
+    signal bus_write_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    constant RAM_BASE : unsigned( 15 downto 0 ) := x"0000";
+    constant RAM_LAST : unsigned( 15 downto 0 ) := x"0FFF";
+    constant UART_BASE : unsigned( 15 downto 0 ) := x"1000";
+    constant UART_LAST : unsigned( 15 downto 0 ) := x"100F";
+    signal ram_select : std_logic;
+    signal uart_select : std_logic;
+begin
+     bus_address <= cpu_address;
+     bus_byteenable <= cpu_byteenable;
+     bus_read <= cpu_read;
+     bus_write <= cpu_write;
+     bus_write_data <= cpu_write_data;
+     ram_select <= '1' when unsigned( bus_address ) >= RAM_BASE and unsigned( bus_address ) <= RAM_LAST else '0';
+     ram_address <= std_logic_vector( resize( unsigned( bus_address ) - RAM_BASE, bus_address'length ) );
+     ram_byteenable <= bus_byteenable;
+     ram_read <= bus_read and ram_select;
+     ram_write <= bus_write and ram_select;
+     ram_write_data <= bus_write_data;
+     uart_select <= '1' when unsigned( bus_address ) >= UART_BASE and unsigned( bus_address ) <= UART_LAST else '0';
+     uart_address <= std_logic_vector( resize( unsigned( bus_address ) - UART_BASE, bus_address'length ) );
+     uart_byteenable <= bus_byteenable;
+     uart_read <= bus_read and uart_select;
+     uart_write <= bus_write and uart_select;
+     uart_write_data <= bus_write_data;
+     bus_read_data <= ram_read_data when ram_select = '1' else
+        uart_read_data when uart_select = '1' else
+        (others => '0');
+     cpu_read_data <= bus_read_data;
+     bus_response <= ram_response when ram_select = '1' else
+        uart_response when uart_select = '1' else
+        "11";
+     cpu_response <= bus_response;
+end architecture rtl;
+
+library ieee;
+    use ieee.std_logic_1164.all;
+
+entity main is
+    generic (
+        ADDR_WIDTH : positive;
+        DATA_WIDTH : positive;
+        BYTE_COUNT : positive
+    );
+begin
+end entity main;
+
+architecture struct of main is
+    signal cpu_to_interconnect_host_address : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal cpu_to_interconnect_host_byteenable : std_logic_vector( BYTE_COUNT - 1 downto 0 );
+    signal cpu_to_interconnect_host_read : std_logic;
+    signal interconnect_to_cpu_host_read_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal interconnect_to_cpu_host_response : std_logic_vector( 1 downto 0 );
+    signal cpu_to_interconnect_host_write : std_logic;
+    signal cpu_to_interconnect_host_write_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal interconnect_to_ram_ram_address : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal interconnect_to_ram_ram_byteenable : std_logic_vector( BYTE_COUNT - 1 downto 0 );
+    signal interconnect_to_ram_ram_read : std_logic;
+    signal ram_to_interconnect_ram_read_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal ram_to_interconnect_ram_response : std_logic_vector( 1 downto 0 );
+    signal interconnect_to_ram_ram_write : std_logic;
+    signal interconnect_to_ram_ram_write_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal interconnect_to_uart_uart_address : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal interconnect_to_uart_uart_byteenable : std_logic_vector( BYTE_COUNT - 1 downto 0 );
+    signal interconnect_to_uart_uart_read : std_logic;
+    signal uart_to_interconnect_uart_read_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal uart_to_interconnect_uart_response : std_logic_vector( 1 downto 0 );
+    signal interconnect_to_uart_uart_write : std_logic;
+    signal interconnect_to_uart_uart_write_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+begin
+    cpu : entity work.cpu
+        generic map (
+            host_ADDR_WIDTH => ADDR_WIDTH,
+            host_DATA_WIDTH => DATA_WIDTH,
+            host_BYTE_COUNT => BYTE_COUNT
+        )
+        port map (
+            host_address => cpu_to_interconnect_host_address,
+            host_byteenable => cpu_to_interconnect_host_byteenable,
+            host_read => cpu_to_interconnect_host_read,
+            host_read_data => interconnect_to_cpu_host_read_data,
+            host_response => interconnect_to_cpu_host_response,
+            host_write => cpu_to_interconnect_host_write,
+            host_write_data => cpu_to_interconnect_host_write_data
+        );
+    interconnect : entity work.interconnect
+        generic map (
+            ADDR_WIDTH => ADDR_WIDTH,
+            DATA_WIDTH => DATA_WIDTH,
+            BYTE_COUNT => BYTE_COUNT
+        )
+        port map (
+            cpu_address => cpu_to_interconnect_host_address,
+            cpu_byteenable => cpu_to_interconnect_host_byteenable,
+            cpu_read => cpu_to_interconnect_host_read,
+            cpu_read_data => interconnect_to_cpu_host_read_data,
+            cpu_response => interconnect_to_cpu_host_response,
+            cpu_write => cpu_to_interconnect_host_write,
+            cpu_write_data => cpu_to_interconnect_host_write_data,
+            ram_address => interconnect_to_ram_ram_address,
+            ram_byteenable => interconnect_to_ram_ram_byteenable,
+            ram_read => interconnect_to_ram_ram_read,
+            ram_read_data => ram_to_interconnect_ram_read_data,
+            ram_response => ram_to_interconnect_ram_response,
+            ram_write => interconnect_to_ram_ram_write,
+            ram_write_data => interconnect_to_ram_ram_write_data,
+            uart_address => interconnect_to_uart_uart_address,
+            uart_byteenable => interconnect_to_uart_uart_byteenable,
+            uart_read => interconnect_to_uart_uart_read,
+            uart_read_data => uart_to_interconnect_uart_read_data,
+            uart_response => uart_to_interconnect_uart_response,
+            uart_write => interconnect_to_uart_uart_write,
+            uart_write_data => interconnect_to_uart_uart_write_data
+        );
+    ram : entity work.ram
+        generic map (
+            agent_ADDR_WIDTH => ADDR_WIDTH,
+            agent_DATA_WIDTH => DATA_WIDTH,
+            agent_BYTE_COUNT => BYTE_COUNT
+        )
+        port map (
+            agent_address => interconnect_to_ram_ram_address,
+            agent_byteenable => interconnect_to_ram_ram_byteenable,
+            agent_read => interconnect_to_ram_ram_read,
+            agent_read_data => ram_to_interconnect_ram_read_data,
+            agent_response => ram_to_interconnect_ram_response,
+            agent_write => interconnect_to_ram_ram_write,
+            agent_write_data => interconnect_to_ram_ram_write_data
+        );
+    uart : entity work.uart
+        generic map (
+            agent_ADDR_WIDTH => ADDR_WIDTH,
+            agent_DATA_WIDTH => DATA_WIDTH,
+            agent_BYTE_COUNT => BYTE_COUNT
+        )
+        port map (
+            agent_address => interconnect_to_uart_uart_address,
+            agent_byteenable => interconnect_to_uart_uart_byteenable,
+            agent_read => interconnect_to_uart_uart_read,
+            agent_read_data => uart_to_interconnect_uart_read_data,
+            agent_response => uart_to_interconnect_uart_response,
+            agent_write => interconnect_to_uart_uart_write,
+            agent_write_data => interconnect_to_uart_uart_write_data
+        );
+end architecture struct;
+
@@ -0,0 +1,901 @@
+--
+-- Automatically generated on 2026-10-19 06:51:28.
+-- Do _NOT_ edit!
+--
+library ieee;
+    use ieee.std_logic_1164.all;
+
+entity cpu is
+    generic (
+        ADDR_WIDTH : positive;
+        DATA_WIDTH : positive;
+        BYTE_COUNT : positive
+    );
+    port (
+        host_address : out std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        host_byteenable : out std_logic_vector( BYTE_COUNT - 1 downto 0 );
+        host_read : out std_logic;
+        host_read_data : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        host_response : in std_logic_vector( 1 downto 0 );
+        host_write : out std_logic;
+        host_write_data : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        host_waitrequest : in std_logic
+    );
+begin
+end entity cpu;
+
+library ieee;
+    use ieee.std_logic_1164.all;
+
+entity ram is
+    generic (
+        ADDR_WIDTH : positive;
+        DATA_WIDTH : positive
+    );
+    port (
+        ram_awaddr : in std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        ram_awprot : in std_logic_vector( 2 downto 0 );
+        ram_awvalid : in std_logic;
+        ram_awready : out std_logic;
+        ram_wdata : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        ram_wstrb : in std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+        ram_wvalid : in std_logic;
+        ram_wready : out std_logic;
+        ram_bresp : out std_logic_vector( 1 downto 0 );
+        ram_bvalid : out std_logic;
+        ram_bready : in std_logic;
+        ram_araddr : in std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        ram_arprot : in std_logic_vector( 2 downto 0 );
+        ram_arvalid : in std_logic;
+        ram_arready : out std_logic;
+        ram_rdata : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        ram_rresp : out std_logic_vector( 1 downto 0 );
+        ram_rvalid : out std_logic;
+        ram_rready : in std_logic
+    );
+begin
+end entity ram;
+
+library ieee;
+    use ieee.std_logic_1164.all;
+
+entity uart is
+    generic (
+        ADDR_WIDTH : positive;
+        DATA_WIDTH : positive
+    );
+    port (
+        apb_paddr : in std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        apb_pprot : in std_logic_vector( 2 downto 0 );
+        apb_psel : in std_logic;
+        apb_penable : in std_logic;
+        apb_pwrite : in std_logic;
+        apb_pwdata : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        apb_pstrb : in std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+        apb_pready : out std_logic;
+        apb_prdata : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        apb_pslverr : out std_logic
+    );
+begin
+end entity uart;
+
+library ieee;
+    use ieee.std_logic_1164.all;
+
+entity avalon_mm_to_axi4_lite is
+    generic (
+        ADDR_WIDTH : positive;
+        DATA_WIDTH : positive;
+        BYTE_COUNT : positive
+    );
+    port (
+        clk : in std_logic;
+        rst : in std_logic;
+        avalon_address : in std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        avalon_byteenable : in std_logic_vector( BYTE_COUNT - 1 downto 0 );
+        avalon_read : in std_logic;
+        avalon_read_data : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        avalon_response : out std_logic_vector( 1 downto 0 );
+        avalon_write : in std_logic;
+        avalon_write_data : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        avalon_waitrequest : out std_logic;
+        axi_awaddr : out std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        axi_awprot : out std_logic_vector( 2 downto 0 );
+        axi_awvalid : out std_logic;
+        axi_awready : in std_logic;
+        axi_wdata : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        axi_wstrb : out std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+        axi_wvalid : out std_logic;
+        axi_wready : in std_logic;
+        axi_bresp : in std_logic_vector( 1 downto 0 );
+        axi_bvalid : in std_logic;
+        axi_bready : out std_logic;
+        axi_araddr : out std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        axi_arprot : out std_logic_vector( 2 downto 0 );
+        axi_arvalid : out std_logic;
+        axi_arready : in std_logic;
+        axi_rdata : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        axi_rresp : in std_logic_vector( 1 downto 0 );
+        axi_rvalid : in std_logic;
+        axi_rready : out std_logic
+    );
+begin
+end entity avalon_mm_to_axi4_lite;
+
+architecture rtl of avalon_mm_to_axi4_lite is
+    constant STATE_IDLE : std_logic_vector( 2 downto 0 ) := "000";
+    constant STATE_WRITE : std_logic_vector( 2 downto 0 ) := "001";
+    constant STATE_WRITE_RESPONSE : std_logic_vector( 2 downto 0 ) := "010";
+    constant STATE_READ : std_logic_vector( 2 downto 0 ) := "011";
+    constant STATE_READ_RESPONSE : std_logic_vector( 2 downto 0 ) := "100";
+    constant STATE_DONE : std_logic_vector( 2 downto 0 ) := "101";
+    signal state : std_logic_vector( 2 downto 0 );
+    signal write_address_done : std_logic;
+    signal write_data_done : std_logic;
+    signal read_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal response : std_logic_vector( 1 downto 0 );
+begin
+     axi_awaddr <= avalon_address;
+     axi_awprot <= "000";
+     axi_awvalid <= '1' when state = STATE_WRITE and write_address_done = '0' else '0';
+     axi_wdata <= avalon_write_data;
+     axi_wstrb <= avalon_byteenable;
+     axi_wvalid <= '1' when state = STATE_WRITE and write_data_done = '0' else '0';
+     axi_bready <= '1' when state = STATE_WRITE_RESPONSE else '0';
+     axi_araddr <= avalon_address;
+     axi_arprot <= "000";
+     axi_arvalid <= '1' when state = STATE_READ else '0';
+     axi_rready <= '1' when state = STATE_READ_RESPONSE else '0';
+     avalon_read_data <= read_data;
+     avalon_waitrequest <= '0' when state = STATE_DONE else '1';
+     avalon_response <= response;
+    bridge : process( clk ) is
+    begin
+        if rising_edge( clk ) then
+            if rst = '1' then
+                 state <= STATE_IDLE;
+            elsif state = STATE_IDLE then
+                 write_address_done <= '0';
+                 write_data_done <= '0';
+                if avalon_write = '1' then
+                     state <= STATE_WRITE;
+                elsif avalon_read = '1' then
+                     state <= STATE_READ;
+                end if;
+            elsif state = STATE_WRITE then
+                if axi_awready = '1' then
+                     write_address_done <= '1';
+                end if;
+                if axi_wready = '1' then
+                     write_data_done <= '1';
+                end if;
+                if ( write_address_done = '1' or axi_awready = '1' ) and ( write_data_done = '1' or axi_wready = '1' ) then
+                     state <= STATE_WRITE_RESPONSE;
+                end if;
+            elsif state = STATE_WRITE_RESPONSE then
+                if axi_bvalid = '1' then
+                     state <= STATE_DONE;
+                     response <= axi_bresp;
+                end if;
+            elsif state = STATE_READ then
+                if axi_arready = '1' then
+                     state <= STATE_READ_RESPONSE;
+                end if;
+            elsif state = STATE_READ_RESPONSE then
+                if axi_rvalid = '1' then
+                     state <= STATE_DONE;
+                     read_data <= axi_rdata;
+                     response <= axi_rresp;
+                end if;
+            else
+                 state <= STATE_IDLE;
+            end if;
+        end if;
+    end process bridge;
+end architecture rtl;
+
+library ieee;
+    use ieee.numeric_std.all;
+    use ieee.std_logic_1164.all;
+
+entity crossbar is
+    generic (
+        ADDR_WIDTH : positive;
+        DATA_WIDTH : positive
+    );
+    port (
+        clk : in std_logic;
+        rst : in std_logic;
+        axi_awaddr : in std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        axi_awprot : in std_logic_vector( 2 downto 0 );
+        axi_awvalid : in std_logic;
+        axi_awready : out std_logic;
+        axi_wdata : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        axi_wstrb : in std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+        axi_wvalid : in std_logic;
+        axi_wready : out std_logic;
+        axi_bresp : out std_logic_vector( 1 downto 0 );
+        axi_bvalid : out std_logic;
+        axi_bready : in std_logic;
+        axi_araddr : in std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        axi_arprot : in std_logic_vector( 2 downto 0 );
+        axi_arvalid : in std_logic;
+        axi_arready : out std_logic;
+        axi_rdata : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        axi_rresp : out std_logic_vector( 1 downto 0 );
+        axi_rvalid : out std_logic;
+        axi_rready : in std_logic;
+        ram_awaddr : out std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        ram_awprot : out std_logic_vector( 2 downto 0 );
+        ram_awvalid : out std_logic;
+        ram_awready : in std_logic;
+        ram_wdata : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        ram_wstrb : out std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+        ram_wvalid : out std_logic;
+        ram_wready : in std_logic;
+        ram_bresp : in std_logic_vector( 1 downto 0 );
+        ram_bvalid : in std_logic;
+        ram_bready : out std_logic;
+        ram_araddr : out std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        ram_arprot : out std_logic_vector( 2 downto 0 );
+        ram_arvalid : out std_logic;
+        ram_arready : in std_logic;
+        ram_rdata : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        ram_rresp : in std_logic_vector( 1 downto 0 );
+        ram_rvalid : in std_logic;
+        ram_rready : out std_logic;
+        peripherals_awaddr : out std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        peripherals_awprot : out std_logic_vector( 2 downto 0 );
+        peripherals_awvalid : out std_logic;
+        peripherals_awready : in std_logic;
+        peripherals_wdata : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        peripherals_wstrb : out std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+        peripherals_wvalid : out std_logic;
+        peripherals_wready : in std_logic;
+        peripherals_bresp : in std_logic_vector( 1 downto 0 );
+        peripherals_bvalid : in std_logic;
+        peripherals_bready : out std_logic;
+        peripherals_araddr : out std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        peripherals_arprot : out std_logic_vector( 2 downto 0 );
+        peripherals_arvalid : out std_logic;
+        peripherals_arready : in std_logic;
+        peripherals_rdata : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        peripherals_rresp : in std_logic_vector( 1 downto 0 );
+        peripherals_rvalid : in std_logic;
+        peripherals_rready : out std_logic
+    );
+begin
+end entity crossbar;
+
+architecture rtl of crossbar is
+    signal bus_awaddr : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal bus_awprot : std_logic_vector( 2 downto 0 );
+    signal bus_awvalid : std_logic;
+    signal bus_awready : std_logic;
+    signal bus_wdata : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal bus_wstrb : std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+    signal bus_wvalid : std_logic;
+    signal bus_wready : std_logic;
+    signal bus_bresp : std_logic_vector( 1 downto 0 );
+    signal bus_bvalid : std_logic;
+    signal bus_bready : std_logic;
+    signal bus_araddr : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal bus_arprot : std_logic_vector( 2 downto 0 );
+    signal bus_arvalid : std_logic;
+    signal bus_arready : std_logic;
+    signal bus_rdata : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal bus_rresp : std_logic_vector( 1 downto 0 );
+    signal bus_rvalid : std_logic;
+    signal bus_rready : std_logic;
+    constant RAM_BASE : unsigned( 15 downto 0 ) := x"0000";
+    constant RAM_LAST : unsigned( 15 downto 0 ) := x"0FFF";
+    constant PERIPHERALS_BASE : unsigned( 15 downto 0 ) := x"1000";
+    constant PERIPHERALS_LAST : unsigned( 15 downto 0 ) := x"1FFF";
+    signal write_request_address : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal ram_write_hit : std_logic;
+    signal peripherals_write_hit : std_logic;
+    signal write_active : std_logic;
+    signal write_error : std_logic;
+    signal write_error_response : std_logic;
+    signal write_aw_done : std_logic;
+    signal write_w_done : std_logic;
+    signal axi_write_grant : std_logic;
+    signal ram_write_select : std_logic;
+    signal peripherals_write_select : std_logic;
+    signal read_request_address : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal ram_read_hit : std_logic;
+    signal peripherals_read_hit : std_logic;
+    signal read_active : std_logic;
+    signal read_error : std_logic;
+    signal read_error_response : std_logic;
+    signal read_ar_done : std_logic;
+    signal axi_read_grant : std_logic;
+    signal ram_read_select : std_logic;
+    signal peripherals_read_select : std_logic;
+begin
+     write_request_address <= axi_awaddr when axi_awvalid = '1' else
+        (others => '0');
+     ram_write_hit <= '1' when unsigned( write_request_address ) >= RAM_BASE and unsigned( write_request_address ) <= RAM_LAST else '0';
+     peripherals_write_hit <= '1' when unsigned( write_request_address ) >= PERIPHERALS_BASE and unsigned( write_request_address ) <= PERIPHERALS_LAST else '0';
+     bus_awaddr <= axi_awaddr when axi_write_grant = '1' else
+        (others => '0');
+     ram_awaddr <= std_logic_vector( resize( unsigned( bus_awaddr ) - RAM_BASE, bus_awaddr'length ) );
+     peripherals_awaddr <= std_logic_vector( resize( unsigned( bus_awaddr ) - PERIPHERALS_BASE, bus_awaddr'length ) );
+     bus_awprot <= axi_awprot when axi_write_grant = '1' else
+        (others => '0');
+     ram_awprot <= bus_awprot;
+     peripherals_awprot <= bus_awprot;
+     bus_awvalid <= axi_awvalid when axi_write_grant = '1' else
+        '0';
+     ram_awvalid <= bus_awvalid and ram_write_select and not write_aw_done;
+     peripherals_awvalid <= bus_awvalid and peripherals_write_select and not write_aw_done;
+     bus_wdata <= axi_wdata when axi_write_grant = '1' else
+        (others => '0');
+     ram_wdata <= bus_wdata;
+     peripherals_wdata <= bus_wdata;
+     bus_wstrb <= axi_wstrb when axi_write_grant = '1' else
+        (others => '0');
+     ram_wstrb <= bus_wstrb;
+     peripherals_wstrb <= bus_wstrb;
+     bus_wvalid <= axi_wvalid when axi_write_grant = '1' else
+        '0';
+     ram_wvalid <= bus_wvalid and ram_write_select and not write_w_done;
+     peripherals_wvalid <= bus_wvalid and peripherals_write_select and not write_w_done;
+     bus_bready <= axi_bready when axi_write_grant = '1' else
+        '0';
+     ram_bready <= bus_bready and ram_write_select;
+     peripherals_bready <= bus_bready and peripherals_write_select;
+     bus_awready <= ram_awready when ram_write_select = '1' else
+        peripherals_awready when peripherals_write_select = '1' else
+        write_error and not write_aw_done;
+     axi_awready <= bus_awready and axi_write_grant and not write_aw_done;
+     bus_wready <= ram_wready when ram_write_select = '1' else
+        peripherals_wready when peripherals_write_select = '1' else
+        write_error and not write_w_done;
+     axi_wready <= bus_wready and axi_write_grant and not write_w_done;
+     bus_bresp <= ram_bresp when ram_write_select = '1' else
+        peripherals_bresp when peripherals_write_select = '1' else
+        "11";
+     axi_bresp <= bus_bresp;
+     bus_bvalid <= ram_bvalid when ram_write_select = '1' else
+        peripherals_bvalid when peripherals_write_select = '1' else
+        write_error_response;
+     axi_bvalid <= bus_bvalid and axi_write_grant;
+    write_arbiter : process( clk ) is
+    begin
+        if rising_edge( clk ) then
+            if rst = '1' then
+                 write_active <= '0';
+                 write_error <= '0';
+                 write_error_response <= '0';
+                 write_aw_done <= '0';
+                 write_w_done <= '0';
+                 axi_write_grant <= '0';
+                 ram_write_select <= '0';
+                 peripherals_write_select <= '0';
+            elsif write_active = '0' then
+                if axi_awvalid = '1' then
+                     write_active <= '1';
+                     axi_write_grant <= axi_awvalid;
+                     ram_write_select <= ram_write_hit;
+                     peripherals_write_select <= peripherals_write_hit;
+                     write_error <= not ( ram_write_hit or peripherals_write_hit );
+                end if;
+            elsif bus_bvalid = '1' and bus_bready = '1' then
+                 write_active <= '0';
+                 write_error <= '0';
+                 write_error_response <= '0';
+                 write_aw_done <= '0';
+                 write_w_done <= '0';
+                 axi_write_grant <= '0';
+                 ram_write_select <= '0';
+                 peripherals_write_select <= '0';
+            else
+                if bus_awvalid = '1' and bus_awready = '1' then
+                     write_aw_done <= '1';
+                end if;
+                if bus_wvalid = '1' and bus_wready = '1' then
+                     write_w_done <= '1';
+                end if;
+                if write_error = '1' and write_aw_done = '1' and write_w_done = '1' then
+                     write_error_response <= '1';
+                end if;
+            end if;
+        end if;
+    end process write_arbiter;
+     read_request_address <= axi_araddr when axi_arvalid = '1' else
+        (others => '0');
+     ram_read_hit <= '1' when unsigned( read_request_address ) >= RAM_BASE and unsigned( read_request_address ) <= RAM_LAST else '0';
+     peripherals_read_hit <= '1' when unsigned( read_request_address ) >= PERIPHERALS_BASE and unsigned( read_request_address ) <= PERIPHERALS_LAST else '0';
+     bus_araddr <= axi_araddr when axi_read_grant = '1' else
+        (others => '0');
+     ram_araddr <= std_logic_vector( resize( unsigned( bus_araddr ) - RAM_BASE, bus_araddr'length ) );
+     peripherals_araddr <= std_logic_vector( resize( unsigned( bus_araddr ) - PERIPHERALS_BASE, bus_araddr'length ) );
+     bus_arprot <= axi_arprot when axi_read_grant = '1' else
+        (others => '0');
+     ram_arprot <= bus_arprot;
+     peripherals_arprot <= bus_arprot;
+     bus_arvalid <= axi_arvalid when axi_read_grant = '1' else
+        '0';
+     ram_arvalid <= bus_arvalid and ram_read_select and not read_ar_done;
+     peripherals_arvalid <= bus_arvalid and peripherals_read_select and not read_ar_done;
+     bus_rready <= axi_rready when axi_read_grant = '1' else
+        '0';
+     ram_rready <= bus_rready and ram_read_select;
+     peripherals_rready <= bus_rready and peripherals_read_select;
+     bus_arready <= ram_arready when ram_read_select = '1' else
+        peripherals_arready when peripherals_read_select = '1' else
+        read_error and not read_ar_done;
+     axi_arready <= bus_arready and axi_read_grant and not read_ar_done;
+     bus_rdata <= ram_rdata when ram_read_select = '1' else
+        peripherals_rdata when peripherals_read_select = '1' else
+        (others => '0');
+     axi_rdata <= bus_rdata;
+     bus_rresp <= ram_rresp when ram_read_select = '1' else
+        peripherals_rresp when peripherals_read_select = '1' else
+        "11";
+     axi_rresp <= bus_rresp;
+     bus_rvalid <= ram_rvalid when ram_read_select = '1' else
+        peripherals_rvalid when peripherals_read_select = '1' else
+        read_error_response;
+     axi_rvalid <= bus_rvalid and axi_read_grant;
+    read_arbiter : process( clk ) is
+    begin
+        if rising_edge( clk ) then
+            if rst = '1' then
+                 read_active <= '0';
+                 read_error <= '0';
+                 read_error_response <= '0';
+                 read_ar_done <= '0';
+                 axi_read_grant <= '0';
+                 ram_read_select <= '0';
+                 peripherals_read_select <= '0';
+            elsif read_active = '0' then
+                if axi_arvalid = '1' then
+                     read_active <= '1';
+                     axi_read_grant <= axi_arvalid;
+                     ram_read_select <= ram_read_hit;
+                     peripherals_read_select <= peripherals_read_hit;
+                     read_error <= not ( ram_read_hit or peripherals_read_hit );
+                end if;
+            elsif bus_rvalid = '1' and bus_rready = '1' then
+                 read_active <= '0';
+                 read_error <= '0';
+                 read_error_response <= '0';
+                 read_ar_done <= '0';
+                 axi_read_grant <= '0';
+                 ram_read_select <= '0';
+                 peripherals_read_select <= '0';
+            else
+                if bus_arvalid = '1' and bus_arready = '1' then
+                     read_ar_done <= '1';
+                end if;
+                if read_error = '1' and read_ar_done = '1' then
+                     read_error_response <= '1';
+                end if;
+            end if;
+        end if;
+    end process read_arbiter;
+end architecture rtl;
+
+library ieee;
+    use ieee.std_logic_1164.all;
+
+entity axi4_lite_to_apb is
+    generic (
+        ADDR_WIDTH : positive;
+        DATA_WIDTH : positive
+    );
+    port (
+        clk : in std_logic;
+        rst : in std_logic;
+        axi_awaddr : in std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        axi_awprot : in std_logic_vector( 2 downto 0 );
+        axi_awvalid : in std_logic;
+        axi_awready : out std_logic;
+        axi_wdata : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        axi_wstrb : in std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+        axi_wvalid : in std_logic;
+        axi_wready : out std_logic;
+        axi_bresp : out std_logic_vector( 1 downto 0 );
+        axi_bvalid : out std_logic;
+        axi_bready : in std_logic;
+        axi_araddr : in std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        axi_arprot : in std_logic_vector( 2 downto 0 );
+        axi_arvalid : in std_logic;
+        axi_arready : out std_logic;
+        axi_rdata : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        axi_rresp : out std_logic_vector( 1 downto 0 );
+        axi_rvalid : out std_logic;
+        axi_rready : in std_logic;
+        apb_paddr : out std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+        apb_pprot : out std_logic_vector( 2 downto 0 );
+        apb_psel : out std_logic;
+        apb_penable : out std_logic;
+        apb_pwrite : out std_logic;
+        apb_pwdata : out std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        apb_pstrb : out std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+        apb_pready : in std_logic;
+        apb_prdata : in std_logic_vector( DATA_WIDTH - 1 downto 0 );
+        apb_pslverr : in std_logic
+    );
+begin
+end entity axi4_lite_to_apb;
+
+architecture rtl of axi4_lite_to_apb is
+    constant STATE_IDLE : std_logic_vector( 1 downto 0 ) := "00";
+    constant STATE_SETUP : std_logic_vector( 1 downto 0 ) := "01";
+    constant STATE_ACCESS : std_logic_vector( 1 downto 0 ) := "10";
+    signal state : std_logic_vector( 1 downto 0 );
+    signal transfer_address : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal transfer_prot : std_logic_vector( 2 downto 0 );
+    signal transfer_write : std_logic;
+    signal transfer_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal transfer_strobe : std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+    signal write_request : std_logic;
+    signal read_request : std_logic;
+    signal write_response_valid : std_logic;
+    signal write_response : std_logic_vector( 1 downto 0 );
+    signal read_response_valid : std_logic;
+    signal read_response : std_logic_vector( 1 downto 0 );
+    signal read_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+begin
+     write_request <= axi_awvalid and axi_wvalid and not write_response_valid;
+     read_request <= axi_arvalid and not read_response_valid and not write_request;
+     axi_awready <= write_request when state = STATE_IDLE else '0';
+     axi_wready <= write_request when state = STATE_IDLE else '0';
+     axi_bresp <= write_response;
+     axi_bvalid <= write_response_valid;
+     axi_arready <= read_request when state = STATE_IDLE else '0';
+     axi_rdata <= read_data;
+     axi_rresp <= read_response;
+     axi_rvalid <= read_response_valid;
+     apb_paddr <= transfer_address;
+     apb_pprot <= transfer_prot;
+     apb_psel <= '0' when state = STATE_IDLE else '1';
+     apb_penable <= '1' when state = STATE_ACCESS else '0';
+     apb_pwrite <= transfer_write;
+     apb_pwdata <= transfer_data;
+     apb_pstrb <= transfer_strobe;
+    bridge : process( clk ) is
+    begin
+        if rising_edge( clk ) then
+            if rst = '1' then
+                 state <= STATE_IDLE;
+                 write_response_valid <= '0';
+                 read_response_valid <= '0';
+            else
+                if axi_bready = '1' then
+                     write_response_valid <= '0';
+                end if;
+                if axi_rready = '1' then
+                     read_response_valid <= '0';
+                end if;
+                if state = STATE_IDLE then
+                    if write_request = '1' then
+                         state <= STATE_SETUP;
+                         transfer_address <= axi_awaddr;
+                         transfer_prot <= axi_awprot;
+                         transfer_write <= '1';
+                         transfer_data <= axi_wdata;
+                         transfer_strobe <= axi_wstrb;
+                    elsif read_request = '1' then
+                         state <= STATE_SETUP;
+                         transfer_address <= axi_araddr;
+                         transfer_prot <= axi_arprot;
+                         transfer_write <= '0';
+                         transfer_strobe <= (others => '0');
+                    end if;
+                elsif state = STATE_SETUP then
+                     state <= STATE_ACCESS;
+                elsif apb_pready = '1' then
+                     state <= STATE_IDLE;
+                    if transfer_write = '1' then
+                         write_response_valid <= '1';
+                         write_response <= apb_pslverr & '0';
+                    else
+                         read_response_valid <= '1';
+                         read_response <= apb_pslverr & '0';
+                         read_data <= apb_prdata;
+                    end if;
+                end if;
+            end if;
+        end if;
+    end process bridge;
+end architecture rtl;
+
+library ieee;
+    use ieee.std_logic_1164.all;
+
+entity main is
+    generic (
+        ADDR_WIDTH : positive;
+        DATA_WIDTH : positive;
+        BYTE_COUNT : positive
+    );
+    port (
+        clk : in std_logic;
+        rst : in std_logic
+    );
+begin
+end entity main;
+
+architecture struct of main is
+    signal cpu_to_bridge_host_address : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal cpu_to_bridge_host_byteenable : std_logic_vector( BYTE_COUNT - 1 downto 0 );
+    signal cpu_to_bridge_host_read : std_logic;
+    signal bridge_to_cpu_host_read_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal bridge_to_cpu_host_response : std_logic_vector( 1 downto 0 );
+    signal cpu_to_bridge_host_write : std_logic;
+    signal cpu_to_bridge_host_write_data : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal bridge_to_cpu_host_waitrequest : std_logic;
+    signal bridge_to_crossbar_axi_awaddr : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal bridge_to_crossbar_axi_awprot : std_logic_vector( 2 downto 0 );
+    signal bridge_to_crossbar_axi_awvalid : std_logic;
+    signal crossbar_to_bridge_axi_awready : std_logic;
+    signal bridge_to_crossbar_axi_wdata : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal bridge_to_crossbar_axi_wstrb : std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+    signal bridge_to_crossbar_axi_wvalid : std_logic;
+    signal crossbar_to_bridge_axi_wready : std_logic;
+    signal crossbar_to_bridge_axi_bresp : std_logic_vector( 1 downto 0 );
+    signal crossbar_to_bridge_axi_bvalid : std_logic;
+    signal bridge_to_crossbar_axi_bready : std_logic;
+    signal bridge_to_crossbar_axi_araddr : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal bridge_to_crossbar_axi_arprot : std_logic_vector( 2 downto 0 );
+    signal bridge_to_crossbar_axi_arvalid : std_logic;
+    signal crossbar_to_bridge_axi_arready : std_logic;
+    signal crossbar_to_bridge_axi_rdata : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal crossbar_to_bridge_axi_rresp : std_logic_vector( 1 downto 0 );
+    signal crossbar_to_bridge_axi_rvalid : std_logic;
+    signal bridge_to_crossbar_axi_rready : std_logic;
+    signal crossbar_to_ram_ram_awaddr : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal crossbar_to_ram_ram_awprot : std_logic_vector( 2 downto 0 );
+    signal crossbar_to_ram_ram_awvalid : std_logic;
+    signal ram_to_crossbar_ram_awready : std_logic;
+    signal crossbar_to_ram_ram_wdata : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal crossbar_to_ram_ram_wstrb : std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+    signal crossbar_to_ram_ram_wvalid : std_logic;
+    signal ram_to_crossbar_ram_wready : std_logic;
+    signal ram_to_crossbar_ram_bresp : std_logic_vector( 1 downto 0 );
+    signal ram_to_crossbar_ram_bvalid : std_logic;
+    signal crossbar_to_ram_ram_bready : std_logic;
+    signal crossbar_to_ram_ram_araddr : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal crossbar_to_ram_ram_arprot : std_logic_vector( 2 downto 0 );
+    signal crossbar_to_ram_ram_arvalid : std_logic;
+    signal ram_to_crossbar_ram_arready : std_logic;
+    signal ram_to_crossbar_ram_rdata : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal ram_to_crossbar_ram_rresp : std_logic_vector( 1 downto 0 );
+    signal ram_to_crossbar_ram_rvalid : std_logic;
+    signal crossbar_to_ram_ram_rready : std_logic;
+    signal crossbar_to_apb_bridge_peripherals_awaddr : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal crossbar_to_apb_bridge_peripherals_awprot : std_logic_vector( 2 downto 0 );
+    signal crossbar_to_apb_bridge_peripherals_awvalid : std_logic;
+    signal apb_bridge_to_crossbar_peripherals_awready : std_logic;
+    signal crossbar_to_apb_bridge_peripherals_wdata : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal crossbar_to_apb_bridge_peripherals_wstrb : std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+    signal crossbar_to_apb_bridge_peripherals_wvalid : std_logic;
+    signal apb_bridge_to_crossbar_peripherals_wready : std_logic;
+    signal apb_bridge_to_crossbar_peripherals_bresp : std_logic_vector( 1 downto 0 );
+    signal apb_bridge_to_crossbar_peripherals_bvalid : std_logic;
+    signal crossbar_to_apb_bridge_peripherals_bready : std_logic;
+    signal crossbar_to_apb_bridge_peripherals_araddr : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal crossbar_to_apb_bridge_peripherals_arprot : std_logic_vector( 2 downto 0 );
+    signal crossbar_to_apb_bridge_peripherals_arvalid : std_logic;
+    signal apb_bridge_to_crossbar_peripherals_arready : std_logic;
+    signal apb_bridge_to_crossbar_peripherals_rdata : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal apb_bridge_to_crossbar_peripherals_rresp : std_logic_vector( 1 downto 0 );
+    signal apb_bridge_to_crossbar_peripherals_rvalid : std_logic;
+    signal crossbar_to_apb_bridge_peripherals_rready : std_logic;
+    signal apb_bridge_to_uart_apb_paddr : std_logic_vector( ADDR_WIDTH - 1 downto 0 );
+    signal apb_bridge_to_uart_apb_pprot : std_logic_vector( 2 downto 0 );
+    signal apb_bridge_to_uart_apb_psel : std_logic;
+    signal apb_bridge_to_uart_apb_penable : std_logic;
+    signal apb_bridge_to_uart_apb_pwrite : std_logic;
+    signal apb_bridge_to_uart_apb_pwdata : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal apb_bridge_to_uart_apb_pstrb : std_logic_vector( DATA_WIDTH / 8 - 1 downto 0 );
+    signal uart_to_apb_bridge_apb_pready : std_logic;
+    signal uart_to_apb_bridge_apb_prdata : std_logic_vector( DATA_WIDTH - 1 downto 0 );
+    signal uart_to_apb_bridge_apb_pslverr : std_logic;
+begin
+    cpu : entity work.cpu
+        generic map (
+            ADDR_WIDTH => ADDR_WIDTH,
+            DATA_WIDTH => DATA_WIDTH,
+            BYTE_COUNT => BYTE_COUNT
+        )
+        port map (
+            host_address => cpu_to_bridge_host_address,
+            host_byteenable => cpu_to_bridge_host_byteenable,
+            host_read => cpu_to_bridge_host_read,
+            host_read_data => bridge_to_cpu_host_read_data,
+            host_response => bridge_to_cpu_host_response,
+            host_write => cpu_to_bridge_host_write,
+            host_write_data => cpu_to_bridge_host_write_data,
+            host_waitrequest => bridge_to_cpu_host_waitrequest
+        );
+    bridge : entity work.avalon_mm_to_axi4_lite
+        generic map (
+            ADDR_WIDTH => ADDR_WIDTH,
+            DATA_WIDTH => DATA_WIDTH,
+            BYTE_COUNT => BYTE_COUNT
+        )
+        port map (
+            clk => clk,
+            rst => rst,
+            avalon_address => cpu_to_bridge_host_address,
+            avalon_byteenable => cpu_to_bridge_host_byteenable,
+            avalon_read => cpu_to_bridge_host_read,
+            avalon_read_data => bridge_to_cpu_host_read_data,
+            avalon_response => bridge_to_cpu_host_response,
+            avalon_write => cpu_to_bridge_host_write,
+            avalon_write_data => cpu_to_bridge_host_write_data,
+            avalon_waitrequest => bridge_to_cpu_host_waitrequest,
+            axi_awaddr => bridge_to_crossbar_axi_awaddr,
+            axi_awprot => bridge_to_crossbar_axi_awprot,
+            axi_awvalid => bridge_to_crossbar_axi_awvalid,
+            axi_awready => crossbar_to_bridge_axi_awready,
+            axi_wdata => bridge_to_crossbar_axi_wdata,
+            axi_wstrb => bridge_to_crossbar_axi_wstrb,
+            axi_wvalid => bridge_to_crossbar_axi_wvalid,
+            axi_wready => crossbar_to_bridge_axi_wready,
+            axi_bresp => crossbar_to_bridge_axi_bresp,
+            axi_bvalid => crossbar_to_bridge_axi_bvalid,
+            axi_bready => bridge_to_crossbar_axi_bready,
+            axi_araddr => bridge_to_crossbar_axi_araddr,
+            axi_arprot => bridge_to_crossbar_axi_arprot,
+            axi_arvalid => bridge_to_crossbar_axi_arvalid,
+            axi_arready => crossbar_to_bridge_axi_arready,
+            axi_rdata => crossbar_to_bridge_axi_rdata,
+            axi_rresp => crossbar_to_bridge_axi_rresp,
+            axi_rvalid => crossbar_to_bridge_axi_rvalid,
+            axi_rready => bridge_to_crossbar_axi_rready
+        );
+    crossbar : entity work.crossbar
+        generic map (
+            ADDR_WIDTH => ADDR_WIDTH,
+            DATA_WIDTH => DATA_WIDTH
+        )
+        port map (
+            clk => clk,
+            rst => rst,
+            axi_awaddr => bridge_to_crossbar_axi_awaddr,
+            axi_awprot => bridge_to_crossbar_axi_awprot,
+            axi_awvalid => bridge_to_crossbar_axi_awvalid,
+            axi_awready => crossbar_to_bridge_axi_awready,
+            axi_wdata => bridge_to_crossbar_axi_wdata,
+            axi_wstrb => bridge_to_crossbar_axi_wstrb,
+            axi_wvalid => bridge_to_crossbar_axi_wvalid,
+            axi_wready => crossbar_to_bridge_axi_wready,
+            axi_bresp => crossbar_to_bridge_axi_bresp,
+            axi_bvalid => crossbar_to_bridge_axi_bvalid,
+            axi_bready => bridge_to_crossbar_axi_bready,
+            axi_araddr => bridge_to_crossbar_axi_araddr,
+            axi_arprot => bridge_to_crossbar_axi_arprot,
+            axi_arvalid => bridge_to_crossbar_axi_arvalid,
+            axi_arready => crossbar_to_bridge_axi_arready,
+            axi_rdata => crossbar_to_bridge_axi_rdata,
+            axi_rresp => crossbar_to_bridge_axi_rresp,
+            axi_rvalid => crossbar_to_bridge_axi_rvalid,
+            axi_rready => bridge_to_crossbar_axi_rready,
+            ram_awaddr => crossbar_to_ram_ram_awaddr,
+            ram_awprot => crossbar_to_ram_ram_awprot,
+            ram_awvalid => crossbar_to_ram_ram_awvalid,
+            ram_awready => ram_to_crossbar_ram_awready,
+            ram_wdata => crossbar_to_ram_ram_wdata,
+            ram_wstrb => crossbar_to_ram_ram_wstrb,
+            ram_wvalid => crossbar_to_ram_ram_wvalid,
+            ram_wready => ram_to_crossbar_ram_wready,
+            ram_bresp => ram_to_crossbar_ram_bresp,
+            ram_bvalid => ram_to_crossbar_ram_bvalid,
+            ram_bready => crossbar_to_ram_ram_bready,
+            ram_araddr => crossbar_to_ram_ram_araddr,
+            ram_arprot => crossbar_to_ram_ram_arprot,
+            ram_arvalid => crossbar_to_ram_ram_arvalid,
+            ram_arready => ram_to_crossbar_ram_arready,
+            ram_rdata => ram_to_crossbar_ram_rdata,
+            ram_rresp => ram_to_crossbar_ram_rresp,
+            ram_rvalid => ram_to_crossbar_ram_rvalid,
+            ram_rready => crossbar_to_ram_ram_rready,
+            peripherals_awaddr => crossbar_to_apb_bridge_peripherals_awaddr,
+            peripherals_awprot => crossbar_to_apb_bridge_peripherals_awprot,
+            peripherals_awvalid => crossbar_to_apb_bridge_peripherals_awvalid,
+            peripherals_awready => apb_bridge_to_crossbar_peripherals_awready,
+            peripherals_wdata => crossbar_to_apb_bridge_peripherals_wdata,
+            peripherals_wstrb => crossbar_to_apb_bridge_peripherals_wstrb,
+            peripherals_wvalid => crossbar_to_apb_bridge_peripherals_wvalid,
+            peripherals_wready => apb_bridge_to_crossbar_peripherals_wready,
+            peripherals_bresp => apb_bridge_to_crossbar_peripherals_bresp,
+            peripherals_bvalid => apb_bridge_to_crossbar_peripherals_bvalid,
+            peripherals_bready => crossbar_to_apb_bridge_peripherals_bready,
+            peripherals_araddr => crossbar_to_apb_bridge_peripherals_araddr,
+            peripherals_arprot => crossbar_to_apb_bridge_peripherals_arprot,
+            peripherals_arvalid => crossbar_to_apb_bridge_peripherals_arvalid,
+            peripherals_arready => apb_bridge_to_crossbar_peripherals_arready,
+            peripherals_rdata => apb_bridge_to_crossbar_peripherals_rdata,
+            peripherals_rresp => apb_bridge_to_crossbar_peripherals_rresp,
+            peripherals_rvalid => apb_bridge_to_crossbar_peripherals_rvalid,
+            peripherals_rready => crossbar_to_apb_bridge_peripherals_rready
+        );
+    ram : entity work.ram
+        generic map (
+            ADDR_WIDTH => ADDR_WIDTH,
+            DATA_WIDTH => DATA_WIDTH
+        )
+        port map (
+            ram_awaddr => crossbar_to_ram_ram_awaddr,
+            ram_awprot => crossbar_to_ram_ram_awprot,
+            ram_awvalid => crossbar_to_ram_ram_awvalid,
+            ram_awready => ram_to_crossbar_ram_awready,
+            ram_wdata => crossbar_to_ram_ram_wdata,
+            ram_wstrb => crossbar_to_ram_ram_wstrb,
+            ram_wvalid => crossbar_to_ram_ram_wvalid,
+            ram_wready => ram_to_crossbar_ram_wready,
+            ram_bresp => ram_to_crossbar_ram_bresp,
+            ram_bvalid => ram_to_crossbar_ram_bvalid,
+            ram_bready => crossbar_to_ram_ram_bready,
+            ram_araddr => crossbar_to_ram_ram_araddr,
+            ram_arprot => crossbar_to_ram_ram_arprot,
+            ram_arvalid => crossbar_to_ram_ram_arvalid,
+            ram_arready => ram_to_crossbar_ram_arready,
+            ram_rdata => ram_to_crossbar_ram_rdata,
+            ram_rresp => ram_to_crossbar_ram_rresp,
+            ram_rvalid => ram_to_crossbar_ram_rvalid,
+            ram_rready => crossbar_to_ram_ram_rready
+        );
+    apb_bridge : entity work.axi4_lite_to_apb
+        generic map (
+            ADDR_WIDTH => ADDR_WIDTH,
+            DATA_WIDTH => DATA_WIDTH
+        )
+        port map (
+            clk => clk,
+            rst => rst,
+            axi_awaddr => crossbar_to_apb_bridge_peripherals_awaddr,
+            axi_awprot => crossbar_to_apb_bridge_peripherals_awprot,
+            axi_awvalid => crossbar_to_apb_bridge_peripherals_awvalid,
+            axi_awready => apb_bridge_to_crossbar_peripherals_awready,
+            axi_wdata => crossbar_to_apb_bridge_peripherals_wdata,
+            axi_wstrb => crossbar_to_apb_bridge_peripherals_wstrb,
+            axi_wvalid => crossbar_to_apb_bridge_peripherals_wvalid,
+            axi_wready => apb_bridge_to_crossbar_peripherals_wready,
+            axi_bresp => apb_bridge_to_crossbar_peripherals_bresp,
+            axi_bvalid => apb_bridge_to_crossbar_peripherals_bvalid,
+            axi_bready => crossbar_to_apb_bridge_peripherals_bready,
+            axi_araddr => crossbar_to_apb_bridge_peripherals_araddr,
+            axi_arprot => crossbar_to_apb_bridge_peripherals_arprot,
+            axi_arvalid => crossbar_to_apb_bridge_peripherals_arvalid,
+            axi_arready => apb_bridge_to_crossbar_peripherals_arready,
+            axi_rdata => apb_bridge_to_crossbar_peripherals_rdata,
+            axi_rresp => apb_bridge_to_crossbar_peripherals_rresp,
+            axi_rvalid => apb_bridge_to_crossbar_peripherals_rvalid,
+            axi_rready => crossbar_to_apb_bridge_peripherals_rready,
+            apb_paddr => apb_bridge_to_uart_apb_paddr,
+            apb_pprot => apb_bridge_to_uart_apb_pprot,
+            apb_psel => apb_bridge_to_uart_apb_psel,
+            apb_penable => apb_bridge_to_uart_apb_penable,
+            apb_pwrite => apb_bridge_to_uart_apb_pwrite,
+            apb_pwdata => apb_bridge_to_uart_apb_pwdata,
+            apb_pstrb => apb_bridge_to_uart_apb_pstrb,
+            apb_pready => uart_to_apb_bridge_apb_pready,
+            apb_prdata => uart_to_apb_bridge_apb_prdata,
+            apb_pslverr => uart_to_apb_bridge_apb_pslverr
+        );
+    uart : entity work.uart
+        generic map (
+            ADDR_WIDTH => ADDR_WIDTH,
+            DATA_WIDTH => DATA_WIDTH
+        )
+        port map (
+            apb_paddr => apb_bridge_to_uart_apb_paddr,
+            apb_pprot => apb_bridge_to_uart_apb_pprot,
+            apb_psel => apb_bridge_to_uart_apb_psel,
+            apb_penable => apb_bridge_to_uart_apb_penable,
+            apb_pwrite => apb_bridge_to_uart_apb_pwrite,
+            apb_pwdata => apb_bridge_to_uart_apb_pwdata,
+            apb_pstrb => apb_bridge_to_uart_apb_pstrb,
+            apb_pready => uart_to_apb_bridge_apb_pready,
+            apb_prdata => uart_to_apb_bridge_apb_prdata,
+            apb_pslverr => uart_to_apb_bridge_apb_pslverr
+        );
+end architecture struct;
+
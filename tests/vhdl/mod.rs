@@ -2,3 +2,6 @@ pub mod connect_clk_rst;
 pub mod connect_ports;
 pub mod connect_instances;
 pub mod connect_instances_and_ports;
+pub mod promote_unbound;
+pub mod avalon_mm_interconnect;
+pub mod axi4_lite_system;
@@ -0,0 +1,66 @@
+use std::path::Path;
+use std::error::Error;
+use source_generator::source_file::SourceFile;
+use source_generator::vhdl::vhdl_file::VhdlFile;
+use source_generator::vhdl::entity::Entity;
+use source_generator::vhdl::entity_interface::EntityInterface;
+use source_generator::vhdl::architecture::Architecture;
+use source_generator::vhdl::instance::Instance;
+use source_generator::vhdl::prefix_policy::PrefixPolicy;
+
+/**
+ * Two sub modules are chained by their avalon_mm interfaces.  The interfaces left unbound after
+ * the connection are promoted to the main entity with the instance name as prefix.
+ *
+ *                    +---------------------------------------------------------------+
+ *                    |                             main                              |
+ *                    |  +-------------------+                 +-------------------+  |
+ *                    |  |       left        |                 |      right        |  |
+ * left_agent=========|=>|agent         host |<= avalon_mm ==> |agent         host |==|==>right_host
+ *                    |  +-------------------+                 +-------------------+  |
+ *                    +---------------------------------------------------------------+
+ */
+const OUTPUT_FILE : & str =  "tests/vhdl/test_promote_unbound.vhd";
+const EXPECTED_FILE : & str =  "tests/vhdl/expected_promote_unbound.vhd";
+
+fn main() -> Result< (), Box< dyn Error > > {
+    let mut host = EntityInterface::from_file( "host",
+        Path::new( "tests/vhdl/avalon_mm.json" ) )?;
+    host.remove_interface_from_generics();
+    let mut agent = host.clone_inverted_with_rename( "agent" );
+    agent.remove_generics();
+    let mut main = Entity::new( "main" );
+    for generic in host.get_generics() {
+        main.add_generic( generic.clone() );
+    }
+    let mut sub = Entity::new( "sub" );
+    sub.add_interface( & host )?;
+    sub.add_interface( & agent )?;
+    let mut arch = Architecture::new( "struct", & main );
+    arch.add_instance( Instance::from_entity( "left", & sub ) );
+    arch.add_instance( Instance::from_entity( "right", & sub ) );
+    arch.connect_instance_to_instance_by_interface( "left", "right", "host", "agent" )?;
+    arch.connect_instance_unbound_by_name( "left" )?;
+    arch.connect_instance_unbound_by_name( "right" )?;
+    arch.promote_unbound( & PrefixPolicy::InstanceName )?;
+
+    let mut vhdl_file = VhdlFile::new( OUTPUT_FILE );
+    vhdl_file.add_entity( sub );
+    vhdl_file.add_architecture( arch );
+    vhdl_file.write()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utility::*;
+    #[test]
+    fn promote_unbound() -> Result< (), Box< dyn Error > > {
+        {
+            main()?;
+            compare_files_with_header( OUTPUT_FILE, EXPECTED_FILE )?;
+        }
+        Ok(())
+    }
+}